@@ -0,0 +1,250 @@
+use std::cell::RefCell;
+use std::hint::black_box;
+
+use bevy::audio::AudioPlayer;
+use bevy::ecs::schedule::ScheduleLabel;
+use bevy::ecs::system::RunSystemOnce;
+use bevy::math::Vec3;
+use bevy::prelude::{Entity, Events, IntoSystemConfigs, Schedule, With};
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion};
+
+use bevy_ball::arena::Arena;
+use bevy_ball::game::elapsed_time::ElapsedTime;
+use bevy_ball::game::enemy::{
+    bounce_colliding_enemies, enemy_movement, update_enemy_direction_when_out_of_bound, Enemy,
+};
+use bevy_ball::game::lives::LostLife;
+use bevy_ball::game::movement::confine_movement;
+use bevy_ball::game::player::{
+    on_hit_enemy_emit_collide_event, on_hit_star_emit_collide_event, CollidedWithEnemy,
+    CollidedWithStar,
+};
+use bevy_ball::game::random::GameRng;
+use bevy_ball::game::spatial_index::rebuild_spatial_index;
+use bevy_ball::harness::SimulationHarness;
+use bevy_ball::helpers::MovementHelper;
+
+/// Enemies and stars of the arenas the simulation is measured in
+const CROWDS: [(usize, usize); 3] = [(10, 5), (100, 20), (1_000, 100)];
+
+/// Seconds between two spawns, long enough that nothing spawns while measuring
+const NEVER: f32 = 1.0e9;
+
+/// Harness in the middle of a run of `enemy_count` enemies and `star_count` stars.
+///
+/// The run stays that way while it is measured: nothing spawns and the first wave never ends.
+fn crowded_run(enemy_count: usize, star_count: usize) -> SimulationHarness {
+    let mut harness = SimulationHarness::empty_run_with(|config| {
+        // Power-ups would pile up over the iterations, which all run on the same arena.
+        config.power_up.spawn_time = NEVER;
+        for wave in config.waves.iter_mut() {
+            wave.enemy_spawn_time = NEVER;
+            wave.star_spawn_time = NEVER;
+            wave.duration = None;
+            wave.score = None;
+        }
+    });
+
+    let arena = harness.arena();
+    let mut rng = GameRng::from_seed(0);
+    for _ in 0..enemy_count {
+        let translation = arena.random_point(&mut rng);
+        let angle = rng.random_f32() * std::f32::consts::TAU;
+        harness.spawn_enemy(translation, Vec3::new(angle.cos(), angle.sin(), 0.0));
+    }
+    for _ in 0..star_count {
+        let translation = arena.random_point(&mut rng);
+        harness.spawn_star(translation);
+    }
+    harness
+}
+
+/// Where the enemies of a crowded run started and how it stood, to put it back before every
+/// measured iteration. Otherwise each iteration would measure the arena the previous one left.
+struct Crowd {
+    enemies: Vec<(Entity, Vec3, Vec3)>,
+    lives: Option<u32>,
+}
+
+impl Crowd {
+    fn of(harness: &mut SimulationHarness) -> Self {
+        let enemies = harness
+            .enemies()
+            .into_iter()
+            .map(|enemy| {
+                let translation = harness.translation(enemy).unwrap();
+                let velocity = harness.world().get::<Enemy>(enemy).unwrap().velocity();
+                (enemy, translation, velocity)
+            })
+            .collect();
+        Self {
+            enemies,
+            lives: harness.lives(),
+        }
+    }
+
+    fn restore(&self, harness: &mut SimulationHarness) {
+        for (enemy, translation, velocity) in self.enemies.iter().copied() {
+            harness.teleport(enemy, translation);
+            harness
+                .world_mut()
+                .get_mut::<Enemy>(enemy)
+                .unwrap()
+                .set_velocity(velocity, f32::INFINITY);
+        }
+        if let Some(lives) = self.lives {
+            harness.set_lives(lives);
+        }
+        harness.world_mut().insert_resource(ElapsedTime::default());
+
+        // Bounces play sounds and hits send events, none of which the next iteration should see.
+        let world = harness.world_mut();
+        let sounds: Vec<Entity> = world
+            .query_filtered::<Entity, With<AudioPlayer>>()
+            .iter(world)
+            .collect();
+        for sound in sounds {
+            world.despawn(sound);
+        }
+        world.resource_mut::<Events<CollidedWithEnemy>>().clear();
+        world.resource_mut::<Events<CollidedWithStar>>().clear();
+        world.resource_mut::<Events<LostLife>>().clear();
+        world.run_system_once(rebuild_spatial_index).unwrap();
+    }
+}
+
+/// Whole simulation ticks, without a player so the run never ends.
+fn tick(criterion: &mut Criterion) {
+    let mut group = criterion.benchmark_group("simulation/tick");
+    for (enemy_count, star_count) in CROWDS {
+        let mut harness = crowded_run(enemy_count, star_count);
+        let player = harness.player().unwrap();
+        harness.world_mut().despawn(player);
+        let crowd = Crowd::of(&mut harness);
+        let harness = RefCell::new(harness);
+
+        group.bench_function(
+            BenchmarkId::from_parameter(format!("{enemy_count}_enemies_{star_count}_stars")),
+            |bencher| {
+                bencher.iter_batched(
+                    || crowd.restore(&mut harness.borrow_mut()),
+                    |()| {
+                        harness.borrow_mut().tick();
+                    },
+                    BatchSize::PerIteration,
+                )
+            },
+        );
+    }
+    group.finish();
+}
+
+#[derive(ScheduleLabel, Debug, Clone, PartialEq, Eq, Hash)]
+struct Measured;
+
+/// Run a single system of a tick, in the world of a run in progress
+fn bench_system<M>(
+    criterion: &mut Criterion,
+    name: &str,
+    system: impl IntoSystemConfigs<M> + Copy,
+) {
+    let mut group = criterion.benchmark_group(format!("simulation/{name}"));
+    for (enemy_count, star_count) in CROWDS {
+        let mut harness = crowded_run(enemy_count, star_count);
+        let crowd = Crowd::of(&mut harness);
+        let harness = RefCell::new(harness);
+        let mut schedule = Schedule::new(Measured);
+        schedule.add_systems(system);
+
+        group.bench_function(
+            BenchmarkId::from_parameter(format!("{enemy_count}_enemies_{star_count}_stars")),
+            |bencher| {
+                bencher.iter_batched(
+                    || crowd.restore(&mut harness.borrow_mut()),
+                    |()| schedule.run(harness.borrow_mut().world_mut()),
+                    BatchSize::PerIteration,
+                )
+            },
+        );
+    }
+    group.finish();
+}
+
+fn systems(criterion: &mut Criterion) {
+    bench_system(criterion, "enemy_movement", enemy_movement);
+    bench_system(
+        criterion,
+        "update_enemy_direction_when_out_of_bound",
+        update_enemy_direction_when_out_of_bound,
+    );
+    bench_system(criterion, "confine_movement", confine_movement);
+    bench_system(criterion, "rebuild_spatial_index", rebuild_spatial_index);
+    bench_system(
+        criterion,
+        "bounce_colliding_enemies",
+        bounce_colliding_enemies,
+    );
+    bench_system(
+        criterion,
+        "on_hit_enemy_emit_collide_event",
+        on_hit_enemy_emit_collide_event,
+    );
+    bench_system(
+        criterion,
+        "on_hit_star_emit_collide_event",
+        on_hit_star_emit_collide_event,
+    );
+}
+
+fn movement_helper(criterion: &mut Criterion) {
+    let arena = Arena::new(1280.0, 720.0);
+    let first = Vec3::new(100.0, 100.0, 0.0);
+    let second = Vec3::new(140.0, 120.0, 0.0);
+
+    let mut group = criterion.benchmark_group("movement_helper");
+    group.bench_function("confine", |bencher| {
+        bencher.iter(|| {
+            MovementHelper::confine(
+                black_box(&arena),
+                black_box(Vec3::new(-10.0, 800.0, 0.0)),
+                black_box(64.0),
+            )
+        })
+    });
+    group.bench_function("is_collided", |bencher| {
+        bencher.iter(|| {
+            MovementHelper::is_collided(
+                black_box(64.0),
+                black_box(first),
+                black_box(64.0),
+                black_box(second),
+            )
+        })
+    });
+    group.bench_function("separate", |bencher| {
+        bencher.iter(|| {
+            MovementHelper::separate(
+                black_box(64.0),
+                black_box(first),
+                black_box(32.0),
+                black_box(second),
+            )
+        })
+    });
+    group.bench_function("elastic_bounce", |bencher| {
+        bencher.iter(|| {
+            MovementHelper::elastic_bounce(
+                black_box(64.0),
+                black_box(first),
+                black_box(Vec3::X),
+                black_box(32.0),
+                black_box(second),
+                black_box(Vec3::NEG_X),
+            )
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, tick, systems, movement_helper);
+criterion_main!(benches);
@@ -0,0 +1,90 @@
+use bevy::math::Vec3;
+use bevy::prelude::{Entity, World};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+
+use bevy_ball::game::random::GameRng;
+use bevy_ball::game::spatial_index::SpatialIndex;
+use bevy_ball::helpers::MovementHelper;
+
+const UNIT_SIZE: f32 = 64.0;
+
+/// `count` units spread over a square arena, as crowded whatever their number
+fn units(count: usize) -> Vec<(Entity, Vec3, f32)> {
+    let mut world = World::new();
+    let mut rng = GameRng::from_seed(0);
+    let side = (count as f32).sqrt() * UNIT_SIZE * 2.0;
+    (0..count)
+        .map(|_| {
+            let translation = Vec3::new(rng.random_f32() * side, rng.random_f32() * side, 0.0);
+            (world.spawn_empty().id(), translation, UNIT_SIZE)
+        })
+        .collect()
+}
+
+fn rebuild(criterion: &mut Criterion) {
+    let mut group = criterion.benchmark_group("spatial_index/rebuild");
+    for count in [100, 1_000, 10_000] {
+        let units = units(count);
+        let mut spatial_index = SpatialIndex::default();
+        group.bench_with_input(
+            BenchmarkId::from_parameter(count),
+            &units,
+            |bencher, units| {
+                bencher.iter(|| spatial_index.rebuild(units.iter().copied()));
+            },
+        );
+    }
+    group.finish();
+}
+
+/// Find what every unit collides with, as the collision systems would with the index or without.
+fn colliding_with_every_unit(criterion: &mut Criterion) {
+    let mut group = criterion.benchmark_group("spatial_index/colliding_with_every_unit");
+    for count in [100, 1_000, 10_000] {
+        let units = units(count);
+        let mut spatial_index = SpatialIndex::default();
+        spatial_index.rebuild(units.iter().copied());
+
+        group.bench_with_input(BenchmarkId::new("grid", count), &units, |bencher, units| {
+            bencher.iter(|| {
+                units
+                    .iter()
+                    .map(|(_, translation, size)| {
+                        spatial_index.colliding_with(*translation, *size).count()
+                    })
+                    .sum::<usize>()
+            });
+        });
+        // Checking every pair at 10k units takes too long to be worth measuring.
+        if count <= 1_000 {
+            group.bench_with_input(
+                BenchmarkId::new("every_pair", count),
+                &units,
+                |bencher, units| {
+                    bencher.iter(|| {
+                        units
+                            .iter()
+                            .map(|(_, first_translation, first_size)| {
+                                units
+                                    .iter()
+                                    .filter(|(_, second_translation, second_size)| {
+                                        MovementHelper::is_collided(
+                                            *first_size,
+                                            *first_translation,
+                                            *second_size,
+                                            *second_translation,
+                                        )
+                                    })
+                                    .count()
+                            })
+                            .sum::<usize>()
+                    });
+                },
+            );
+        }
+    }
+    group.finish();
+}
+
+criterion_group!(benches, rebuild, colliding_with_every_unit);
+criterion_main!(benches);
@@ -0,0 +1,154 @@
+use bevy::math::{UVec2, Vec3};
+use bevy::prelude::{
+    App, Camera, Component, DetectChanges, OrthographicProjection, Plugin, Query, Ref, Res,
+    Resource, Transform, Update, Window, With,
+};
+use bevy::render::camera::{ScalingMode, Viewport};
+use bevy::window::PrimaryWindow;
+
+use crate::game::random::GameRng;
+
+pub struct ArenaPlugin;
+
+impl Plugin for ArenaPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Arena>()
+            .add_systems(Update, fit_camera_to_window);
+    }
+}
+
+pub const ARENA_WIDTH: f32 = 1280.0;
+pub const ARENA_HEIGHT: f32 = 720.0;
+/// Render layer of the [`LetterboxCamera`], which nothing is drawn on.
+pub const LETTERBOX_RENDER_LAYER: usize = 31;
+/// Draws of a [`SpawnArea`] point before settling for one in the cleared disc.
+const SPAWN_ATTEMPTS: usize = 16;
+
+/// Playing field, in logical units from `(0, 0)` to `(width, height)`.
+///
+/// Gameplay only ever reads the arena, never the window: the camera scales the arena to fit the
+/// window, so resizing the window does not move the walls.
+#[derive(Resource, Debug, Clone, Copy, PartialEq)]
+pub struct Arena {
+    pub width: f32,
+    pub height: f32,
+}
+
+impl Default for Arena {
+    fn default() -> Self {
+        Self::new(ARENA_WIDTH, ARENA_HEIGHT)
+    }
+}
+
+impl Arena {
+    pub fn new(width: f32, height: f32) -> Self {
+        Self { width, height }
+    }
+
+    pub fn center(&self) -> Vec3 {
+        Vec3::new(self.width / 2.0, self.height / 2.0, 0.0)
+    }
+
+    /// Random point of the arena
+    pub fn random_point(&self, rng: &mut GameRng) -> Vec3 {
+        let x = rng.random_f32() * self.width;
+        let y = rng.random_f32() * self.height;
+        Vec3::new(x, y, 0.0)
+    }
+
+    /// Area random spawns land in, leaving `clear_radius` around `keep_clear` free
+    pub fn spawn_area(&self, keep_clear: Vec3, clear_radius: f32) -> SpawnArea {
+        SpawnArea {
+            arena: *self,
+            keep_clear,
+            clear_radius,
+        }
+    }
+
+    /// Largest area of a `window_size` window showing the whole arena undistorted, as its
+    /// `(position, size)` in physical pixels. The rest of the window is left as borders.
+    pub fn letterbox(&self, window_size: UVec2) -> (UVec2, UVec2) {
+        let scale = (window_size.x as f32 / self.width).min(window_size.y as f32 / self.height);
+        let size = UVec2::new(
+            ((self.width * scale).round() as u32).clamp(1, window_size.x.max(1)),
+            ((self.height * scale).round() as u32).clamp(1, window_size.y.max(1)),
+        );
+        let position = (window_size.saturating_sub(size)) / 2;
+        (position, size)
+    }
+
+    pub fn projection(&self) -> OrthographicProjection {
+        OrthographicProjection {
+            scaling_mode: ScalingMode::Fixed {
+                width: self.width,
+                height: self.height,
+            },
+            ..OrthographicProjection::default_2d()
+        }
+    }
+}
+
+/// Part of the arena enemies and stars spawn in: all of it but a disc around `keep_clear`, the
+/// player, so nothing spawns on top of it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SpawnArea {
+    pub arena: Arena,
+    pub keep_clear: Vec3,
+    pub clear_radius: f32,
+}
+
+impl SpawnArea {
+    /// Random point of the area. An arena too small to leave room gets the last of a few draws.
+    pub fn random_point(&self, rng: &mut GameRng) -> Vec3 {
+        let mut point = self.arena.random_point(rng);
+        for _ in 1..SPAWN_ATTEMPTS {
+            if point.distance(self.keep_clear) >= self.clear_radius {
+                break;
+            }
+            point = self.arena.random_point(rng);
+        }
+        point
+    }
+}
+
+/// Camera showing the arena, letterboxed to fit the window.
+#[derive(Component)]
+pub struct ArenaCamera {}
+
+/// Camera drawing nothing but the clear color over the whole window, before the [`ArenaCamera`],
+/// so the letterbox borders never show what was left there.
+#[derive(Component)]
+pub struct LetterboxCamera {}
+
+pub fn fit_camera_to_window(
+    arena: Res<Arena>,
+    window_query: Query<Ref<Window>, With<PrimaryWindow>>,
+    mut camera_query: Query<
+        (&mut Camera, &mut OrthographicProjection, &mut Transform),
+        With<ArenaCamera>,
+    >,
+) {
+    let Ok(window) = window_query.get_single() else {
+        return;
+    };
+    if !window.is_changed() && !arena.is_changed() {
+        return;
+    }
+
+    let window_size = window.physical_size();
+    // A minimized window has no area to draw on, keep the last viewport.
+    if window_size.x == 0 || window_size.y == 0 {
+        return;
+    }
+
+    let (position, size) = arena.letterbox(window_size);
+    for (mut camera, mut projection, mut transform) in camera_query.iter_mut() {
+        camera.viewport = Some(Viewport {
+            physical_position: position,
+            physical_size: size,
+            ..Viewport::default()
+        });
+        *projection = arena.projection();
+        transform.translation = arena.center();
+    }
+}
@@ -0,0 +1,743 @@
+use std::fmt::{Display, Formatter};
+
+use bevy::asset::io::Reader;
+use bevy::asset::{Asset, AssetApp, AssetLoadFailedEvent, AssetLoader, LoadContext};
+use bevy::prelude::{
+    info, warn, App, AssetEvent, AssetServer, Assets, Commands, EventReader, Handle, Plugin, Res,
+    ResMut, Resource, Startup, TypePath, Update,
+};
+use ron::de::SpannedError;
+use serde::{Deserialize, Serialize};
+
+use crate::game::difficulty::{DifficultyCurve, MAX_CURVE_STEEPNESS};
+use crate::game::enemy_behaviour::{EnemyKind, EnemyWeight};
+
+pub struct GameConfigPlugin;
+
+impl Plugin for GameConfigPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<GameConfig>()
+            .register_asset_loader(GameConfigLoader)
+            .init_resource::<GameConfig>()
+            .init_resource::<GameConfigWarning>()
+            .add_systems(Startup, load_game_config)
+            .add_systems(
+                Update,
+                (on_game_config_loaded_apply, on_game_config_load_failed_warn),
+            );
+    }
+}
+
+/// Path of the tuning file, relative to the `assets` folder.
+pub const GAME_CONFIG_PATH: &str = "config/game.config.ron";
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// Tuning of the gameplay, loaded from [`GAME_CONFIG_PATH`].
+///
+/// Systems read the `GameConfig` resource, which holds the defaults until the file is loaded.
+/// With the `hot-reload` feature, edits to the file are applied live; an invalid file leaves the
+/// resource as it was.
+#[derive(Asset, TypePath, Resource, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GameConfig {
+    pub player: PlayerConfig,
+    pub enemy: EnemyConfig,
+    pub star: StarConfig,
+    pub power_up: PowerUpConfig,
+    pub gamepad: GamepadConfig,
+    pub pointer: PointerConfig,
+    /// Seconds the game holds still between two waves
+    pub intermission_time: f32,
+    /// Waves of a run, in order. The last one goes on until the end of the run.
+    pub waves: Vec<WaveConfig>,
+    pub difficulty: DifficultyConfig,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlayerConfig {
+    /// Arena units per second
+    pub speed: f32,
+    pub size: f32,
+    /// Hits the player takes before the run is over
+    pub lives: u32,
+    /// Seconds the player cannot be hit after losing a life
+    pub invulnerability_time: f32,
+    /// Arena units around the respawn point that enemies are pushed out of
+    pub respawn_clear_radius: f32,
+    /// Arena units around the player that enemies and stars do not spawn in
+    pub spawn_clear_radius: f32,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EnemyConfig {
+    /// Arena units per second, before the multiplier of the wave
+    pub speed: f32,
+    pub size: f32,
+    /// Most an enemy can speed up bouncing off faster enemies, as a multiplier of `speed`
+    pub max_bounce_speed_multiplier: f32,
+    pub behaviour: EnemyBehaviourConfig,
+}
+
+/// Tuning of the enemy kinds moving other than in a straight line.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EnemyBehaviourConfig {
+    /// Radians per second a homing enemy turns toward the player
+    pub homing_turn_rate: f32,
+    /// Seconds a zig-zagging enemy veers to one side before veering to the other
+    pub zig_zag_period: f32,
+    /// Radians a zig-zagging enemy veers off its heading, less than a right angle
+    pub zig_zag_angle: f32,
+    /// Arena units from an orbiting enemy to the center of its orbit
+    pub orbit_radius: f32,
+    /// Seconds a charger stands still before dashing
+    pub charger_pause_time: f32,
+    /// Seconds a dash lasts
+    pub charger_dash_time: f32,
+    /// Multiplier of the enemy speed during a dash
+    pub charger_dash_speed_multiplier: f32,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StarConfig {
+    pub size: f32,
+}
+
+/// Tuning of the power-up pickups, and of the effects they give the player.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PowerUpConfig {
+    pub size: f32,
+    /// Seconds between two power-ups spawning
+    pub spawn_time: f32,
+    /// Seconds a shield lasts, unless it absorbs a hit first
+    pub shield_time: f32,
+    pub slow_motion_time: f32,
+    /// Multiplier of the enemy speed during a slow motion
+    pub slow_motion_multiplier: f32,
+    pub magnet_time: f32,
+    /// Arena units from the player within which stars are pulled
+    pub magnet_radius: f32,
+    /// Arena units per second a pulled star moves toward the player
+    pub magnet_speed: f32,
+    pub score_multiplier_time: f32,
+    /// Points per star while the score multiplier lasts
+    pub score_multiplier: u32,
+}
+
+/// Response of the left stick of a gamepad moving the player.
+///
+/// The stick is ignored within `stick_deadzone` of its center. Past it, the tilt is rescaled to
+/// `0..=1` and raised to `stick_response_exponent`, above 1 giving finer control at low speed.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GamepadConfig {
+    pub stick_deadzone: f32,
+    pub stick_response_exponent: f32,
+}
+
+/// Steering of the player towards the mouse cursor or the touch point.
+///
+/// The player heads for the pointer at the speed covering the gap in `smoothing_time`, up to
+/// `player.speed`, so it slows down on arrival instead of overshooting. It stops within
+/// `dead_radius` of the pointer.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PointerConfig {
+    /// Arena units
+    pub dead_radius: f32,
+    /// Seconds
+    pub smoothing_time: f32,
+}
+
+/// One wave of a run. The next wave starts after `duration` seconds or once `score` points are
+/// scored during the wave, whichever comes first. A wave with neither never ends.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WaveConfig {
+    /// Enemies joining the arena when the wave starts
+    pub enemy_count: usize,
+    /// Multiplier of `enemy.speed` during the wave
+    pub enemy_speed_multiplier: f32,
+    /// Seconds between two enemies spawning during the wave
+    pub enemy_spawn_time: f32,
+    /// Kinds of the enemies spawned during the wave, picked at random by weight
+    pub enemy_weights: Vec<EnemyWeight>,
+    /// Stars in the arena when the wave starts
+    pub star_count: usize,
+    /// Seconds between two stars spawning during the wave
+    pub star_spawn_time: f32,
+    pub duration: Option<f32>,
+    pub score: Option<u32>,
+}
+
+/// Ramp of the difficulty over a run, on top of the waves.
+///
+/// The pressure of a run is its elapsed seconds plus `seconds_per_point` for each point scored.
+/// The curve turns it into a difficulty, 1 being full difficulty, which is capped at `cap`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DifficultyConfig {
+    pub curve: DifficultyCurve,
+    pub seconds_per_point: f32,
+    pub cap: f32,
+    /// Extra enemy speed at full difficulty, 0.5 moving enemies 50% faster
+    pub enemy_speed: f32,
+    /// Extra enemy spawn rate at full difficulty, 1.0 spawning enemies twice as often
+    pub enemy_spawn_rate: f32,
+    /// Extra time between two stars at full difficulty, 1.0 spawning stars half as often
+    pub star_scarcity: f32,
+}
+
+impl Default for DifficultyConfig {
+    fn default() -> Self {
+        Self {
+            curve: DifficultyCurve::default(),
+            seconds_per_point: 2.0,
+            cap: 1.0,
+            enemy_speed: 0.5,
+            enemy_spawn_rate: 1.0,
+            star_scarcity: 1.0,
+        }
+    }
+}
+
+impl Default for PlayerConfig {
+    fn default() -> Self {
+        Self {
+            speed: 500.0,
+            size: 64.0,
+            lives: 3,
+            invulnerability_time: 2.0,
+            respawn_clear_radius: 200.0,
+            spawn_clear_radius: 200.0,
+        }
+    }
+}
+
+impl Default for EnemyConfig {
+    fn default() -> Self {
+        Self {
+            speed: 200.0,
+            size: 64.0,
+            max_bounce_speed_multiplier: 2.0,
+            behaviour: EnemyBehaviourConfig::default(),
+        }
+    }
+}
+
+impl Default for EnemyBehaviourConfig {
+    fn default() -> Self {
+        Self {
+            homing_turn_rate: 1.5,
+            zig_zag_period: 0.6,
+            zig_zag_angle: 0.6,
+            orbit_radius: 120.0,
+            charger_pause_time: 1.5,
+            charger_dash_time: 0.6,
+            charger_dash_speed_multiplier: 3.0,
+        }
+    }
+}
+
+impl Default for StarConfig {
+    fn default() -> Self {
+        Self { size: 30.0 }
+    }
+}
+
+impl Default for PowerUpConfig {
+    fn default() -> Self {
+        Self {
+            size: 40.0,
+            spawn_time: 12.0,
+            shield_time: 10.0,
+            slow_motion_time: 5.0,
+            slow_motion_multiplier: 0.5,
+            magnet_time: 8.0,
+            magnet_radius: 250.0,
+            magnet_speed: 400.0,
+            score_multiplier_time: 10.0,
+            score_multiplier: 2,
+        }
+    }
+}
+
+impl Default for GamepadConfig {
+    fn default() -> Self {
+        Self {
+            stick_deadzone: 0.15,
+            stick_response_exponent: 1.5,
+        }
+    }
+}
+
+impl Default for PointerConfig {
+    fn default() -> Self {
+        Self {
+            dead_radius: 4.0,
+            smoothing_time: 0.15,
+        }
+    }
+}
+
+impl Default for GameConfig {
+    fn default() -> Self {
+        Self {
+            player: PlayerConfig::default(),
+            enemy: EnemyConfig::default(),
+            star: StarConfig::default(),
+            power_up: PowerUpConfig::default(),
+            gamepad: GamepadConfig::default(),
+            pointer: PointerConfig::default(),
+            intermission_time: 3.0,
+            waves: vec![
+                WaveConfig::new(4, 1.0, 5.0, 10, 1.0, Some(30.0), Some(10)),
+                WaveConfig::new(2, 1.1, 4.0, 8, 1.5, Some(30.0), Some(15)).with_enemy_weights(
+                    vec![
+                        EnemyWeight::new(EnemyKind::Bouncer, 3.0, 2.0),
+                        EnemyWeight::new(EnemyKind::ZigZag, 1.0, 2.0),
+                    ],
+                ),
+                WaveConfig::new(2, 1.25, 3.5, 8, 1.5, Some(40.0), Some(20)).with_enemy_weights(
+                    vec![
+                        EnemyWeight::new(EnemyKind::Bouncer, 2.0, 1.0),
+                        EnemyWeight::new(EnemyKind::ZigZag, 1.0, 1.0),
+                        EnemyWeight::new(EnemyKind::Homing, 1.0, 2.0),
+                    ],
+                ),
+                WaveConfig::new(3, 1.4, 3.0, 6, 2.0, Some(45.0), Some(25)).with_enemy_weights(
+                    vec![
+                        EnemyWeight::new(EnemyKind::Bouncer, 2.0, 1.0),
+                        EnemyWeight::new(EnemyKind::ZigZag, 1.0, 1.0),
+                        EnemyWeight::new(EnemyKind::Homing, 1.0, 2.0),
+                        EnemyWeight::new(EnemyKind::Charger, 1.0, 2.0),
+                        EnemyWeight::new(EnemyKind::WallHugger, 1.0, 1.0),
+                    ],
+                ),
+                WaveConfig::new(3, 1.6, 2.5, 6, 2.0, None, None).with_enemy_weights(vec![
+                    EnemyWeight::new(EnemyKind::Bouncer, 2.0, 1.0),
+                    EnemyWeight::new(EnemyKind::ZigZag, 1.0, 1.0),
+                    EnemyWeight::new(EnemyKind::Homing, 1.0, 2.0),
+                    EnemyWeight::new(EnemyKind::Orbiter, 1.0, 1.0),
+                    EnemyWeight::new(EnemyKind::Charger, 1.0, 2.0),
+                    EnemyWeight::new(EnemyKind::WallHugger, 1.0, 1.0),
+                ]),
+            ],
+            difficulty: DifficultyConfig::default(),
+        }
+    }
+}
+
+impl WaveConfig {
+    pub fn new(
+        enemy_count: usize,
+        enemy_speed_multiplier: f32,
+        enemy_spawn_time: f32,
+        star_count: usize,
+        star_spawn_time: f32,
+        duration: Option<f32>,
+        score: Option<u32>,
+    ) -> Self {
+        Self {
+            enemy_count,
+            enemy_speed_multiplier,
+            enemy_spawn_time,
+            enemy_weights: vec![EnemyWeight::new(EnemyKind::default(), 1.0, 1.0)],
+            star_count,
+            star_spawn_time,
+            duration,
+            score,
+        }
+    }
+
+    pub fn with_enemy_weights(mut self, enemy_weights: Vec<EnemyWeight>) -> Self {
+        self.enemy_weights = enemy_weights;
+        self
+    }
+}
+
+impl GameConfig {
+    /// Tuning of the wave `number`, counted from 1. Waves past the last one repeat it.
+    pub fn wave(&self, number: u32) -> &WaveConfig {
+        let index = (number.max(1) as usize - 1).min(self.waves.len().saturating_sub(1));
+        &self.waves[index]
+    }
+
+    /// Hash of the tuning, the same on every platform and build, e.g. to tell whether a replay was
+    /// recorded with it
+    pub fn fingerprint(&self) -> u64 {
+        // FNV-1a of the RON text, the hashers of `std` may change between Rust versions.
+        let content = ron::to_string(self).unwrap_or_default();
+        content.bytes().fold(FNV_OFFSET_BASIS, |hash, byte| {
+            (hash ^ byte as u64).wrapping_mul(FNV_PRIME)
+        })
+    }
+
+    /// Parse and validate the content of a tuning file
+    pub fn from_ron(content: &[u8]) -> Result<GameConfig, GameConfigError> {
+        let config: GameConfig = ron::de::from_bytes(content)?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Check that every value makes sense, reporting the first one that does not
+    pub fn validate(&self) -> Result<(), GameConfigError> {
+        Self::at_least("player.speed", self.player.speed, 0.0)?;
+        Self::positive("player.size", self.player.size)?;
+        if self.player.lives == 0 {
+            return Err(GameConfigError::Invalid {
+                field: "player.lives".to_string(),
+                reason: "must be at least 1, got 0".to_string(),
+            });
+        }
+        Self::at_least(
+            "player.invulnerability_time",
+            self.player.invulnerability_time,
+            0.0,
+        )?;
+        Self::at_least(
+            "player.respawn_clear_radius",
+            self.player.respawn_clear_radius,
+            0.0,
+        )?;
+        Self::at_least(
+            "player.spawn_clear_radius",
+            self.player.spawn_clear_radius,
+            0.0,
+        )?;
+        Self::at_least("enemy.speed", self.enemy.speed, 0.0)?;
+        Self::positive("enemy.size", self.enemy.size)?;
+        Self::at_least(
+            "enemy.max_bounce_speed_multiplier",
+            self.enemy.max_bounce_speed_multiplier,
+            1.0,
+        )?;
+        Self::positive("star.size", self.star.size)?;
+        Self::at_least("intermission_time", self.intermission_time, 0.0)?;
+        if self.waves.is_empty() {
+            return Err(GameConfigError::Invalid {
+                field: "waves".to_string(),
+                reason: "must hold at least one wave".to_string(),
+            });
+        }
+        for (index, wave) in self.waves.iter().enumerate() {
+            let field = |name: &str| format!("waves[{}].{}", index, name);
+            Self::at_least(
+                field("enemy_speed_multiplier"),
+                wave.enemy_speed_multiplier,
+                0.0,
+            )?;
+            Self::positive(field("enemy_spawn_time"), wave.enemy_spawn_time)?;
+            Self::positive(field("star_spawn_time"), wave.star_spawn_time)?;
+            if let Some(duration) = wave.duration {
+                Self::positive(field("duration"), duration)?;
+            }
+            // A wave done on its first tick would chain straight into the next intermission.
+            if wave.score == Some(0) {
+                return Err(GameConfigError::Invalid {
+                    field: field("score"),
+                    reason: "must be at least 1, got 0".to_string(),
+                });
+            }
+            for (weight_index, weight) in wave.enemy_weights.iter().enumerate() {
+                let field = |name: &str| {
+                    format!("waves[{}].enemy_weights[{}].{}", index, weight_index, name)
+                };
+                Self::at_least(field("weight"), weight.weight, 0.0)?;
+                Self::at_least(
+                    field("full_difficulty_weight"),
+                    weight.full_difficulty_weight,
+                    0.0,
+                )?;
+            }
+            // Weights move linearly with the difficulty, so checking both ends covers the range.
+            let has_kind_at = |level: f32| {
+                wave.enemy_weights
+                    .iter()
+                    .any(|weight| weight.at_level(level) > 0.0)
+            };
+            if !has_kind_at(0.0) || !has_kind_at(1.0) {
+                return Err(GameConfigError::Invalid {
+                    field: field("enemy_weights"),
+                    reason: "must give some kind a weight at every difficulty".to_string(),
+                });
+            }
+        }
+        self.validate_enemy_behaviour()?;
+        self.validate_power_up()?;
+        self.validate_gamepad()?;
+        Self::at_least("pointer.dead_radius", self.pointer.dead_radius, 0.0)?;
+        Self::positive("pointer.smoothing_time", self.pointer.smoothing_time)?;
+        self.validate_difficulty()
+    }
+
+    fn validate_power_up(&self) -> Result<(), GameConfigError> {
+        let power_up = &self.power_up;
+        Self::positive("power_up.size", power_up.size)?;
+        Self::positive("power_up.spawn_time", power_up.spawn_time)?;
+        Self::positive("power_up.shield_time", power_up.shield_time)?;
+        Self::positive("power_up.slow_motion_time", power_up.slow_motion_time)?;
+        Self::at_least(
+            "power_up.slow_motion_multiplier",
+            power_up.slow_motion_multiplier,
+            0.0,
+        )?;
+        Self::positive("power_up.magnet_time", power_up.magnet_time)?;
+        Self::at_least("power_up.magnet_radius", power_up.magnet_radius, 0.0)?;
+        Self::at_least("power_up.magnet_speed", power_up.magnet_speed, 0.0)?;
+        Self::positive(
+            "power_up.score_multiplier_time",
+            power_up.score_multiplier_time,
+        )?;
+        if power_up.score_multiplier == 0 {
+            return Err(GameConfigError::Invalid {
+                field: "power_up.score_multiplier".to_string(),
+                reason: "must be at least 1, got 0".to_string(),
+            });
+        }
+        Ok(())
+    }
+
+    fn validate_gamepad(&self) -> Result<(), GameConfigError> {
+        let gamepad = &self.gamepad;
+        Self::at_least("gamepad.stick_deadzone", gamepad.stick_deadzone, 0.0)?;
+        if gamepad.stick_deadzone >= 1.0 {
+            return Err(GameConfigError::Invalid {
+                field: "gamepad.stick_deadzone".to_string(),
+                reason: format!("must be less than 1, got {}", gamepad.stick_deadzone),
+            });
+        }
+        Self::positive(
+            "gamepad.stick_response_exponent",
+            gamepad.stick_response_exponent,
+        )
+    }
+
+    fn validate_enemy_behaviour(&self) -> Result<(), GameConfigError> {
+        let behaviour = &self.enemy.behaviour;
+        Self::at_least(
+            "enemy.behaviour.homing_turn_rate",
+            behaviour.homing_turn_rate,
+            0.0,
+        )?;
+        Self::positive("enemy.behaviour.zig_zag_period", behaviour.zig_zag_period)?;
+        Self::at_least(
+            "enemy.behaviour.zig_zag_angle",
+            behaviour.zig_zag_angle,
+            0.0,
+        )?;
+        if behaviour.zig_zag_angle >= std::f32::consts::FRAC_PI_2 {
+            return Err(GameConfigError::Invalid {
+                field: "enemy.behaviour.zig_zag_angle".to_string(),
+                reason: format!(
+                    "must be less than a right angle, got {}",
+                    behaviour.zig_zag_angle
+                ),
+            });
+        }
+        Self::positive("enemy.behaviour.orbit_radius", behaviour.orbit_radius)?;
+        Self::positive(
+            "enemy.behaviour.charger_pause_time",
+            behaviour.charger_pause_time,
+        )?;
+        Self::positive(
+            "enemy.behaviour.charger_dash_time",
+            behaviour.charger_dash_time,
+        )?;
+        Self::at_least(
+            "enemy.behaviour.charger_dash_speed_multiplier",
+            behaviour.charger_dash_speed_multiplier,
+            0.0,
+        )
+    }
+
+    fn validate_difficulty(&self) -> Result<(), GameConfigError> {
+        let difficulty = &self.difficulty;
+        match &difficulty.curve {
+            DifficultyCurve::Linear { ramp_time } => {
+                Self::positive("difficulty.curve.ramp_time", *ramp_time)?;
+            }
+            DifficultyCurve::Exponential {
+                ramp_time,
+                steepness,
+            } => {
+                Self::positive("difficulty.curve.ramp_time", *ramp_time)?;
+                Self::at_least("difficulty.curve.steepness", *steepness, 0.0)?;
+                if *steepness > MAX_CURVE_STEEPNESS {
+                    return Err(GameConfigError::Invalid {
+                        field: "difficulty.curve.steepness".to_string(),
+                        reason: format!(
+                            "must be at most {}, got {}",
+                            MAX_CURVE_STEEPNESS, steepness
+                        ),
+                    });
+                }
+            }
+            DifficultyCurve::Piecewise { points } => {
+                if points.is_empty() {
+                    return Err(GameConfigError::Invalid {
+                        field: "difficulty.curve.points".to_string(),
+                        reason: "must hold at least one point".to_string(),
+                    });
+                }
+                for (index, (pressure, level)) in points.iter().enumerate() {
+                    let field = |name: &str| format!("difficulty.curve.points[{}].{}", index, name);
+                    Self::at_least(field("pressure"), *pressure, 0.0)?;
+                    Self::at_least(field("difficulty"), *level, 0.0)?;
+                }
+                if points.windows(2).any(|window| window[0].0 >= window[1].0) {
+                    return Err(GameConfigError::Invalid {
+                        field: "difficulty.curve.points".to_string(),
+                        reason: "must be sorted by increasing pressure".to_string(),
+                    });
+                }
+            }
+        }
+        Self::at_least(
+            "difficulty.seconds_per_point",
+            difficulty.seconds_per_point,
+            0.0,
+        )?;
+        Self::at_least("difficulty.cap", difficulty.cap, 0.0)?;
+        Self::at_least("difficulty.enemy_speed", difficulty.enemy_speed, 0.0)?;
+        Self::at_least(
+            "difficulty.enemy_spawn_rate",
+            difficulty.enemy_spawn_rate,
+            0.0,
+        )?;
+        Self::at_least("difficulty.star_scarcity", difficulty.star_scarcity, 0.0)?;
+        Ok(())
+    }
+
+    fn at_least(field: impl Into<String>, value: f32, minimum: f32) -> Result<(), GameConfigError> {
+        if value.is_finite() && value >= minimum {
+            Ok(())
+        } else {
+            Err(GameConfigError::Invalid {
+                field: field.into(),
+                reason: format!("must be at least {}, got {}", minimum, value),
+            })
+        }
+    }
+
+    fn positive(field: impl Into<String>, value: f32) -> Result<(), GameConfigError> {
+        if value.is_finite() && value > 0.0 {
+            Ok(())
+        } else {
+            Err(GameConfigError::Invalid {
+                field: field.into(),
+                reason: format!("must be greater than 0, got {}", value),
+            })
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum GameConfigError {
+    Io(std::io::Error),
+    Parse(SpannedError),
+    Invalid { field: String, reason: String },
+}
+
+impl Display for GameConfigError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GameConfigError::Io(error) => write!(f, "could not read the game config: {}", error),
+            GameConfigError::Parse(error) => write!(f, "invalid game config: {}", error),
+            GameConfigError::Invalid { field, reason } => {
+                write!(f, "invalid game config: {} {}", field, reason)
+            }
+        }
+    }
+}
+
+impl std::error::Error for GameConfigError {}
+
+impl From<std::io::Error> for GameConfigError {
+    fn from(error: std::io::Error) -> Self {
+        GameConfigError::Io(error)
+    }
+}
+
+impl From<SpannedError> for GameConfigError {
+    fn from(error: SpannedError) -> Self {
+        GameConfigError::Parse(error)
+    }
+}
+
+#[derive(Default)]
+pub struct GameConfigLoader;
+
+impl AssetLoader for GameConfigLoader {
+    type Asset = GameConfig;
+    type Settings = ();
+    type Error = GameConfigError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<GameConfig, GameConfigError> {
+        let mut content = Vec::new();
+        reader.read_to_end(&mut content).await?;
+        GameConfig::from_ron(&content)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["config.ron"]
+    }
+}
+
+/// Keeps the tuning file loaded.
+#[derive(Resource)]
+pub struct GameConfigHandle {
+    pub handle: Handle<GameConfig>,
+}
+
+/// Why the last edit of the tuning file was not applied, shown on screen until a valid edit.
+#[derive(Resource, Debug, Default)]
+pub struct GameConfigWarning {
+    pub message: Option<String>,
+}
+
+pub fn load_game_config(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(GameConfigHandle {
+        handle: asset_server.load(GAME_CONFIG_PATH),
+    });
+}
+
+pub fn on_game_config_loaded_apply(
+    mut event_reader: EventReader<AssetEvent<GameConfig>>,
+    game_config_handle: Option<Res<GameConfigHandle>>,
+    game_configs: Res<Assets<GameConfig>>,
+    mut game_config: ResMut<GameConfig>,
+    mut game_config_warning: ResMut<GameConfigWarning>,
+) {
+    let Some(game_config_handle) = game_config_handle else {
+        return;
+    };
+
+    for event in event_reader.read() {
+        let is_game_config = event.is_loaded_with_dependencies(&game_config_handle.handle)
+            || event.is_modified(&game_config_handle.handle);
+        if !is_game_config {
+            continue;
+        }
+
+        game_config_warning.message = None;
+        if let Some(loaded_config) = game_configs.get(&game_config_handle.handle) {
+            // A reload sends several events, only touch the resource once per change.
+            if *game_config != *loaded_config {
+                *game_config = loaded_config.clone();
+                info!("Game config applied: {:?}", *game_config);
+            }
+        }
+    }
+}
+
+pub fn on_game_config_load_failed_warn(
+    mut event_reader: EventReader<AssetLoadFailedEvent<GameConfig>>,
+    mut game_config_warning: ResMut<GameConfigWarning>,
+) {
+    for event in event_reader.read() {
+        warn!("Keeping the previous game config: {}", event.error);
+        game_config_warning.message = Some(event.error.to_string());
+    }
+}
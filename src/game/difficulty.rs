@@ -0,0 +1,156 @@
+use bevy::prelude::{
+    in_state, App, Commands, FixedUpdate, IntoSystemConfigs, OnEnter, OnExit, Plugin, Res, ResMut,
+    Resource,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::game::config::{DifficultyConfig, GameConfig};
+use crate::game::elapsed_time::{tick_elapsed_time, ElapsedTime};
+use crate::game::score::Score;
+use crate::game::simulation::{is_simulating, SimulationSet};
+use crate::ApplicationState;
+
+pub struct DifficultyPlugin;
+
+impl Plugin for DifficultyPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            OnEnter(ApplicationState::InGame),
+            insert_difficulty_director_resource,
+        )
+        .add_systems(
+            OnExit(ApplicationState::InGame),
+            remove_difficulty_director_resource,
+        )
+        .add_systems(
+            FixedUpdate,
+            update_difficulty_director
+                // Always from the elapsed time of this tick, for replays to ramp up the same.
+                .after(tick_elapsed_time)
+                .before(SimulationSet::Movement)
+                .run_if(in_state(ApplicationState::InGame))
+                .run_if(is_simulating),
+        );
+    }
+}
+
+/// Steepest [`DifficultyCurve::Exponential`], past it the curve overflows `f32` before reaching
+/// full difficulty.
+pub const MAX_CURVE_STEEPNESS: f32 = 64.0;
+
+/// Shape of the difficulty over the pressure of a run, in seconds of run time.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum DifficultyCurve {
+    /// Full difficulty after `ramp_time` seconds, at a steady pace
+    Linear { ramp_time: f32 },
+    /// Full difficulty after `ramp_time` seconds, slow at first then faster and faster.
+    /// The higher the `steepness`, the later the difficulty picks up, up to [`MAX_CURVE_STEEPNESS`].
+    Exponential { ramp_time: f32, steepness: f32 },
+    /// Difficulty at given pressures, linear in between and held past the last point.
+    /// Points are `(pressure, difficulty)`, sorted by pressure.
+    Piecewise { points: Vec<(f32, f32)> },
+}
+
+impl Default for DifficultyCurve {
+    fn default() -> Self {
+        DifficultyCurve::Linear { ramp_time: 180.0 }
+    }
+}
+
+impl DifficultyCurve {
+    /// Difficulty at `pressure`, where 1 is the full difficulty, not capped yet
+    pub fn level(&self, pressure: f32) -> f32 {
+        let pressure = pressure.max(0.0);
+        match self {
+            DifficultyCurve::Linear { ramp_time } => pressure / ramp_time,
+            DifficultyCurve::Exponential {
+                ramp_time,
+                steepness,
+            } => {
+                let progress = pressure / ramp_time;
+                // A flat exponential is a straight line, and would divide by zero below.
+                if steepness.abs() < f32::EPSILON {
+                    progress
+                } else {
+                    (steepness * progress).exp_m1() / steepness.exp_m1()
+                }
+            }
+            DifficultyCurve::Piecewise { points } => {
+                let Some(first) = points.first() else {
+                    return 0.0;
+                };
+                if pressure <= first.0 {
+                    return first.1;
+                }
+                for window in points.windows(2) {
+                    let (start, end) = (window[0], window[1]);
+                    if pressure <= end.0 {
+                        let progress = (pressure - start.0) / (end.0 - start.0);
+                        return start.1 + (end.1 - start.1) * progress;
+                    }
+                }
+                points[points.len() - 1].1
+            }
+        }
+    }
+}
+
+/// How hard the current run is, ramping up from its elapsed time and score.
+///
+/// Gameplay scales the enemy speed, the enemy spawn rate and the time between two stars by the
+/// multipliers, on top of the tuning of the current wave.
+#[derive(Resource, Debug, Clone, Copy, PartialEq)]
+pub struct DifficultyDirector {
+    /// From 0 at the start of a run, capped by the [`DifficultyConfig`]
+    pub level: f32,
+    pub enemy_speed_multiplier: f32,
+    pub enemy_spawn_rate_multiplier: f32,
+    /// Multiplier of the time between two stars, the higher the scarcer
+    pub star_scarcity_multiplier: f32,
+}
+
+impl Default for DifficultyDirector {
+    fn default() -> Self {
+        Self::at_level(0.0, &DifficultyConfig::default())
+    }
+}
+
+impl DifficultyDirector {
+    pub fn at_level(level: f32, config: &DifficultyConfig) -> Self {
+        Self {
+            level,
+            enemy_speed_multiplier: 1.0 + level * config.enemy_speed,
+            enemy_spawn_rate_multiplier: 1.0 + level * config.enemy_spawn_rate,
+            star_scarcity_multiplier: 1.0 + level * config.star_scarcity,
+        }
+    }
+
+    /// Director of a run after `seconds` of simulated time with `score` points
+    pub fn from_progress(seconds: f32, score: u32, config: &DifficultyConfig) -> Self {
+        let pressure = seconds + score as f32 * config.seconds_per_point;
+        let level = config.curve.level(pressure).clamp(0.0, config.cap);
+        Self::at_level(level, config)
+    }
+}
+
+pub fn insert_difficulty_director_resource(mut commands: Commands) {
+    commands.insert_resource(DifficultyDirector::default());
+}
+
+pub fn remove_difficulty_director_resource(mut commands: Commands) {
+    commands.remove_resource::<DifficultyDirector>();
+}
+
+pub fn update_difficulty_director(
+    elapsed_time: Res<ElapsedTime>,
+    score: Res<Score>,
+    config: Res<GameConfig>,
+    mut difficulty_director: ResMut<DifficultyDirector>,
+) {
+    let updated =
+        DifficultyDirector::from_progress(elapsed_time.seconds(), score.value, &config.difficulty);
+    // Only touch the resource on change, the debug overlay redraws on change.
+    if *difficulty_director != updated {
+        *difficulty_director = updated;
+    }
+}
@@ -0,0 +1,54 @@
+use bevy::prelude::{
+    in_state, App, Commands, FixedUpdate, IntoSystemConfigs, OnEnter, OnExit, Plugin, Res, ResMut,
+    Resource, Time,
+};
+use bevy::time::Stopwatch;
+
+use crate::game::simulation::{is_simulating, SimulationSet};
+use crate::ApplicationState;
+
+pub struct ElapsedTimePlugin;
+
+impl Plugin for ElapsedTimePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            OnEnter(ApplicationState::InGame),
+            insert_elapsed_time_resource,
+        )
+        .add_systems(
+            OnExit(ApplicationState::InGame),
+            remove_elapsed_time_resource,
+        )
+        .add_systems(
+            FixedUpdate,
+            tick_elapsed_time
+                .in_set(SimulationSet::Input)
+                .run_if(in_state(ApplicationState::InGame))
+                .run_if(is_simulating),
+        );
+    }
+}
+
+/// Simulated time of the current run, not counting pauses.
+#[derive(Resource, Default)]
+pub struct ElapsedTime {
+    pub stopwatch: Stopwatch,
+}
+
+impl ElapsedTime {
+    pub fn seconds(&self) -> f32 {
+        self.stopwatch.elapsed_secs()
+    }
+}
+
+pub fn insert_elapsed_time_resource(mut commands: Commands) {
+    commands.insert_resource(ElapsedTime::default());
+}
+
+pub fn remove_elapsed_time_resource(mut commands: Commands) {
+    commands.remove_resource::<ElapsedTime>();
+}
+
+pub fn tick_elapsed_time(time: Res<Time>, mut elapsed_time: ResMut<ElapsedTime>) {
+    elapsed_time.stopwatch.tick(time.delta());
+}
@@ -1,14 +1,25 @@
+use std::time::Duration;
+
 use bevy::math::Vec3;
 use bevy::prelude::{
-    in_state, App, AssetServer, Bundle, Commands, Component, Entity, IntoSystemConfigs, Name,
-    OnEnter, OnExit, Plugin, Query, Res, ResMut, Resource, Sprite, SpriteBundle, Time, Timer,
-    TimerMode, Transform, Update, Window, With,
+    in_state, resource_changed, App, AssetServer, Bundle, Commands, Component, Entity, FixedUpdate,
+    IntoSystemConfigs, Name, OnEnter, OnExit, Plugin, Query, Res, ResMut, Resource, Sprite,
+    SpriteBundle, Time, Timer, TimerMode, Transform, Update, With,
 };
-use bevy::window::PrimaryWindow;
 
+use crate::arena::{Arena, SpawnArea};
 use crate::asset_handler::AssetHandler;
-use crate::game::{Confined, GameState, Size};
-use crate::helpers::{AudioHelper, RandomHelper};
+use crate::game::config::{on_game_config_loaded_apply, EnemyConfig, GameConfig};
+use crate::game::difficulty::DifficultyDirector;
+use crate::game::enemy_behaviour::{enemy_speed, Bouncer, BouncesOffWalls, EnemyKind, Orbiter};
+use crate::game::player::PlayerSpawnArea;
+use crate::game::power_up::SlowMotion;
+use crate::game::random::{GameRng, RandomSet};
+use crate::game::simulation::{is_simulating, InterpolatedTransform};
+use crate::game::spatial_index::SpatialIndex;
+use crate::game::wave::{Wave, WAVE_START};
+use crate::game::{Confined, Size};
+use crate::helpers::{AudioHelper, MovementHelper};
 use crate::{ApplicationState, ScheduleDespawn};
 
 pub struct EnemyPlugin;
@@ -16,30 +27,75 @@ pub struct EnemyPlugin;
 impl Plugin for EnemyPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<EnemySpawnTimer>()
-            .add_systems(OnEnter(ApplicationState::InGame), spawn_initial_enemies)
+            .add_systems(
+                OnEnter(ApplicationState::InGame),
+                (spawn_initial_enemies, reset_enemy_spawn_timer).in_set(RandomSet::Enemy),
+            )
+            .add_systems(
+                WAVE_START,
+                (
+                    on_wave_start_spawn_enemies,
+                    on_wave_start_reset_enemy_spawn_timer,
+                )
+                    .in_set(RandomSet::Enemy),
+            )
             .add_systems(OnExit(ApplicationState::InGame), despawn_all_enemies)
             .add_systems(
-                Update,
+                FixedUpdate,
                 (
                     enemy_movement,
                     update_enemy_direction_when_out_of_bound,
                     tick_spawn_enemy_overtime,
                     spawn_enemy_overtime,
                 )
+                    .chain()
+                    .in_set(RandomSet::Enemy)
+                    .run_if(in_state(ApplicationState::InGame))
+                    .run_if(is_simulating),
+            )
+            .add_systems(
+                FixedUpdate,
+                bounce_colliding_enemies
+                    .in_set(RandomSet::Collision)
                     .run_if(in_state(ApplicationState::InGame))
-                    .run_if(in_state(GameState::Running)),
+                    .run_if(is_simulating),
+            )
+            .add_systems(
+                Update,
+                on_game_config_changed_update_enemies
+                    .after(on_game_config_loaded_apply)
+                    .run_if(resource_changed::<GameConfig>),
             );
     }
 }
 
-pub const NUMBER_OF_ENEMIES: usize = 4;
-pub const ENEMY_SPEED: f32 = 200.0;
-pub const ENEMY_SIZE: f32 = 64.0;
-pub const ENEMY_SPAWN_TIME: f32 = 5.0;
-
 #[derive(Component)]
+#[require(InterpolatedTransform, EnemyKind)]
 pub struct Enemy {
     pub direction: Vec3,
+    /// Multiplier of the speed of every enemy, traded by bouncing off other enemies
+    pub speed_multiplier: f32,
+}
+
+impl Enemy {
+    pub fn new(direction: Vec3) -> Self {
+        Self {
+            direction,
+            speed_multiplier: 1.0,
+        }
+    }
+
+    /// Velocity relative to the speed of every enemy, see [`enemy_speed`]
+    pub fn velocity(&self) -> Vec3 {
+        self.direction * self.speed_multiplier
+    }
+
+    /// Head along `velocity`, at most `max_speed_multiplier` times the speed of every enemy
+    pub fn set_velocity(&mut self, velocity: Vec3, max_speed_multiplier: f32) {
+        // A stopped enemy keeps its heading for when it is hit again.
+        self.direction = velocity.normalize_or(self.direction);
+        self.speed_multiplier = velocity.length().min(max_speed_multiplier);
+    }
 }
 
 #[derive(Bundle)]
@@ -52,26 +108,27 @@ pub struct EnemyBundle {
 }
 
 impl EnemyBundle {
-    pub fn randomize_direction() -> Vec3 {
-        Vec3::new(RandomHelper::random_f32(), RandomHelper::random_f32(), 0.0).normalize()
+    pub fn randomize_direction(rng: &mut GameRng) -> Vec3 {
+        Vec3::new(rng.random_f32(), rng.random_f32(), 0.0).normalize()
     }
 
     pub fn at_randomized_location(
-        window: &Window,
+        spawn_area: &SpawnArea,
+        config: &EnemyConfig,
+        kind: EnemyKind,
         asset_handler: &Res<AssetHandler>,
-    ) -> (Name, Enemy, Confined, Size, Sprite, Transform) {
-        let random_x = RandomHelper::random_f32() * window.width();
-        let random_y = RandomHelper::random_f32() * window.height();
+        rng: &mut GameRng,
+    ) -> (Name, Enemy, EnemyKind, Confined, Size, Sprite, Transform) {
+        let random_location = spawn_area.random_point(rng);
 
         (
             Name::new("Enemy"),
-            Enemy {
-                direction: Self::randomize_direction(),
-            },
+            Enemy::new(Self::randomize_direction(rng)),
+            kind,
             Confined {},
-            Size { value: ENEMY_SIZE },
+            Size { value: config.size },
             Sprite::from_image(asset_handler.enemy_texture.clone()),
-            Transform::from_xyz(random_x, random_y, 0.0),
+            Transform::from_translation(random_location),
         )
     }
 }
@@ -83,8 +140,14 @@ pub struct EnemySpawnTimer {
 
 impl Default for EnemySpawnTimer {
     fn default() -> Self {
+        Self::new(GameConfig::default().wave(1).enemy_spawn_time)
+    }
+}
+
+impl EnemySpawnTimer {
+    pub fn new(spawn_time: f32) -> Self {
         Self {
-            timer: Timer::from_seconds(ENEMY_SPAWN_TIME, TimerMode::Repeating),
+            timer: Timer::from_seconds(spawn_time, TimerMode::Repeating),
         }
     }
 }
@@ -92,68 +155,282 @@ impl Default for EnemySpawnTimer {
 pub fn spawn_initial_enemies(
     mut commands: Commands,
     asset_handler: Res<AssetHandler>,
-    window_query: Query<&Window, With<PrimaryWindow>>,
+    player_spawn_area: PlayerSpawnArea,
+    config: Res<GameConfig>,
+    mut rng: ResMut<GameRng>,
 ) {
-    if let Ok(window) = window_query.get_single() {
-        for _ in 0..NUMBER_OF_ENEMIES {
-            commands.spawn(EnemyBundle::at_randomized_location(window, &asset_handler));
-        }
+    spawn_wave_enemies(
+        &mut commands,
+        &asset_handler,
+        &player_spawn_area.get(),
+        &config,
+        1,
+        0.0,
+        &mut rng,
+    );
+}
+
+/// Bring in the enemies joining the arena with the new wave
+pub fn on_wave_start_spawn_enemies(
+    mut commands: Commands,
+    asset_handler: Res<AssetHandler>,
+    player_spawn_area: PlayerSpawnArea,
+    config: Res<GameConfig>,
+    wave: Res<Wave>,
+    difficulty_director: Res<DifficultyDirector>,
+    mut rng: ResMut<GameRng>,
+) {
+    spawn_wave_enemies(
+        &mut commands,
+        &asset_handler,
+        &player_spawn_area.get(),
+        &config,
+        wave.number,
+        difficulty_director.level,
+        &mut rng,
+    );
+}
+
+/// Spawn the enemies at the pace of the new wave
+pub fn on_wave_start_reset_enemy_spawn_timer(
+    mut enemy_spawn_timer: ResMut<EnemySpawnTimer>,
+    config: Res<GameConfig>,
+    wave: Res<Wave>,
+) {
+    *enemy_spawn_timer = EnemySpawnTimer::new(config.wave(wave.number).enemy_spawn_time);
+}
+
+fn spawn_wave_enemies(
+    commands: &mut Commands,
+    asset_handler: &Res<AssetHandler>,
+    spawn_area: &SpawnArea,
+    config: &GameConfig,
+    wave_number: u32,
+    difficulty_level: f32,
+    rng: &mut GameRng,
+) {
+    let wave_config = config.wave(wave_number);
+    for _ in 0..wave_config.enemy_count {
+        let kind = EnemyKind::pick(&wave_config.enemy_weights, difficulty_level, rng);
+        commands.spawn(EnemyBundle::at_randomized_location(
+            spawn_area,
+            &config.enemy,
+            kind,
+            asset_handler,
+            rng,
+        ));
     }
 }
 
-pub fn enemy_movement(mut enemy_query: Query<(&mut Transform, &Enemy)>, time: Res<Time>) {
+pub fn enemy_movement(
+    mut enemy_query: Query<(&mut Transform, &Enemy), With<Bouncer>>,
+    config: Res<GameConfig>,
+    wave: Res<Wave>,
+    difficulty_director: Res<DifficultyDirector>,
+    slow_motion_query: Query<(), With<SlowMotion>>,
+    time: Res<Time>,
+) {
+    let is_slow_motion = !slow_motion_query.is_empty();
+    let speed = enemy_speed(&config, &wave, &difficulty_director, is_slow_motion);
     for (mut enemy_transform, enemy) in enemy_query.iter_mut() {
-        let enemy_direction = enemy.direction;
-        enemy_transform.translation += enemy_direction * ENEMY_SPEED * time.delta_secs();
+        enemy_transform.translation += enemy.velocity() * speed * time.delta_secs();
     }
 }
 
 pub fn update_enemy_direction_when_out_of_bound(
     mut commands: Commands,
-    mut enemy_query: Query<(&Transform, &mut Enemy)>,
-    window_query: Query<&Window, With<PrimaryWindow>>,
+    mut enemy_query: Query<(&Transform, &mut Enemy), BouncesOffWalls>,
+    arena: Res<Arena>,
+    config: Res<GameConfig>,
     asset_server: Res<AssetServer>,
+    mut rng: ResMut<GameRng>,
 ) {
-    let window = window_query.get_single().unwrap();
     for (enemy_transform, mut enemy) in enemy_query.iter_mut() {
-        let half_unit_size = ENEMY_SIZE / 2.0;
+        let half_unit_size = config.enemy.size / 2.0;
         let x_min = 0.0 + half_unit_size;
-        let x_max = window.width() - half_unit_size;
+        let x_max = arena.width - half_unit_size;
         let y_min = 0.0 + half_unit_size;
-        let y_max = window.height() - half_unit_size;
+        let y_max = arena.height - half_unit_size;
 
         let new_translation = enemy_transform.translation;
         let mut is_direction_changed: bool = false;
 
-        if new_translation.x <= x_min || new_translation.x >= x_max {
+        // Only bounce off a wall the enemy is heading into, so it never sticks to the wall.
+        if (new_translation.x <= x_min && enemy.direction.x < 0.0)
+            || (new_translation.x >= x_max && enemy.direction.x > 0.0)
+        {
             enemy.direction.x *= -1.0;
             is_direction_changed = true;
         }
-        if new_translation.y <= y_min || new_translation.y >= y_max {
+        if (new_translation.y <= y_min && enemy.direction.y < 0.0)
+            || (new_translation.y >= y_max && enemy.direction.y > 0.0)
+        {
             enemy.direction.y *= -1.0;
             is_direction_changed = true;
         }
 
         if is_direction_changed {
-            commands.spawn(AudioHelper::play_bounce_sound(&asset_server));
+            commands.spawn(AudioHelper::play_bounce_sound(&asset_server, &mut rng));
+        }
+    }
+}
+
+/// Enemy bouncing off other enemies, with the orbit it takes along if it is an orbiter
+type BouncingEnemy<'a> = (
+    Entity,
+    &'a mut Transform,
+    &'a mut Enemy,
+    &'a Size,
+    Option<&'a mut Orbiter>,
+);
+
+/// Push overlapping enemies apart and bounce them off each other, of every kind.
+///
+/// Enemies pushed apart stay in the arena and are moved in the [`SpatialIndex`] right away, so the
+/// next checks of this tick see them where they are. Orbiters take their orbit along and spin the
+/// way they are bounced, wall huggers crawl back to the nearest wall.
+pub fn bounce_colliding_enemies(
+    mut commands: Commands,
+    mut enemy_query: Query<BouncingEnemy>,
+    mut spatial_index: ResMut<SpatialIndex>,
+    arena: Res<Arena>,
+    config: Res<GameConfig>,
+    asset_server: Res<AssetServer>,
+    mut rng: ResMut<GameRng>,
+) {
+    let enemies: Vec<Entity> = enemy_query.iter().map(|(entity, ..)| entity).collect();
+    let mut is_bounced = false;
+
+    for entity in enemies {
+        let Some(entry) = spatial_index.get(entity).copied() else {
+            continue;
+        };
+        let others: Vec<Entity> = spatial_index
+            .colliding_with(entry.translation, entry.size)
+            // Each pair once, from the enemy that comes first.
+            .filter(|other| *other > entity)
+            .collect();
+
+        for other in others {
+            let Ok([first, second]) = enemy_query.get_many_mut([entity, other]) else {
+                continue;
+            };
+            let (_, mut first_transform, mut first_enemy, first_size, first_orbiter) = first;
+            let (_, mut second_transform, mut second_enemy, second_size, second_orbiter) = second;
+            let (first_translation, second_translation) =
+                (first_transform.translation, second_transform.translation);
+            // An earlier bounce of this tick may have pushed them apart already.
+            if !MovementHelper::is_collided(
+                first_size.value,
+                first_translation,
+                second_size.value,
+                second_translation,
+            ) {
+                continue;
+            }
+
+            let (first_separated, second_separated) = MovementHelper::separate(
+                first_size.value,
+                first_translation,
+                second_size.value,
+                second_translation,
+            );
+            first_transform.translation =
+                MovementHelper::confine(&arena, first_separated, first_size.value);
+            second_transform.translation =
+                MovementHelper::confine(&arena, second_separated, second_size.value);
+            // Orbiters would be put back on their old orbit next tick otherwise.
+            if let Some(mut orbiter) = first_orbiter {
+                orbiter.center += first_transform.translation - first_translation;
+            }
+            if let Some(mut orbiter) = second_orbiter {
+                orbiter.center += second_transform.translation - second_translation;
+            }
+            spatial_index.update(entity, first_transform.translation);
+            spatial_index.update(other, second_transform.translation);
+
+            if let Some((first_velocity, second_velocity)) = MovementHelper::elastic_bounce(
+                first_size.value,
+                first_translation,
+                first_enemy.velocity(),
+                second_size.value,
+                second_translation,
+                second_enemy.velocity(),
+            ) {
+                let max_speed_multiplier = config.enemy.max_bounce_speed_multiplier;
+                first_enemy.set_velocity(first_velocity, max_speed_multiplier);
+                second_enemy.set_velocity(second_velocity, max_speed_multiplier);
+                is_bounced = true;
+            }
         }
     }
+
+    // A single sound per tick, however many enemies bounced.
+    if is_bounced {
+        commands.spawn(AudioHelper::play_bounce_sound(&asset_server, &mut rng));
+    }
 }
 
-pub fn tick_spawn_enemy_overtime(time: Res<Time>, mut enemy_spawn_timer: ResMut<EnemySpawnTimer>) {
-    enemy_spawn_timer.timer.tick(time.delta());
+/// Start every run with a fresh timer, so a run does not depend on the previous one
+pub fn reset_enemy_spawn_timer(
+    mut enemy_spawn_timer: ResMut<EnemySpawnTimer>,
+    config: Res<GameConfig>,
+) {
+    *enemy_spawn_timer = EnemySpawnTimer::new(config.wave(1).enemy_spawn_time);
+}
+
+pub fn tick_spawn_enemy_overtime(
+    time: Res<Time>,
+    difficulty_director: Res<DifficultyDirector>,
+    mut enemy_spawn_timer: ResMut<EnemySpawnTimer>,
+) {
+    // Running the timer faster spawns more often, without losing its progress as difficulty ramps.
+    enemy_spawn_timer.timer.tick(
+        time.delta()
+            .mul_f32(difficulty_director.enemy_spawn_rate_multiplier),
+    );
 }
 
 pub fn spawn_enemy_overtime(
     mut commands: Commands,
     enemy_spawn_timer: Res<EnemySpawnTimer>,
-    window_query: Query<&Window, With<PrimaryWindow>>,
+    player_spawn_area: PlayerSpawnArea,
+    config: Res<GameConfig>,
+    (wave, difficulty_director): (Res<Wave>, Res<DifficultyDirector>),
     asset_handler: Res<AssetHandler>,
+    mut rng: ResMut<GameRng>,
 ) {
     if enemy_spawn_timer.timer.just_finished() {
-        if let Ok(window) = window_query.get_single() {
-            commands.spawn(EnemyBundle::at_randomized_location(window, &asset_handler));
-        }
+        let kind = EnemyKind::pick(
+            &config.wave(wave.number).enemy_weights,
+            difficulty_director.level,
+            &mut rng,
+        );
+        commands.spawn(EnemyBundle::at_randomized_location(
+            &player_spawn_area.get(),
+            &config.enemy,
+            kind,
+            &asset_handler,
+            &mut rng,
+        ));
+    }
+}
+
+/// Apply a reloaded config to the enemies already in the arena, keeping the spawn progress
+pub fn on_game_config_changed_update_enemies(
+    config: Res<GameConfig>,
+    wave: Option<Res<Wave>>,
+    mut enemy_spawn_timer: ResMut<EnemySpawnTimer>,
+    mut enemy_query: Query<&mut Size, With<Enemy>>,
+) {
+    let wave_number = wave.map_or(1, |wave| wave.number);
+    enemy_spawn_timer
+        .timer
+        .set_duration(Duration::from_secs_f32(
+            config.wave(wave_number).enemy_spawn_time,
+        ));
+    for mut size in enemy_query.iter_mut() {
+        size.value = config.enemy.size;
     }
 }
 
@@ -0,0 +1,395 @@
+use bevy::ecs::component::ComponentId;
+use bevy::ecs::world::DeferredWorld;
+use bevy::math::{Vec2, Vec3};
+use bevy::prelude::{
+    in_state, App, Component, Entity, FixedUpdate, IntoSystemConfigs, Plugin, Query, Res, Time,
+    Timer, TimerMode, Transform, With, Without,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::arena::Arena;
+use crate::game::config::GameConfig;
+use crate::game::difficulty::DifficultyDirector;
+use crate::game::enemy::{enemy_movement, update_enemy_direction_when_out_of_bound, Enemy};
+use crate::game::player::Player;
+use crate::game::power_up::SlowMotion;
+use crate::game::random::{GameRng, RandomSet};
+use crate::game::simulation::is_simulating;
+use crate::game::wave::Wave;
+use crate::ApplicationState;
+
+pub struct EnemyBehaviourPlugin;
+
+impl Plugin for EnemyBehaviourPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            FixedUpdate,
+            (
+                homing_movement,
+                zig_zag_movement,
+                orbit_movement,
+                charger_movement,
+                wall_hugger_movement,
+            )
+                .chain()
+                .after(enemy_movement)
+                .before(update_enemy_direction_when_out_of_bound)
+                .in_set(RandomSet::Enemy)
+                .run_if(in_state(ApplicationState::InGame))
+                .run_if(is_simulating),
+        );
+    }
+}
+
+/// How an enemy moves, picked from the weights of the current wave when it spawns.
+///
+/// Adding a kind to an enemy gives it the component driving its movement, e.g. [`Homing`].
+#[derive(Component, Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[component(on_add = insert_enemy_behaviour)]
+pub enum EnemyKind {
+    /// Moves in a straight line and bounces off the walls
+    #[default]
+    Bouncer,
+    /// Steers toward the player
+    Homing,
+    /// Bounces around, veering left and right of its heading
+    ZigZag,
+    /// Circles around the point it spawned at
+    Orbiter,
+    /// Stands still, then dashes at the player
+    Charger,
+    /// Heads to the nearest wall, then crawls along the walls
+    WallHugger,
+}
+
+/// Chance of an enemy kind to be picked, relative to the other kinds of a wave.
+///
+/// The weight moves from `weight` at the start of a run to `full_difficulty_weight` as the
+/// difficulty ramps up.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EnemyWeight {
+    pub kind: EnemyKind,
+    pub weight: f32,
+    pub full_difficulty_weight: f32,
+}
+
+impl EnemyWeight {
+    pub fn new(kind: EnemyKind, weight: f32, full_difficulty_weight: f32) -> Self {
+        Self {
+            kind,
+            weight,
+            full_difficulty_weight,
+        }
+    }
+
+    /// Weight at a difficulty `level`, held past full difficulty
+    pub fn at_level(&self, level: f32) -> f32 {
+        let progress = level.clamp(0.0, 1.0);
+        self.weight + (self.full_difficulty_weight - self.weight) * progress
+    }
+}
+
+impl EnemyKind {
+    /// Pick one of `weights` at random at a difficulty `level`, drawing only when there is a choice
+    pub fn pick(weights: &[EnemyWeight], level: f32, rng: &mut GameRng) -> EnemyKind {
+        match weights {
+            [] => EnemyKind::default(),
+            [weight] => weight.kind,
+            _ => {
+                let total: f32 = weights.iter().map(|weight| weight.at_level(level)).sum();
+                let mut draw = rng.random_f32() * total;
+                for weight in weights {
+                    draw -= weight.at_level(level);
+                    if draw < 0.0 {
+                        return weight.kind;
+                    }
+                }
+                // Rounding can leave a sliver past the last kind.
+                weights[weights.len() - 1].kind
+            }
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct Bouncer {}
+
+#[derive(Component)]
+pub struct Homing {}
+
+#[derive(Component, Default)]
+pub struct ZigZag {
+    /// Simulated seconds since the enemy spawned
+    pub elapsed: f32,
+}
+
+#[derive(Component)]
+pub struct Orbiter {
+    pub center: Vec3,
+    /// Radians, counter-clockwise from the right of the center
+    pub angle: f32,
+}
+
+#[derive(Component)]
+pub struct Charger {
+    /// Time left standing still or dashing
+    pub timer: Timer,
+    pub is_dashing: bool,
+}
+
+#[derive(Component)]
+pub struct WallHugger {}
+
+/// Filter of the enemies bouncing off the walls, orbiters and wall huggers steer along them.
+pub type BouncesOffWalls = (Without<Orbiter>, Without<WallHugger>);
+
+/// Give a new enemy the component driving the movement of its kind.
+fn insert_enemy_behaviour(mut world: DeferredWorld, entity: Entity, _: ComponentId) {
+    let Some(kind) = world.get::<EnemyKind>(entity).copied() else {
+        return;
+    };
+    let translation = world
+        .get::<Transform>(entity)
+        .map_or(Vec3::ZERO, |transform| transform.translation);
+    let behaviour_config = world
+        .get_resource::<GameConfig>()
+        .map(|config| config.enemy.behaviour.clone())
+        .unwrap_or_default();
+
+    let mut commands = world.commands();
+    let mut entity_commands = commands.entity(entity);
+    match kind {
+        EnemyKind::Bouncer => entity_commands.insert(Bouncer {}),
+        EnemyKind::Homing => entity_commands.insert(Homing {}),
+        EnemyKind::ZigZag => entity_commands.insert(ZigZag::default()),
+        // Start on the orbit, at the spawn point.
+        EnemyKind::Orbiter => entity_commands.insert(Orbiter {
+            center: translation - Vec3::X * behaviour_config.orbit_radius,
+            angle: 0.0,
+        }),
+        EnemyKind::Charger => entity_commands.insert(Charger {
+            timer: Timer::from_seconds(behaviour_config.charger_pause_time, TimerMode::Once),
+            is_dashing: false,
+        }),
+        EnemyKind::WallHugger => entity_commands.insert(WallHugger {}),
+    };
+}
+
+/// Speed of every enemy this tick, from the tuning, the wave, the difficulty and whether the
+/// player is in [`SlowMotion`]
+pub fn enemy_speed(
+    config: &GameConfig,
+    wave: &Wave,
+    difficulty_director: &DifficultyDirector,
+    is_slow_motion: bool,
+) -> f32 {
+    let slow_motion_multiplier = if is_slow_motion {
+        config.power_up.slow_motion_multiplier
+    } else {
+        1.0
+    };
+    config.enemy.speed
+        * config.wave(wave.number).enemy_speed_multiplier
+        * difficulty_director.enemy_speed_multiplier
+        * slow_motion_multiplier
+}
+
+/// Direction from `from` toward `to`, or `None` when they overlap
+fn direction_toward(from: Vec3, to: Vec3) -> Option<Vec3> {
+    let direction = (to - from).truncate().normalize_or_zero();
+    (direction != Vec2::ZERO).then(|| direction.extend(0.0))
+}
+
+pub fn homing_movement(
+    mut enemy_query: Query<(&mut Transform, &mut Enemy, &Homing), Without<Player>>,
+    player_query: Query<&Transform, With<Player>>,
+    config: Res<GameConfig>,
+    wave: Res<Wave>,
+    difficulty_director: Res<DifficultyDirector>,
+    slow_motion_query: Query<(), With<SlowMotion>>,
+    time: Res<Time>,
+) {
+    let is_slow_motion = !slow_motion_query.is_empty();
+    let speed = enemy_speed(&config, &wave, &difficulty_director, is_slow_motion);
+    let max_turn = config.enemy.behaviour.homing_turn_rate * time.delta_secs();
+    let player_translation = player_query.iter().next().map(|player| player.translation);
+
+    for (mut enemy_transform, mut enemy, _) in enemy_query.iter_mut() {
+        let target = player_translation
+            .and_then(|player| direction_toward(enemy_transform.translation, player));
+        if let Some(target) = target {
+            let heading = enemy.direction.truncate();
+            let turn = heading
+                .angle_to(target.truncate())
+                .clamp(-max_turn, max_turn);
+            enemy.direction = Vec2::from_angle(turn).rotate(heading).extend(0.0);
+        }
+        enemy_transform.translation += enemy.velocity() * speed * time.delta_secs();
+    }
+}
+
+pub fn zig_zag_movement(
+    mut enemy_query: Query<(&mut Transform, &Enemy, &mut ZigZag)>,
+    config: Res<GameConfig>,
+    wave: Res<Wave>,
+    difficulty_director: Res<DifficultyDirector>,
+    slow_motion_query: Query<(), With<SlowMotion>>,
+    time: Res<Time>,
+) {
+    let is_slow_motion = !slow_motion_query.is_empty();
+    let speed = enemy_speed(&config, &wave, &difficulty_director, is_slow_motion);
+    let behaviour_config = &config.enemy.behaviour;
+
+    for (mut enemy_transform, enemy, mut zig_zag) in enemy_query.iter_mut() {
+        zig_zag.elapsed += time.delta_secs();
+        // Veer to the left of the heading, then to the right, and so on.
+        let turn_count = (zig_zag.elapsed / behaviour_config.zig_zag_period) as u32;
+        let side = if turn_count.is_multiple_of(2) {
+            1.0
+        } else {
+            -1.0
+        };
+        let veer = Vec2::from_angle(side * behaviour_config.zig_zag_angle);
+        let velocity = veer.rotate(enemy.velocity().truncate()).extend(0.0) * speed;
+        enemy_transform.translation += velocity * time.delta_secs();
+    }
+}
+
+pub fn orbit_movement(
+    mut enemy_query: Query<(&mut Transform, &mut Enemy, &mut Orbiter)>,
+    arena: Res<Arena>,
+    config: Res<GameConfig>,
+    wave: Res<Wave>,
+    difficulty_director: Res<DifficultyDirector>,
+    slow_motion_query: Query<(), With<SlowMotion>>,
+    time: Res<Time>,
+) {
+    let is_slow_motion = !slow_motion_query.is_empty();
+    let speed = enemy_speed(&config, &wave, &difficulty_director, is_slow_motion);
+    let radius = config.enemy.behaviour.orbit_radius;
+    // Keep the whole orbit in the arena, so the orbit is never cut by the walls.
+    let margin = (radius + config.enemy.size / 2.0)
+        .min(arena.width / 2.0)
+        .min(arena.height / 2.0);
+    let min = Vec3::new(margin, margin, 0.0);
+    let max = Vec3::new(arena.width - margin, arena.height - margin, 0.0);
+
+    for (mut enemy_transform, mut enemy, mut orbiter) in enemy_query.iter_mut() {
+        // Spin the way the enemy heads, counter-clockwise unless a bounce turned it around.
+        let tangent = Vec2::from_angle(orbiter.angle).perp();
+        let spin = if enemy.direction.truncate().dot(tangent) < 0.0 {
+            -1.0
+        } else {
+            1.0
+        };
+        orbiter.center = orbiter.center.clamp(min, max);
+        orbiter.angle += spin * speed * enemy.speed_multiplier / radius * time.delta_secs();
+        let offset = Vec2::from_angle(orbiter.angle) * radius;
+        enemy_transform.translation = orbiter.center + offset.extend(0.0);
+        // Head along the orbit, which is what other enemies bounce off.
+        enemy.direction = (Vec2::from_angle(orbiter.angle).perp() * spin).extend(0.0);
+    }
+}
+
+pub fn charger_movement(
+    mut enemy_query: Query<(&mut Transform, &mut Enemy, &mut Charger), Without<Player>>,
+    player_query: Query<&Transform, With<Player>>,
+    config: Res<GameConfig>,
+    wave: Res<Wave>,
+    difficulty_director: Res<DifficultyDirector>,
+    slow_motion_query: Query<(), With<SlowMotion>>,
+    time: Res<Time>,
+) {
+    let is_slow_motion = !slow_motion_query.is_empty();
+    let speed = enemy_speed(&config, &wave, &difficulty_director, is_slow_motion);
+    let behaviour_config = &config.enemy.behaviour;
+    let player_translation = player_query.iter().next().map(|player| player.translation);
+
+    for (mut enemy_transform, mut enemy, mut charger) in enemy_query.iter_mut() {
+        charger.timer.tick(time.delta());
+        if charger.is_dashing {
+            let dash_speed = speed * behaviour_config.charger_dash_speed_multiplier;
+            enemy_transform.translation += enemy.velocity() * dash_speed * time.delta_secs();
+        }
+
+        if !charger.timer.finished() {
+            continue;
+        }
+        if charger.is_dashing {
+            charger.is_dashing = false;
+            charger.timer =
+                Timer::from_seconds(behaviour_config.charger_pause_time, TimerMode::Once);
+        } else {
+            // Aim once, when the dash starts, so the player can sidestep it.
+            if let Some(target) = player_translation
+                .and_then(|player| direction_toward(enemy_transform.translation, player))
+            {
+                enemy.direction = target;
+            }
+            charger.is_dashing = true;
+            charger.timer =
+                Timer::from_seconds(behaviour_config.charger_dash_time, TimerMode::Once);
+        }
+    }
+}
+
+pub fn wall_hugger_movement(
+    mut enemy_query: Query<(&mut Transform, &mut Enemy), With<WallHugger>>,
+    arena: Res<Arena>,
+    config: Res<GameConfig>,
+    wave: Res<Wave>,
+    difficulty_director: Res<DifficultyDirector>,
+    slow_motion_query: Query<(), With<SlowMotion>>,
+    time: Res<Time>,
+) {
+    let is_slow_motion = !slow_motion_query.is_empty();
+    let speed = enemy_speed(&config, &wave, &difficulty_director, is_slow_motion);
+    let half_unit_size = config.enemy.size / 2.0;
+    let min = Vec3::new(half_unit_size, half_unit_size, 0.0);
+    let max = Vec3::new(
+        arena.width - half_unit_size,
+        arena.height - half_unit_size,
+        0.0,
+    );
+
+    for (mut enemy_transform, mut enemy) in enemy_query.iter_mut() {
+        let translation = enemy_transform.translation;
+        enemy.direction = wall_hugger_direction(translation, min, max);
+        enemy_transform.translation =
+            (translation + enemy.velocity() * speed * time.delta_secs()).clamp(min, max);
+    }
+}
+
+/// Heading of a wall hugger at `translation`, with `min` and `max` the reachable corners.
+///
+/// Along a wall it crawls counter-clockwise, turning at the corners. Away from the walls it heads
+/// straight to the nearest one.
+pub fn wall_hugger_direction(translation: Vec3, min: Vec3, max: Vec3) -> Vec3 {
+    // How close counts as touching a wall, in arena units.
+    const TOUCH_DISTANCE: f32 = 0.01;
+    let is_at_left = translation.x <= min.x + TOUCH_DISTANCE;
+    let is_at_right = translation.x >= max.x - TOUCH_DISTANCE;
+    let is_at_bottom = translation.y <= min.y + TOUCH_DISTANCE;
+    let is_at_top = translation.y >= max.y - TOUCH_DISTANCE;
+
+    if is_at_bottom && !is_at_right {
+        Vec3::X
+    } else if is_at_right && !is_at_top {
+        Vec3::Y
+    } else if is_at_top && !is_at_left {
+        Vec3::NEG_X
+    } else if is_at_left && !is_at_bottom {
+        Vec3::NEG_Y
+    } else {
+        let distances = [
+            (translation.x - min.x, Vec3::NEG_X),
+            (max.x - translation.x, Vec3::X),
+            (translation.y - min.y, Vec3::NEG_Y),
+            (max.y - translation.y, Vec3::Y),
+        ];
+        distances
+            .into_iter()
+            .min_by(|a, b| a.0.total_cmp(&b.0))
+            .map_or(Vec3::X, |(_, direction)| direction)
+    }
+}
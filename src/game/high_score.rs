@@ -1,38 +1,340 @@
-use bevy::prelude::{App, DetectChanges, EventReader, info, Plugin, Res, ResMut, Resource, Update};
+use std::cmp::Ordering;
+use std::fmt::{Display, Formatter};
+use std::path::Path;
 
+use bevy::prelude::{
+    info, warn, App, DetectChanges, Event, EventReader, NextState, Plugin, Res, ResMut, Resource,
+    Startup, Update,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::game::difficulty::DifficultyDirector;
 use crate::game::player::CollidedWithEnemy;
+use crate::helpers::TimeHelper;
+use crate::storage::{StorageDirectory, StorageError, StorageHelper};
+use crate::ApplicationState;
 
 pub struct HighScorePlugin;
 
 impl Plugin for HighScorePlugin {
     fn build(&self, app: &mut App) {
-        app.init_resource::<HighScore>()
-            .add_systems(Update, on_collided_with_enemy_update_high_score)
+        app.init_resource::<StorageDirectory>()
+            .init_resource::<HighScore>()
+            .init_resource::<LastRun>()
+            .add_event::<HighScoreNameEntered>()
+            .add_systems(Startup, load_high_score)
+            .add_systems(Update, on_collided_with_enemy_record_last_run)
+            .add_systems(Update, on_name_entered_commit_high_score)
             .add_systems(Update, on_high_score_change_print);
     }
 }
 
-#[derive(Resource, Debug)]
+pub const HIGH_SCORE_FILE_NAME: &str = "high_score.ron";
+pub const HIGH_SCORE_SAVE_VERSION: u32 = 2;
+pub const HIGH_SCORE_CAPACITY: usize = 10;
+pub const PLAYER_NAME_MAX_LENGTH: usize = 12;
+
+/// Sent once the player has typed the name to record the last run under.
+#[derive(Event)]
+pub struct HighScoreNameEntered {
+    pub name: String,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum PlayerNameError {
+    Empty,
+    TooLong,
+    InvalidCharacter(char),
+}
+
+impl Display for PlayerNameError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PlayerNameError::Empty => write!(f, "Name cannot be empty"),
+            PlayerNameError::TooLong => {
+                write!(
+                    f,
+                    "Name is longer than {} characters",
+                    PLAYER_NAME_MAX_LENGTH
+                )
+            }
+            PlayerNameError::InvalidCharacter(character) => {
+                write!(f, "'{}' is not allowed in a name", character)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HighScoreEntry {
+    pub name: String,
+    pub score: u32,
+    /// When the run ended, in seconds since the Unix epoch
+    pub timestamp: u64,
+}
+
+impl HighScoreEntry {
+    pub fn new(name: impl Into<String>, score: u32, timestamp: u64) -> Self {
+        Self {
+            name: name.into(),
+            score,
+            timestamp,
+        }
+    }
+
+    /// Whether a character may be part of a player name
+    pub fn is_name_character(character: char) -> bool {
+        character.is_ascii_alphanumeric() || matches!(character, ' ' | '-' | '_')
+    }
+
+    /// Check a typed player name, returning it without surrounding spaces
+    pub fn validate_name(name: &str) -> Result<String, PlayerNameError> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(PlayerNameError::Empty);
+        }
+        if let Some(character) = name.chars().find(|c| !Self::is_name_character(*c)) {
+            return Err(PlayerNameError::InvalidCharacter(character));
+        }
+        if name.chars().count() > PLAYER_NAME_MAX_LENGTH {
+            return Err(PlayerNameError::TooLong);
+        }
+        Ok(name.to_string())
+    }
+
+    /// Leaderboard order: higher score first, and on a tie the earlier run first
+    pub fn leaderboard_order(&self, other: &HighScoreEntry) -> Ordering {
+        other
+            .score
+            .cmp(&self.score)
+            .then(self.timestamp.cmp(&other.timestamp))
+    }
+}
+
+/// Leaderboard of the best runs, kept sorted and capped at `capacity` entries.
+#[derive(Resource, Debug, Serialize, Deserialize)]
 pub struct HighScore {
-    pub scores: Vec<(String, u32)>,
+    entries: Vec<HighScoreEntry>,
+    #[serde(skip, default = "HighScore::default_capacity")]
+    capacity: usize,
 }
 
 impl Default for HighScore {
     fn default() -> Self {
-        Self { scores: Vec::new() }
+        Self::with_capacity(HIGH_SCORE_CAPACITY)
+    }
+}
+
+/// Save format of version 1, before entries had a timestamp and the table was sorted.
+#[derive(Deserialize)]
+struct HighScoreV1 {
+    scores: Vec<(String, u32)>,
+}
+
+impl From<HighScoreV1> for HighScore {
+    fn from(high_score: HighScoreV1) -> Self {
+        let mut migrated = HighScore::default();
+        for (name, score) in high_score.scores {
+            // Unknown run time, insertion keeps the original order between equal scores.
+            migrated.insert(HighScoreEntry::new(name, score, 0));
+        }
+        migrated
+    }
+}
+
+impl HighScore {
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            entries: Vec::new(),
+            capacity,
+        }
+    }
+
+    fn default_capacity() -> usize {
+        HIGH_SCORE_CAPACITY
+    }
+
+    /// Entries from the best to the worst
+    pub fn entries(&self) -> &[HighScoreEntry] {
+        &self.entries
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn best(&self) -> Option<&HighScoreEntry> {
+        self.entries.first()
+    }
+
+    /// Return the 1-based rank a run would get, or `None` when it would not make the table
+    pub fn rank_of(&self, score: u32, timestamp: u64) -> Option<usize> {
+        let candidate = HighScoreEntry::new("", score, timestamp);
+        let index = self
+            .entries
+            .partition_point(|entry| entry.leaderboard_order(&candidate) != Ordering::Greater);
+
+        if index < self.capacity {
+            Some(index + 1)
+        } else {
+            None
+        }
+    }
+
+    /// Whether a run would make the table at all
+    pub fn qualifies(&self, score: u32, timestamp: u64) -> bool {
+        self.rank_of(score, timestamp).is_some()
+    }
+
+    /// Best score recorded under the given name
+    pub fn best_of(&self, name: &str) -> Option<u32> {
+        self.entries
+            .iter()
+            .find(|entry| entry.name == name)
+            .map(|entry| entry.score)
+    }
+
+    /// Whether a score beats everything recorded under the given name
+    pub fn is_personal_best(&self, name: &str, score: u32) -> bool {
+        match self.best_of(name) {
+            Some(best) => score > best,
+            None => score > 0,
+        }
+    }
+
+    /// Insert an entry at its place, dropping whatever falls past the capacity.
+    ///
+    /// Returns the 1-based rank of the entry, or `None` when it did not make the table.
+    pub fn insert(&mut self, entry: HighScoreEntry) -> Option<usize> {
+        let rank = self.rank_of(entry.score, entry.timestamp)?;
+        self.entries.insert(rank - 1, entry);
+        self.entries.truncate(self.capacity);
+        Some(rank)
     }
+
+    /// Load the high score table saved at `path`, or `None` when nothing was saved yet
+    pub fn load_from(path: &Path) -> Result<Option<HighScore>, StorageError> {
+        let Some(content) = StorageHelper::read(path)? else {
+            return Ok(None);
+        };
+
+        let mut high_score = match StorageHelper::read_version(&content)? {
+            1 => StorageHelper::parse::<HighScoreV1>(&content)?.into(),
+            HIGH_SCORE_SAVE_VERSION => StorageHelper::parse::<HighScore>(&content)?,
+            version => {
+                return Err(StorageError::UnsupportedVersion {
+                    found: version,
+                    supported: HIGH_SCORE_SAVE_VERSION,
+                })
+            }
+        };
+
+        // The file may have been edited by hand, restore the invariants.
+        high_score
+            .entries
+            .sort_by(|first, second| first.leaderboard_order(second));
+        high_score.entries.truncate(high_score.capacity);
+        Ok(Some(high_score))
+    }
+
+    pub fn save_to(&self, path: &Path) -> Result<(), StorageError> {
+        StorageHelper::save(path, HIGH_SCORE_SAVE_VERSION, self)
+    }
+}
+
+/// Outcome of the last finished run, as shown on the name entry and game over screens.
+///
+/// `rank` is the rank the run qualifies for until a name is entered, then the rank it got.
+#[derive(Resource, Debug, Default)]
+pub struct LastRun {
+    pub score: u32,
+    pub timestamp: u64,
+    pub rank: Option<usize>,
+    pub is_personal_best: bool,
+    /// Difficulty the run ended at
+    pub difficulty: Option<DifficultyDirector>,
+    /// Whether the run has been recorded in the [`HighScore`] yet, it only ever is once
+    pub is_committed: bool,
 }
 
-pub fn on_collided_with_enemy_update_high_score(
+pub fn load_high_score(mut high_score: ResMut<HighScore>, storage: Res<StorageDirectory>) {
+    let path = storage.file(HIGH_SCORE_FILE_NAME);
+    match HighScore::load_from(&path) {
+        Ok(Some(loaded)) => {
+            info!("High Score loaded from {}", path.display());
+            *high_score = loaded;
+        }
+        Ok(None) => info!("No High Score saved yet at {}", path.display()),
+        Err(error) => {
+            warn!(
+                "Could not load High Score from {}: {}",
+                path.display(),
+                error
+            );
+            // Keep the unreadable file around instead of overwriting it on the next save.
+            if path.exists() {
+                match StorageHelper::quarantine(&path) {
+                    Ok(moved_to) => warn!("Moved unreadable High Score to {}", moved_to.display()),
+                    Err(error) => warn!("Could not move unreadable High Score: {}", error),
+                }
+            }
+        }
+    }
+}
+
+pub fn on_collided_with_enemy_record_last_run(
     mut event_reader: EventReader<CollidedWithEnemy>,
-    mut high_score: ResMut<HighScore>,
+    high_score: Res<HighScore>,
+    difficulty_director: Option<Res<DifficultyDirector>>,
+    mut last_run: ResMut<LastRun>,
 ) {
     for event in event_reader.read() {
         let current_final_score = event.score;
         info!("Your final score is: {}", current_final_score);
-        high_score
-            .scores
-            .push(("Player".to_string(), current_final_score));
+        let difficulty = difficulty_director.as_deref().copied();
+        if let Some(difficulty) = difficulty {
+            info!("Final difficulty: {:?}", difficulty);
+        }
+
+        let timestamp = TimeHelper::now_unix_seconds();
+        *last_run = LastRun {
+            score: current_final_score,
+            timestamp,
+            rank: high_score.rank_of(current_final_score, timestamp),
+            is_personal_best: false,
+            difficulty,
+            is_committed: false,
+        };
+    }
+}
+
+pub fn on_name_entered_commit_high_score(
+    mut event_reader: EventReader<HighScoreNameEntered>,
+    mut high_score: ResMut<HighScore>,
+    mut last_run: ResMut<LastRun>,
+    storage: Res<StorageDirectory>,
+    mut application_state: ResMut<NextState<ApplicationState>>,
+) {
+    for event in event_reader.read() {
+        // A name confirmed twice in a row, e.g. with Enter and the button, is the same run.
+        if last_run.is_committed {
+            continue;
+        }
+        last_run.is_committed = true;
+        last_run.is_personal_best = high_score.is_personal_best(&event.name, last_run.score);
+        last_run.rank = high_score.insert(HighScoreEntry::new(
+            event.name.clone(),
+            last_run.score,
+            last_run.timestamp,
+        ));
+
+        let path = storage.file(HIGH_SCORE_FILE_NAME);
+        if let Err(error) = high_score.save_to(&path) {
+            warn!("Could not save High Score to {}: {}", path.display(), error);
+        }
+
+        application_state.set(ApplicationState::GameOver);
+        info!("{:?}", ApplicationState::GameOver);
     }
 }
 
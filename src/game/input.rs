@@ -0,0 +1,82 @@
+use bevy::math::Vec3;
+use bevy::prelude::{
+    in_state, App, FixedUpdate, IntoSystemConfigs, IntoSystemSetConfigs, Plugin, Res, ResMut,
+    Resource, SystemSet,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::game::config::GameConfig;
+use crate::game::simulation::{is_simulating, SimulationSet};
+use crate::helpers::MovementHelper;
+use crate::input_bindings::ActionInput;
+use crate::settings::{steering_mode_is, SteeringMode};
+use crate::ApplicationState;
+
+pub struct PlayerInputPlugin;
+
+impl Plugin for PlayerInputPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PlayerInput>()
+            .init_resource::<PlayerInputSource>()
+            .configure_sets(FixedUpdate, PlayerInputSet.in_set(SimulationSet::Input))
+            .add_systems(
+                FixedUpdate,
+                read_live_input
+                    .in_set(PlayerInputSet)
+                    .run_if(in_state(ApplicationState::InGame))
+                    .run_if(is_simulating)
+                    .run_if(input_source_is(PlayerInputSource::Live))
+                    .run_if(steering_mode_is(SteeringMode::Buttons)),
+            );
+    }
+}
+
+/// Systems writing [`PlayerInput`], every system reading it runs after them.
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PlayerInputSet;
+
+/// Where [`PlayerInput`] comes from.
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum PlayerInputSource {
+    /// The keyboard, gamepads, mouse and touch screen
+    #[default]
+    Live,
+    Replay,
+}
+
+pub fn input_source_is(source: PlayerInputSource) -> impl Fn(Res<PlayerInputSource>) -> bool {
+    move |current: Res<PlayerInputSource>| *current == source
+}
+
+/// Movement the player asks for this tick, whatever device or replay it comes from.
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct PlayerInput {
+    pub x: f32,
+    pub y: f32,
+}
+
+impl PlayerInput {
+    pub fn from_direction(direction: Vec3) -> Self {
+        Self {
+            x: direction.x,
+            y: direction.y,
+        }
+    }
+
+    pub fn direction(&self) -> Vec3 {
+        Vec3::new(self.x, self.y, 0.0)
+    }
+}
+
+/// Bound keys and buttons move at full speed, the left stick only when none of them is held
+pub fn read_live_input(
+    action_input: ActionInput,
+    config: Res<GameConfig>,
+    mut player_input: ResMut<PlayerInput>,
+) {
+    let mut direction = MovementHelper::handle_input(&action_input);
+    if direction == Vec3::ZERO {
+        direction = MovementHelper::handle_stick(action_input.left_stick(), &config.gamepad);
+    }
+    *player_input = PlayerInput::from_direction(direction);
+}
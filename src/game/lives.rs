@@ -0,0 +1,208 @@
+use bevy::math::Vec3;
+use bevy::prelude::{
+    in_state, App, AssetServer, Commands, Component, Entity, Event, EventReader, FixedUpdate,
+    IntoSystemConfigs, OnEnter, OnExit, Plugin, Query, Res, ResMut, Resource, Time, Timer,
+    TimerMode, Transform, Update, Visibility, With, Without,
+};
+
+use crate::arena::Arena;
+use crate::game::config::GameConfig;
+use crate::game::enemy::Enemy;
+use crate::game::enemy_behaviour::Orbiter;
+use crate::game::player::{on_hit_enemy_emit_collide_event, Player};
+use crate::game::simulation::{is_simulating, InterpolatedTransform, SimulationSet};
+use crate::game::spatial_index::SpatialIndex;
+use crate::helpers::{AudioHelper, MovementHelper};
+use crate::ApplicationState;
+
+/// Seconds the player stays shown, then hidden, while blinking.
+const BLINK_PERIOD: f32 = 0.1;
+
+pub struct LivesPlugin;
+
+impl Plugin for LivesPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<LostLife>()
+            .add_systems(OnEnter(ApplicationState::InGame), insert_lives_resource)
+            .add_systems(OnExit(ApplicationState::InGame), remove_lives_resource)
+            .add_systems(
+                FixedUpdate,
+                tick_invulnerability
+                    .before(SimulationSet::Movement)
+                    .run_if(in_state(ApplicationState::InGame))
+                    .run_if(is_simulating),
+            )
+            .add_systems(
+                FixedUpdate,
+                on_lost_life_respawn_player
+                    .in_set(SimulationSet::Collision)
+                    .after(on_hit_enemy_emit_collide_event)
+                    .run_if(in_state(ApplicationState::InGame))
+                    .run_if(is_simulating),
+            )
+            .add_systems(
+                Update,
+                (blink_invulnerable_player, on_lost_life_play_sound)
+                    .run_if(in_state(ApplicationState::InGame)),
+            );
+    }
+}
+
+/// Hits the player can still take in this run.
+#[derive(Resource, Debug)]
+pub struct Lives {
+    pub remaining: u32,
+}
+
+/// The player was hit but has lives left; the last hit is a
+/// [`CollidedWithEnemy`](crate::game::player::CollidedWithEnemy) instead.
+#[derive(Event)]
+pub struct LostLife {
+    pub lives_left: u32,
+}
+
+/// Enemies go through the player until the timer runs out, the player blinks meanwhile.
+#[derive(Component)]
+pub struct Invulnerable {
+    pub timer: Timer,
+}
+
+pub fn insert_lives_resource(mut commands: Commands, config: Res<GameConfig>) {
+    commands.insert_resource(Lives {
+        remaining: config.player.lives,
+    });
+}
+
+pub fn remove_lives_resource(mut commands: Commands) {
+    commands.remove_resource::<Lives>();
+}
+
+pub fn tick_invulnerability(
+    mut commands: Commands,
+    mut query: Query<(Entity, &mut Invulnerable)>,
+    time: Res<Time>,
+) {
+    for (entity, mut invulnerable) in query.iter_mut() {
+        invulnerable.timer.tick(time.delta());
+        if invulnerable.timer.finished() {
+            commands.entity(entity).remove::<Invulnerable>();
+        }
+    }
+}
+
+/// Enemy a respawn may push away, with the orbit it takes along if it is an orbiter
+type PushedEnemy<'a> = (
+    Entity,
+    &'a mut Transform,
+    &'a mut InterpolatedTransform,
+    &'a mut Enemy,
+    Option<&'a mut Orbiter>,
+);
+
+/// Bring the player back at the center of the arena, pushing the enemies around it out of reach
+pub fn on_lost_life_respawn_player(
+    mut commands: Commands,
+    mut event_reader: EventReader<LostLife>,
+    mut player_query: Query<(Entity, &mut Transform, &mut InterpolatedTransform), With<Player>>,
+    mut enemy_query: Query<PushedEnemy, Without<Player>>,
+    mut spatial_index: ResMut<SpatialIndex>,
+    arena: Res<Arena>,
+    config: Res<GameConfig>,
+) {
+    if event_reader.read().count() == 0 {
+        return;
+    }
+    let Ok((player_entity, mut player_transform, mut interpolated_transform)) =
+        player_query.get_single_mut()
+    else {
+        return;
+    };
+
+    let center = arena.center();
+    // Jump to the center rather than being drawn sliding across the arena.
+    player_transform.translation = center;
+    interpolated_transform.previous = center;
+    interpolated_transform.current = center;
+    spatial_index.update(player_entity, center);
+    commands.entity(player_entity).insert(Invulnerable {
+        timer: Timer::from_seconds(config.player.invulnerability_time, TimerMode::Once),
+    });
+
+    let radius = config.player.respawn_clear_radius;
+    for (enemy_entity, mut enemy_transform, mut interpolated_transform, mut enemy, mut orbiter) in
+        enemy_query.iter_mut()
+    {
+        if push_enemy_away(
+            &mut enemy_transform,
+            &mut enemy,
+            orbiter.as_deref_mut(),
+            center,
+            radius,
+            &arena,
+            config.enemy.size,
+        ) {
+            // Pushed enemies jump out of reach too.
+            let pushed = enemy_transform.translation;
+            interpolated_transform.previous = pushed;
+            interpolated_transform.current = pushed;
+            spatial_index.update(enemy_entity, pushed);
+        }
+    }
+}
+
+/// Move an enemy within `radius` of `center` out to it, heading away, and tell whether it moved.
+///
+/// An orbiter takes its orbit along, it would be put back on the old one next tick otherwise.
+pub fn push_enemy_away(
+    enemy_transform: &mut Transform,
+    enemy: &mut Enemy,
+    orbiter: Option<&mut Orbiter>,
+    center: Vec3,
+    radius: f32,
+    arena: &Arena,
+    enemy_size: f32,
+) -> bool {
+    let offset = enemy_transform.translation - center;
+    if offset.length() >= radius {
+        return false;
+    }
+
+    let away = offset.normalize_or(Vec3::X);
+    let pushed = MovementHelper::confine(arena, center + away * radius, enemy_size);
+    // An orbiter keeps its heading along the orbit, which tells which way it spins.
+    match orbiter {
+        Some(orbiter) => orbiter.center += pushed - enemy_transform.translation,
+        None => enemy.direction = away,
+    }
+    enemy_transform.translation = pushed;
+    true
+}
+
+/// Hide the player every other blink period while it is invulnerable, show it once it is not
+pub fn blink_invulnerable_player(
+    mut player_query: Query<(&mut Visibility, Option<&Invulnerable>), With<Player>>,
+) {
+    for (mut visibility, invulnerable) in player_query.iter_mut() {
+        let is_shown = invulnerable.is_none_or(|invulnerable| {
+            ((invulnerable.timer.elapsed_secs() / BLINK_PERIOD) as u32).is_multiple_of(2)
+        });
+        let target = if is_shown {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        };
+        if *visibility != target {
+            *visibility = target;
+        }
+    }
+}
+
+pub fn on_lost_life_play_sound(
+    mut commands: Commands,
+    mut event_reader: EventReader<LostLife>,
+    asset_server: Res<AssetServer>,
+) {
+    for _event in event_reader.read() {
+        commands.spawn(AudioHelper::play_game_over_sound(&asset_server));
+    }
+}
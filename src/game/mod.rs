@@ -1,35 +1,75 @@
 use bevy::app::App;
 use bevy::prelude::{
-    in_state, info, AppExtStates, ButtonInput, Component, EventReader, IntoSystemConfigs, KeyCode,
-    NextState, OnEnter, OnExit, Plugin, Res, ResMut, State, States, Update,
+    in_state, info, AppExtStates, Component, EventReader, IntoSystemConfigs, NextState, OnEnter,
+    OnExit, Plugin, Res, ResMut, State, States, Update,
 };
 
+use crate::game::config::GameConfigPlugin;
+use crate::game::difficulty::DifficultyPlugin;
+use crate::game::elapsed_time::ElapsedTimePlugin;
 use crate::game::enemy::EnemyPlugin;
+use crate::game::enemy_behaviour::EnemyBehaviourPlugin;
 use crate::game::high_score::HighScorePlugin;
+use crate::game::input::PlayerInputPlugin;
+use crate::game::lives::LivesPlugin;
 use crate::game::movement::MovementPlugin;
 use crate::game::player::{CollidedWithEnemy, PlayerPlugin};
+use crate::game::power_up::PowerUpPlugin;
+use crate::game::random::RandomPlugin;
+use crate::game::replay::ReplayPlugin;
 use crate::game::score::ScorePlugin;
+use crate::game::screen_shake::ScreenShakePlugin;
+use crate::game::simulation::SimulationPlugin;
+use crate::game::spatial_index::SpatialIndexPlugin;
 use crate::game::star::StarPlugin;
+use crate::game::wave::WavePlugin;
+use crate::input_bindings::{ActionInput, InputAction};
 use crate::ApplicationState;
 
+pub mod config;
+pub mod difficulty;
+pub mod elapsed_time;
 pub mod enemy;
+pub mod enemy_behaviour;
 pub mod high_score;
-mod movement;
+pub mod input;
+pub mod lives;
+pub mod movement;
 pub mod player;
+pub mod power_up;
+pub mod random;
+pub mod replay;
 pub mod score;
+pub mod screen_shake;
+pub mod simulation;
+pub mod spatial_index;
 pub mod star;
+pub mod wave;
 
 pub struct GamePlugin;
 
 impl Plugin for GamePlugin {
     fn build(&self, app: &mut App) {
         app.init_state::<GameState>()
+            .add_plugins(SimulationPlugin)
+            .add_plugins(GameConfigPlugin)
+            .add_plugins(RandomPlugin)
+            .add_plugins(PlayerInputPlugin)
+            .add_plugins(ReplayPlugin)
             .add_plugins(MovementPlugin)
+            .add_plugins(SpatialIndexPlugin)
             .add_plugins(PlayerPlugin)
+            .add_plugins(LivesPlugin)
             .add_plugins(EnemyPlugin)
+            .add_plugins(EnemyBehaviourPlugin)
             .add_plugins(StarPlugin)
+            .add_plugins(PowerUpPlugin)
+            .add_plugins(WavePlugin)
+            .add_plugins(DifficultyPlugin)
             .add_plugins(ScorePlugin)
+            .add_plugins(ElapsedTimePlugin)
             .add_plugins(HighScorePlugin)
+            .add_plugins(ScreenShakePlugin)
             .add_systems(OnEnter(ApplicationState::InGame), resume_game)
             .add_systems(OnExit(ApplicationState::InGame), pause_game)
             .add_systems(
@@ -46,14 +86,22 @@ pub enum GameState {
     Stop,
     Running,
     Paused,
+    /// Break between two waves, the simulation holds still
+    Intermission,
+    /// Settings screen opened from the pause menu, the run holds still until it is back to the menu
+    Settings,
 }
 
 pub fn toggle_pause(
-    keyboard_input: Res<ButtonInput<KeyCode>>,
+    action_input: ActionInput,
     state: Res<State<GameState>>,
     next_state: ResMut<NextState<GameState>>,
 ) {
-    if keyboard_input.just_pressed(KeyCode::Space) {
+    // A wave or the run ended this frame, pausing would cancel what comes next.
+    if matches!(*next_state, NextState::Pending(_)) {
+        return;
+    }
+    if action_input.just_pressed(InputAction::Pause) {
         match state.get() {
             GameState::Running => {
                 pause_game(next_state);
@@ -63,6 +111,9 @@ pub fn toggle_pause(
             }
             _ => {}
         }
+    } else if action_input.just_pressed(InputAction::Back) && *state.get() == GameState::Running {
+        // Going back from a run opens the pause menu, whose back button resumes it.
+        pause_game(next_state);
     }
 }
 
@@ -90,5 +141,11 @@ pub struct Size {
     value: f32,
 }
 
+impl Size {
+    pub fn new(value: f32) -> Self {
+        Self { value }
+    }
+}
+
 #[derive(Component)]
 pub struct Confined;
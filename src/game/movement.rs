@@ -1,10 +1,11 @@
 use bevy::prelude::{
-    App, in_state, IntoSystemConfigs, Plugin, Query, Transform, Update, Window, With,
+    App, FixedUpdate, in_state, IntoSystemConfigs, Plugin, Query, Res, Transform, With,
 };
-use bevy::window::PrimaryWindow;
 
+use crate::arena::Arena;
 use crate::ApplicationState;
-use crate::game::{Confined, GameState, Size};
+use crate::game::{Confined, Size};
+use crate::game::simulation::{is_simulating, SimulationSet};
 use crate::helpers::MovementHelper;
 
 pub struct MovementPlugin;
@@ -12,23 +13,22 @@ pub struct MovementPlugin;
 impl Plugin for MovementPlugin {
     fn build(&self, app: &mut App) {
         app.add_systems(
-            Update,
-            (confine_movement)
+            FixedUpdate,
+            confine_movement
+                .in_set(SimulationSet::Confinement)
                 .run_if(in_state(ApplicationState::InGame))
-                .run_if(in_state(GameState::Running)),
+                .run_if(is_simulating),
         );
     }
 }
 
 pub fn confine_movement(
     mut query: Query<(&mut Transform, &Size), With<Confined>>,
-    window_query: Query<&Window, With<PrimaryWindow>>,
+    arena: Res<Arena>,
 ) {
-    if let Ok(window) = window_query.get_single() {
-        for (mut transform, size) in query.iter_mut() {
-            let confined_translation =
-                MovementHelper::confine(window, transform.translation, size.value);
-            transform.translation = confined_translation;
-        }
+    for (mut transform, size) in query.iter_mut() {
+        let confined_translation =
+            MovementHelper::confine(&arena, transform.translation, size.value);
+        transform.translation = confined_translation;
     }
 }
@@ -1,16 +1,27 @@
+use bevy::ecs::system::SystemParam;
+use bevy::math::Vec3;
 use bevy::prelude::{
-    default, in_state, info, App, AssetServer, Bundle, ButtonInput, Commands, Component, Entity,
-    Event, EventReader, EventWriter, IntoSystemConfigs, KeyCode, Name, OnEnter, OnExit, Plugin,
-    Query, Res, ResMut, Sprite, SpriteBundle, Time, Transform, Update, Window, With, Without,
+    in_state, info, resource_changed, App, AssetServer, Bundle, Commands, Component, Entity, Event,
+    EventReader, EventWriter, FixedUpdate, Has, IntoSystemConfigs, Name, OnEnter, OnExit, Plugin,
+    Query, Res, ResMut, Sprite, SpriteBundle, Time, Transform, Update, With, Without,
 };
-use bevy::window::PrimaryWindow;
 
+use crate::arena::{Arena, SpawnArea};
 use crate::asset_handler::AssetHandler;
-use crate::game::enemy::{Enemy, ENEMY_SIZE};
+use crate::game::config::{on_game_config_loaded_apply, GameConfig, PlayerConfig};
+use crate::game::enemy::{bounce_colliding_enemies, Enemy};
+use crate::game::input::{input_source_is, PlayerInput, PlayerInputSet, PlayerInputSource};
+use crate::game::lives::{Invulnerable, Lives, LostLife};
+use crate::game::power_up::{ScoreMultiplier, Shield};
+use crate::game::random::RandomSet;
 use crate::game::score::Score;
-use crate::game::star::{Star, STAR_SIZE};
-use crate::game::{Confined, GameState, Size};
-use crate::helpers::{AudioHelper, MovementHelper, WindowHelper};
+use crate::game::simulation::{is_simulating, InterpolatedTransform, SimulationSet};
+use crate::game::spatial_index::SpatialIndex;
+use crate::game::star::Star;
+use crate::game::{Confined, Size};
+use crate::helpers::{AudioHelper, MovementHelper};
+use crate::input_bindings::PointerPosition;
+use crate::settings::{steering_mode_is, SteeringMode};
 use crate::{ApplicationState, ScheduleDespawn};
 
 pub struct PlayerPlugin;
@@ -22,33 +33,51 @@ impl Plugin for PlayerPlugin {
             .add_systems(OnEnter(ApplicationState::InGame), spawn_player)
             .add_systems(OnExit(ApplicationState::InGame), despawn_player)
             .add_systems(
-                Update,
-                movement
-                    .chain()
+                FixedUpdate,
+                steer_towards_pointer
+                    .in_set(PlayerInputSet)
                     .run_if(in_state(ApplicationState::InGame))
-                    .run_if(in_state(GameState::Running)),
+                    .run_if(is_simulating)
+                    .run_if(input_source_is(PlayerInputSource::Live))
+                    .run_if(steering_mode_is(SteeringMode::Pointer)),
             )
             .add_systems(
-                Update,
-                (on_hit_star_emit_collide_event, on_star_collide_despawn_star)
-                    .chain()
+                FixedUpdate,
+                movement
+                    .in_set(SimulationSet::Movement)
+                    .before(RandomSet::Enemy)
                     .run_if(in_state(ApplicationState::InGame))
-                    .run_if(in_state(GameState::Running)),
+                    .run_if(is_simulating),
             )
             .add_systems(
-                Update,
+                FixedUpdate,
                 (
+                    (on_hit_star_emit_collide_event, on_star_collide_despawn_star).chain(),
                     (
                         on_hit_enemy_emit_collide_event,
                         on_enemy_collide_despawn_player,
                     )
                         .chain(),
+                    on_star_collide_event_add_score.after(on_hit_star_emit_collide_event),
+                )
+                    .in_set(SimulationSet::Collision)
+                    .after(bounce_colliding_enemies)
+                    .run_if(in_state(ApplicationState::InGame))
+                    .run_if(is_simulating),
+            )
+            .add_systems(
+                Update,
+                (
                     on_enemy_collide_play_game_over_sound,
                     on_star_collide_play_star_despawn_sound,
-                    on_star_collide_event_add_score,
                 )
-                    .run_if(in_state(ApplicationState::InGame))
-                    .run_if(in_state(GameState::Running)),
+                    .run_if(in_state(ApplicationState::InGame)),
+            )
+            .add_systems(
+                Update,
+                on_game_config_changed_update_player
+                    .after(on_game_config_loaded_apply)
+                    .run_if(resource_changed::<GameConfig>),
             );
     }
 }
@@ -63,10 +92,8 @@ pub struct CollidedWithStar {
     pub star_entity: Entity,
 }
 
-pub const PLAYER_SPEED: f32 = 500.0;
-pub const PLAYER_SIZE: f32 = 64.0;
-
 #[derive(Component)]
+#[require(InterpolatedTransform)]
 pub struct Player {}
 
 #[derive(Bundle)]
@@ -79,89 +106,154 @@ pub struct PlayerBundle {
 }
 
 impl PlayerBundle {
-    pub fn at_center_of_the_screen(
-        window: &Window,
+    pub fn at_center_of_the_arena(
+        arena: &Arena,
+        config: &PlayerConfig,
         asset_handler: &Res<AssetHandler>,
     ) -> (Name, Player, Confined, Size, Sprite, Transform) {
         (
             Name::new("Player"),
             Player {},
             Confined {},
-            Size { value: PLAYER_SIZE },
+            Size { value: config.size },
             Sprite::from_image(asset_handler.player_texture.clone()),
-            WindowHelper::center(window),
+            Transform::from_translation(arena.center()),
         )
     }
 }
 
+/// The [`SpawnArea`] clear of the player, or of the center it starts a run from.
+#[derive(SystemParam)]
+pub struct PlayerSpawnArea<'w, 's> {
+    arena: Res<'w, Arena>,
+    config: Res<'w, GameConfig>,
+    player_query: Query<'w, 's, &'static Transform, With<Player>>,
+}
+
+impl PlayerSpawnArea<'_, '_> {
+    pub fn get(&self) -> SpawnArea {
+        let player_translation = self
+            .player_query
+            .get_single()
+            .map_or(self.arena.center(), |transform| transform.translation);
+        self.arena
+            .spawn_area(player_translation, self.config.player.spawn_clear_radius)
+    }
+}
+
 pub fn spawn_player(
     mut commands: Commands,
-    window_query: Query<&Window, With<PrimaryWindow>>,
+    arena: Res<Arena>,
+    config: Res<GameConfig>,
     asset_handler: Res<AssetHandler>,
 ) {
-    if let Ok(window) = window_query.get_single() {
-        commands.spawn(PlayerBundle::at_center_of_the_screen(
-            window,
-            &asset_handler,
-        ));
-    }
+    commands.spawn(PlayerBundle::at_center_of_the_arena(
+        &arena,
+        &config.player,
+        &asset_handler,
+    ));
 }
 
 pub fn movement(
-    keyboard_input: Res<ButtonInput<KeyCode>>,
+    player_input: Res<PlayerInput>,
     mut player_query: Query<&mut Transform, With<Player>>,
+    config: Res<GameConfig>,
     time: Res<Time>,
 ) {
     if let Ok(mut transform) = player_query.get_single_mut() {
-        let movement_direction = MovementHelper::handle_input(keyboard_input);
-        transform.translation += movement_direction * PLAYER_SPEED * time.delta_secs();
+        transform.translation += player_input.direction() * config.player.speed * time.delta_secs();
     }
 }
 
-pub fn on_hit_enemy_emit_collide_event(
-    mut event_writer: EventWriter<CollidedWithEnemy>,
-    score: Option<Res<Score>>,
+/// Head for the mouse cursor or the touch point, standing still while there is neither
+pub fn steer_towards_pointer(
+    pointer_position: Res<PointerPosition>,
     player_query: Query<&Transform, With<Player>>,
-    enemy_query: Query<&Transform, With<Enemy>>,
+    config: Res<GameConfig>,
+    mut player_input: ResMut<PlayerInput>,
 ) {
-    if let Ok(player_transform) = player_query.get_single() {
-        for enemy_transform in enemy_query.iter() {
-            let is_collided = MovementHelper::is_collided(
-                PLAYER_SIZE,
-                player_transform.translation,
-                ENEMY_SIZE,
-                enemy_transform.translation,
-            );
+    let direction = match (pointer_position.position, player_query.get_single()) {
+        (Some(position), Ok(transform)) => MovementHelper::steer_towards(
+            transform.translation,
+            position.extend(transform.translation.z),
+            config.player.speed,
+            &config.pointer,
+        ),
+        _ => Vec3::ZERO,
+    };
+    *player_input = PlayerInput::from_direction(direction);
+}
+
+/// Filter of a player enemies can hit, neither invulnerable nor on its way out.
+type HittablePlayer = (
+    With<Player>,
+    Without<Invulnerable>,
+    Without<ScheduleDespawn>,
+);
+
+/// Take a life for an enemy touching the player, ending the run on the last one
+pub fn on_hit_enemy_emit_collide_event(
+    mut commands: Commands,
+    (mut event_writer, mut lost_life_writer): (
+        EventWriter<CollidedWithEnemy>,
+        EventWriter<LostLife>,
+    ),
+    (score, mut lives): (Option<Res<Score>>, Option<ResMut<Lives>>),
+    player_query: Query<(Entity, &Transform, &Size, Has<Shield>), HittablePlayer>,
+    enemy_query: Query<(), (With<Enemy>, Without<ScheduleDespawn>)>,
+    spatial_index: Res<SpatialIndex>,
+) {
+    if let Ok((player_entity, player_transform, player_size, is_shielded)) =
+        player_query.get_single()
+    {
+        // A single hit per tick, the player is out of reach right after it.
+        let hit_enemy = spatial_index
+            .colliding_with(player_transform.translation, player_size.value)
+            .find(|entity| enemy_query.contains(*entity));
+
+        if let Some(hit_enemy) = hit_enemy {
+            if is_shielded {
+                // The shield takes the hit, and the enemy with it.
+                commands.entity(player_entity).remove::<Shield>();
+                commands
+                    .entity(hit_enemy)
+                    .insert(ScheduleDespawn::default());
+                return;
+            }
 
-            if is_collided {
-                info!("Event Writer: CollidedWithEnemy");
-                if let Some(score) = &score {
-                    event_writer.send(CollidedWithEnemy { score: score.value });
-                } else {
-                    event_writer.send(CollidedWithEnemy { score: 0 });
+            if let Some(lives) = &mut lives {
+                lives.remaining = lives.remaining.saturating_sub(1);
+                if lives.remaining > 0 {
+                    info!("Event Writer: LostLife");
+                    lost_life_writer.send(LostLife {
+                        lives_left: lives.remaining,
+                    });
+                    return;
                 }
             }
+
+            info!("Event Writer: CollidedWithEnemy");
+            if let Some(score) = &score {
+                event_writer.send(CollidedWithEnemy { score: score.value });
+            } else {
+                event_writer.send(CollidedWithEnemy { score: 0 });
+            }
         }
     }
 }
 
 pub fn on_hit_star_emit_collide_event(
-    player_query: Query<&Transform, With<Player>>,
-    star_query: Query<(Entity, &Transform), (With<Star>, Without<ScheduleDespawn>)>,
+    player_query: Query<(&Transform, &Size), With<Player>>,
+    star_query: Query<(), (With<Star>, Without<ScheduleDespawn>)>,
     mut event_writer: EventWriter<CollidedWithStar>,
+    spatial_index: Res<SpatialIndex>,
 ) {
-    if let Ok(player_transform) = player_query.get_single() {
-        for (star_entity, star_transform) in star_query.iter() {
-            let is_collided = MovementHelper::is_collided(
-                PLAYER_SIZE,
-                player_transform.translation,
-                STAR_SIZE,
-                star_transform.translation,
-            );
-
-            if is_collided {
-                event_writer.send(CollidedWithStar { star_entity });
-            }
+    if let Ok((player_transform, player_size)) = player_query.get_single() {
+        for star_entity in spatial_index
+            .colliding_with(player_transform.translation, player_size.value)
+            .filter(|entity| star_query.contains(*entity))
+        {
+            event_writer.send(CollidedWithStar { star_entity });
         }
     }
 }
@@ -215,13 +307,30 @@ pub fn on_star_collide_play_star_despawn_sound(
 pub fn on_star_collide_event_add_score(
     mut score: Option<ResMut<Score>>,
     mut event_reader: EventReader<CollidedWithStar>,
+    score_multiplier_query: Query<(), (With<Player>, With<ScoreMultiplier>)>,
+    config: Res<GameConfig>,
 ) {
     let Some(score) = &mut score else {
         return;
     };
 
+    let points = if score_multiplier_query.is_empty() {
+        1
+    } else {
+        config.power_up.score_multiplier
+    };
     for _event in event_reader.read() {
-        score.value += 1;
+        score.value += points;
+    }
+}
+
+/// Apply a reloaded config to the player already in the arena
+pub fn on_game_config_changed_update_player(
+    config: Res<GameConfig>,
+    mut player_query: Query<&mut Size, With<Player>>,
+) {
+    for mut size in player_query.iter_mut() {
+        size.value = config.player.size;
     }
 }
 
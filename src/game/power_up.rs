@@ -0,0 +1,391 @@
+use std::time::Duration;
+
+use bevy::prelude::{
+    in_state, resource_changed, App, AssetServer, Color, Commands, Component, Entity, Event,
+    EventReader, EventWriter, FixedUpdate, IntoSystemConfigs, OnEnter, OnExit, Plugin, Query, Res,
+    ResMut, Resource, Sprite, Time, Timer, TimerMode, Transform, Update, With, Without,
+};
+
+use crate::arena::Arena;
+use crate::asset_handler::AssetHandler;
+use crate::game::config::{on_game_config_loaded_apply, GameConfig, PowerUpConfig};
+use crate::game::player::{movement, Player};
+use crate::game::random::{GameRng, RandomSet};
+use crate::game::simulation::{is_simulating, InterpolatedTransform, SimulationSet};
+use crate::game::spatial_index::SpatialIndex;
+use crate::game::star::Star;
+use crate::game::Size;
+use crate::helpers::AudioHelper;
+use crate::{ApplicationState, ScheduleDespawn};
+
+pub struct PowerUpPlugin;
+
+impl Plugin for PowerUpPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PowerUpSpawnTimer>()
+            .add_event::<CollidedWithPowerUp>()
+            .add_systems(
+                OnEnter(ApplicationState::InGame),
+                reset_power_up_spawn_timer,
+            )
+            .add_systems(OnExit(ApplicationState::InGame), despawn_all_power_ups)
+            .add_systems(
+                FixedUpdate,
+                (
+                    tick_power_up_effect::<Shield>,
+                    tick_power_up_effect::<SlowMotion>,
+                    tick_power_up_effect::<Magnet>,
+                    tick_power_up_effect::<ScoreMultiplier>,
+                )
+                    .before(SimulationSet::Movement)
+                    .run_if(in_state(ApplicationState::InGame))
+                    .run_if(is_simulating),
+            )
+            .add_systems(
+                FixedUpdate,
+                (tick_spawn_power_up_overtime, spawn_power_up_overtime)
+                    .chain()
+                    .in_set(RandomSet::PowerUp)
+                    .run_if(in_state(ApplicationState::InGame))
+                    .run_if(is_simulating),
+            )
+            .add_systems(
+                FixedUpdate,
+                magnet_pull_stars
+                    .in_set(SimulationSet::Movement)
+                    .after(movement)
+                    .run_if(in_state(ApplicationState::InGame))
+                    .run_if(is_simulating),
+            )
+            .add_systems(
+                FixedUpdate,
+                (
+                    on_hit_power_up_emit_collide_event,
+                    on_power_up_collide_apply_effect,
+                )
+                    .chain()
+                    .in_set(SimulationSet::Collision)
+                    .run_if(in_state(ApplicationState::InGame))
+                    .run_if(is_simulating),
+            )
+            .add_systems(
+                Update,
+                on_power_up_collide_play_sound.run_if(in_state(ApplicationState::InGame)),
+            )
+            .add_systems(
+                Update,
+                on_game_config_changed_update_power_ups
+                    .after(on_game_config_loaded_apply)
+                    .run_if(resource_changed::<GameConfig>),
+            );
+    }
+}
+
+/// Effect a power-up pickup gives the player.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PowerUpKind {
+    /// Absorbs the next hit of an enemy
+    Shield,
+    /// Slows every enemy down
+    SlowMotion,
+    /// Pulls the stars around the player toward it
+    Magnet,
+    /// Scores more points per star
+    ScoreMultiplier,
+}
+
+impl PowerUpKind {
+    pub const ALL: [PowerUpKind; 4] = [
+        PowerUpKind::Shield,
+        PowerUpKind::SlowMotion,
+        PowerUpKind::Magnet,
+        PowerUpKind::ScoreMultiplier,
+    ];
+
+    /// Pick a kind at random, every kind being as likely
+    pub fn random(rng: &mut GameRng) -> PowerUpKind {
+        let index = (rng.random_f32() * Self::ALL.len() as f32) as usize;
+        Self::ALL[index.min(Self::ALL.len() - 1)]
+    }
+
+    /// Tint of the pickup, and of its countdown in the HUD
+    pub fn color(&self) -> Color {
+        match self {
+            PowerUpKind::Shield => Color::srgb(0.3, 0.7, 1.0),
+            PowerUpKind::SlowMotion => Color::srgb(0.6, 0.4, 1.0),
+            PowerUpKind::Magnet => Color::srgb(1.0, 0.4, 0.4),
+            PowerUpKind::ScoreMultiplier => Color::srgb(0.4, 1.0, 0.4),
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            PowerUpKind::Shield => "Shield",
+            PowerUpKind::SlowMotion => "Slow",
+            PowerUpKind::Magnet => "Magnet",
+            PowerUpKind::ScoreMultiplier => "Multiplier",
+        }
+    }
+}
+
+/// Pickup in the arena, giving its effect to the player touching it.
+#[derive(Component)]
+#[require(InterpolatedTransform)]
+pub struct PowerUp {
+    pub kind: PowerUpKind,
+}
+
+#[derive(Event)]
+pub struct CollidedWithPowerUp {
+    pub power_up_entity: Entity,
+    pub kind: PowerUpKind,
+}
+
+/// Effect of a power-up on the player, removed once its timer runs out.
+pub trait PowerUpEffect: Component {
+    fn timer(&self) -> &Timer;
+
+    fn timer_mut(&mut self) -> &mut Timer;
+
+    fn remaining_secs(&self) -> f32 {
+        self.timer().remaining_secs()
+    }
+}
+
+#[derive(Component)]
+pub struct Shield {
+    pub timer: Timer,
+}
+
+#[derive(Component)]
+pub struct SlowMotion {
+    pub timer: Timer,
+}
+
+#[derive(Component)]
+pub struct Magnet {
+    pub timer: Timer,
+}
+
+#[derive(Component)]
+pub struct ScoreMultiplier {
+    pub timer: Timer,
+}
+
+impl PowerUpEffect for Shield {
+    fn timer(&self) -> &Timer {
+        &self.timer
+    }
+
+    fn timer_mut(&mut self) -> &mut Timer {
+        &mut self.timer
+    }
+}
+
+impl PowerUpEffect for SlowMotion {
+    fn timer(&self) -> &Timer {
+        &self.timer
+    }
+
+    fn timer_mut(&mut self) -> &mut Timer {
+        &mut self.timer
+    }
+}
+
+impl PowerUpEffect for Magnet {
+    fn timer(&self) -> &Timer {
+        &self.timer
+    }
+
+    fn timer_mut(&mut self) -> &mut Timer {
+        &mut self.timer
+    }
+}
+
+impl PowerUpEffect for ScoreMultiplier {
+    fn timer(&self) -> &Timer {
+        &self.timer
+    }
+
+    fn timer_mut(&mut self) -> &mut Timer {
+        &mut self.timer
+    }
+}
+
+#[derive(Resource)]
+pub struct PowerUpSpawnTimer {
+    pub timer: Timer,
+}
+
+impl Default for PowerUpSpawnTimer {
+    fn default() -> Self {
+        Self::new(PowerUpConfig::default().spawn_time)
+    }
+}
+
+impl PowerUpSpawnTimer {
+    pub fn new(spawn_time: f32) -> Self {
+        Self {
+            timer: Timer::from_seconds(spawn_time, TimerMode::Repeating),
+        }
+    }
+}
+
+/// Start every run with a fresh timer, so a run does not depend on the previous one
+pub fn reset_power_up_spawn_timer(
+    mut power_up_spawn_timer: ResMut<PowerUpSpawnTimer>,
+    config: Res<GameConfig>,
+) {
+    *power_up_spawn_timer = PowerUpSpawnTimer::new(config.power_up.spawn_time);
+}
+
+pub fn tick_spawn_power_up_overtime(
+    time: Res<Time>,
+    mut power_up_spawn_timer: ResMut<PowerUpSpawnTimer>,
+) {
+    power_up_spawn_timer.timer.tick(time.delta());
+}
+
+pub fn spawn_power_up_overtime(
+    mut commands: Commands,
+    power_up_spawn_timer: Res<PowerUpSpawnTimer>,
+    arena: Res<Arena>,
+    config: Res<GameConfig>,
+    asset_handler: Res<AssetHandler>,
+    mut rng: ResMut<GameRng>,
+) {
+    if power_up_spawn_timer.timer.just_finished() {
+        let kind = PowerUpKind::random(&mut rng);
+        commands.spawn((
+            PowerUp { kind },
+            Size::new(config.power_up.size),
+            Sprite {
+                color: kind.color(),
+                ..Sprite::from_image(asset_handler.star_texture.clone())
+            },
+            Transform::from_translation(arena.random_point(&mut rng)),
+        ));
+    }
+}
+
+/// Run the timer of an effect down, and take the effect away from the player once it is over
+pub fn tick_power_up_effect<E: PowerUpEffect>(
+    mut commands: Commands,
+    mut query: Query<(Entity, &mut E)>,
+    time: Res<Time>,
+) {
+    for (entity, mut effect) in query.iter_mut() {
+        effect.timer_mut().tick(time.delta());
+        if effect.timer().finished() {
+            commands.entity(entity).remove::<E>();
+        }
+    }
+}
+
+pub fn magnet_pull_stars(
+    player_query: Query<&Transform, (With<Player>, With<Magnet>)>,
+    mut star_query: Query<&mut Transform, (With<Star>, Without<Player>)>,
+    config: Res<GameConfig>,
+    time: Res<Time>,
+) {
+    let Ok(player_transform) = player_query.get_single() else {
+        return;
+    };
+
+    let step = config.power_up.magnet_speed * time.delta_secs();
+    for mut star_transform in star_query.iter_mut() {
+        let offset = player_transform.translation - star_transform.translation;
+        let distance = offset.length();
+        if distance > 0.0 && distance <= config.power_up.magnet_radius {
+            // Never overshoot the player, a star close enough is simply picked up.
+            star_transform.translation += offset / distance * step.min(distance);
+        }
+    }
+}
+
+pub fn on_hit_power_up_emit_collide_event(
+    player_query: Query<(&Transform, &Size), With<Player>>,
+    power_up_query: Query<&PowerUp, Without<ScheduleDespawn>>,
+    mut event_writer: EventWriter<CollidedWithPowerUp>,
+    spatial_index: Res<SpatialIndex>,
+) {
+    if let Ok((player_transform, player_size)) = player_query.get_single() {
+        for power_up_entity in
+            spatial_index.colliding_with(player_transform.translation, player_size.value)
+        {
+            if let Ok(power_up) = power_up_query.get(power_up_entity) {
+                event_writer.send(CollidedWithPowerUp {
+                    power_up_entity,
+                    kind: power_up.kind,
+                });
+            }
+        }
+    }
+}
+
+/// Give the player the effect of the power-up it picked up, restarting it if already active
+pub fn on_power_up_collide_apply_effect(
+    mut commands: Commands,
+    mut event_reader: EventReader<CollidedWithPowerUp>,
+    player_query: Query<Entity, With<Player>>,
+    config: Res<GameConfig>,
+) {
+    let Ok(player_entity) = player_query.get_single() else {
+        return;
+    };
+
+    let power_up_config = &config.power_up;
+    for event in event_reader.read() {
+        if let Some(mut entity_commands) = commands.get_entity(event.power_up_entity) {
+            entity_commands.insert(ScheduleDespawn::default());
+        }
+
+        let mut player_commands = commands.entity(player_entity);
+        match event.kind {
+            PowerUpKind::Shield => player_commands.insert(Shield {
+                timer: Timer::from_seconds(power_up_config.shield_time, TimerMode::Once),
+            }),
+            PowerUpKind::SlowMotion => player_commands.insert(SlowMotion {
+                timer: Timer::from_seconds(power_up_config.slow_motion_time, TimerMode::Once),
+            }),
+            PowerUpKind::Magnet => player_commands.insert(Magnet {
+                timer: Timer::from_seconds(power_up_config.magnet_time, TimerMode::Once),
+            }),
+            PowerUpKind::ScoreMultiplier => player_commands.insert(ScoreMultiplier {
+                timer: Timer::from_seconds(power_up_config.score_multiplier_time, TimerMode::Once),
+            }),
+        };
+    }
+}
+
+pub fn on_power_up_collide_play_sound(
+    mut commands: Commands,
+    mut event_reader: EventReader<CollidedWithPowerUp>,
+    asset_server: Res<AssetServer>,
+) {
+    for _event in event_reader.read() {
+        commands.spawn(AudioHelper::play_obtain_star_sound(&asset_server));
+    }
+}
+
+/// Apply a reloaded config to the pickups already in the arena, keeping the spawn progress
+pub fn on_game_config_changed_update_power_ups(
+    config: Res<GameConfig>,
+    mut power_up_spawn_timer: ResMut<PowerUpSpawnTimer>,
+    mut power_up_query: Query<&mut Size, With<PowerUp>>,
+) {
+    power_up_spawn_timer
+        .timer
+        .set_duration(Duration::from_secs_f32(config.power_up.spawn_time));
+    for mut size in power_up_query.iter_mut() {
+        size.value = config.power_up.size;
+    }
+}
+
+pub fn despawn_all_power_ups(mut commands: Commands, query: Query<Entity, With<PowerUp>>) {
+    for power_up_entity in query.iter() {
+        commands
+            .entity(power_up_entity)
+            .insert(ScheduleDespawn::default());
+    }
+}
@@ -0,0 +1,113 @@
+use bevy::prelude::{
+    info, App, FixedUpdate, IntoSystemConfigs, IntoSystemSetConfigs, OnEnter, Plugin, Res, ResMut,
+    Resource, SystemSet,
+};
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+use crate::game::simulation::SimulationSet;
+use crate::game::wave::WAVE_START;
+use crate::ApplicationState;
+
+pub struct RandomPlugin;
+
+impl Plugin for RandomPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<RunSeed>()
+            .init_resource::<GameRng>()
+            .configure_sets(
+                OnEnter(ApplicationState::InGame),
+                (RandomSet::Reseed, RandomSet::Enemy, RandomSet::Star).chain(),
+            )
+            .configure_sets(WAVE_START, (RandomSet::Enemy, RandomSet::Star).chain())
+            .configure_sets(
+                FixedUpdate,
+                (RandomSet::Enemy, RandomSet::Star, RandomSet::PowerUp)
+                    .chain()
+                    .in_set(SimulationSet::Movement),
+            )
+            .configure_sets(
+                FixedUpdate,
+                RandomSet::Collision.in_set(SimulationSet::Collision),
+            )
+            .add_systems(
+                OnEnter(ApplicationState::InGame),
+                reseed_game_rng.in_set(RandomSet::Reseed),
+            );
+    }
+}
+
+/// Systems drawing from [`GameRng`], run in a fixed order so the draws are always the same.
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub enum RandomSet {
+    Reseed,
+    Enemy,
+    Star,
+    PowerUp,
+    /// Collisions drawing from it, after every unit has moved
+    Collision,
+}
+
+/// Environment variable that fixes the seed of every run, e.g. to reproduce a bug report.
+pub const SEED_ENVIRONMENT_VARIABLE: &str = "BEVY_BALL_SEED";
+
+/// Seed to use for the next runs. `None` picks a fresh random seed for each run.
+#[derive(Resource, Debug, Default, Clone)]
+pub struct RunSeed {
+    pub seed: Option<u64>,
+}
+
+impl RunSeed {
+    pub fn fixed(seed: u64) -> Self {
+        Self { seed: Some(seed) }
+    }
+
+    /// Read the seed from [`SEED_ENVIRONMENT_VARIABLE`], if set to a valid number
+    pub fn from_env() -> Self {
+        let seed = std::env::var(SEED_ENVIRONMENT_VARIABLE)
+            .ok()
+            .and_then(|value| value.trim().parse().ok());
+        Self { seed }
+    }
+}
+
+/// Single source of randomness of the simulation.
+///
+/// Every random decision of a run draws from it, so a run is reproduced by its seed alone.
+/// ChaCha8 keeps its stream across `rand` versions and platforms, unlike `StdRng`, so saved
+/// replays stay valid.
+#[derive(Resource, Debug)]
+pub struct GameRng {
+    seed: u64,
+    rng: ChaCha8Rng,
+}
+
+impl Default for GameRng {
+    fn default() -> Self {
+        Self::from_seed(0)
+    }
+}
+
+impl GameRng {
+    pub fn from_seed(seed: u64) -> Self {
+        Self {
+            seed,
+            rng: ChaCha8Rng::seed_from_u64(seed),
+        }
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Return a random number in `[0, 1)`
+    pub fn random_f32(&mut self) -> f32 {
+        self.rng.random::<f32>()
+    }
+}
+
+pub fn reseed_game_rng(run_seed: Res<RunSeed>, mut game_rng: ResMut<GameRng>) {
+    let seed = run_seed.seed.unwrap_or_else(rand::random);
+    *game_rng = GameRng::from_seed(seed);
+    info!("Run seed: {}", seed);
+}
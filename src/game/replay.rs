@@ -0,0 +1,309 @@
+use std::path::Path;
+
+use bevy::prelude::{
+    in_state, info, warn, App, Commands, EventReader, FixedUpdate, IntoSystemConfigs, OnEnter,
+    OnExit, Plugin, Res, ResMut, Resource, Update,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::game::config::GameConfig;
+use crate::game::input::{input_source_is, PlayerInput, PlayerInputSet, PlayerInputSource};
+use crate::game::player::CollidedWithEnemy;
+use crate::game::random::{reseed_game_rng, GameRng, RandomSet, RunSeed};
+use crate::game::simulation::{is_simulating, TickRate};
+use crate::storage::{StorageDirectory, StorageError, StorageHelper};
+use crate::ApplicationState;
+
+pub struct ReplayPlugin;
+
+impl Plugin for ReplayPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ReplayMode>()
+            .init_resource::<StorageDirectory>()
+            .add_systems(
+                OnEnter(ApplicationState::InGame),
+                (
+                    prepare_replay.before(reseed_game_rng),
+                    start_replay.after(RandomSet::Reseed),
+                ),
+            )
+            .add_systems(OnExit(ApplicationState::InGame), remove_replay_resource)
+            .add_systems(
+                FixedUpdate,
+                feed_replay_tick
+                    .in_set(PlayerInputSet)
+                    .run_if(in_state(ApplicationState::InGame))
+                    .run_if(is_simulating)
+                    .run_if(input_source_is(PlayerInputSource::Replay)),
+            )
+            .add_systems(
+                FixedUpdate,
+                record_replay_tick
+                    .after(PlayerInputSet)
+                    .run_if(in_state(ApplicationState::InGame))
+                    .run_if(is_simulating)
+                    .run_if(input_source_is(PlayerInputSource::Live)),
+            )
+            .add_systems(Update, on_collided_with_enemy_finish_replay);
+    }
+}
+
+pub const REPLAY_FILE_NAME: &str = "replays/last_run.ron";
+pub const REPLAY_SAVE_VERSION: u32 = 3;
+
+/// Environment variable pointing at a replay file to play back instead of reading the keyboard and gamepads.
+pub const REPLAY_ENVIRONMENT_VARIABLE: &str = "BEVY_BALL_REPLAY";
+
+/// Consecutive simulation ticks that share the same input.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ReplaySpan {
+    pub ticks: u32,
+    pub input: PlayerInput,
+}
+
+/// Everything needed to simulate a run again: its seed, tick rate, tuning and the input of every
+/// tick.
+///
+/// Version 1 recorded variable frame times and cannot be played back on the fixed timestep.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Replay {
+    pub seed: u64,
+    pub tick_rate: f64,
+    /// [`GameConfig::fingerprint`] of the tuning of the run, unknown for replays of version 2
+    pub config_fingerprint: Option<u64>,
+    pub spans: Vec<ReplaySpan>,
+    /// Score sent with `CollidedWithEnemy`, once the run is over
+    pub final_score: Option<u32>,
+}
+
+/// Save format of version 2, before replays held the fingerprint of their tuning.
+#[derive(Deserialize)]
+struct ReplayV2 {
+    seed: u64,
+    tick_rate: f64,
+    spans: Vec<ReplaySpan>,
+    final_score: Option<u32>,
+}
+
+impl From<ReplayV2> for Replay {
+    fn from(replay: ReplayV2) -> Self {
+        Self {
+            seed: replay.seed,
+            tick_rate: replay.tick_rate,
+            config_fingerprint: None,
+            spans: replay.spans,
+            final_score: replay.final_score,
+        }
+    }
+}
+
+impl Replay {
+    pub fn new(seed: u64, tick_rate: f64, config: &GameConfig) -> Self {
+        Self {
+            seed,
+            tick_rate,
+            config_fingerprint: Some(config.fingerprint()),
+            spans: Vec::new(),
+            final_score: None,
+        }
+    }
+
+    /// Whether the run was played with `config`, without which playback is unlikely to reproduce it
+    pub fn is_recorded_with(&self, config: &GameConfig) -> bool {
+        self.config_fingerprint == Some(config.fingerprint())
+    }
+
+    /// Append a tick, merging it into the last span when the input did not change
+    pub fn push(&mut self, input: PlayerInput) {
+        if let Some(last) = self.spans.last_mut() {
+            if last.input == input {
+                last.ticks += 1;
+                return;
+            }
+        }
+        self.spans.push(ReplaySpan { ticks: 1, input });
+    }
+
+    pub fn tick_count(&self) -> u32 {
+        self.spans.iter().map(|span| span.ticks).sum()
+    }
+
+    pub fn load_from(path: &Path) -> Result<Option<Replay>, StorageError> {
+        let Some(content) = StorageHelper::read(path)? else {
+            return Ok(None);
+        };
+
+        match StorageHelper::read_version(&content)? {
+            2 => Ok(Some(StorageHelper::parse::<ReplayV2>(&content)?.into())),
+            REPLAY_SAVE_VERSION => StorageHelper::parse(&content).map(Some),
+            version => Err(StorageError::UnsupportedVersion {
+                found: version,
+                supported: REPLAY_SAVE_VERSION,
+            }),
+        }
+    }
+
+    pub fn save_to(&self, path: &Path) -> Result<(), StorageError> {
+        StorageHelper::save(path, REPLAY_SAVE_VERSION, self)
+    }
+}
+
+/// Whether runs are recorded, or a recorded run is played back.
+#[derive(Resource, Debug, Default, Clone)]
+pub enum ReplayMode {
+    #[default]
+    Record,
+    Playback(Replay),
+}
+
+impl ReplayMode {
+    /// Play back the replay named by [`REPLAY_ENVIRONMENT_VARIABLE`], if any
+    pub fn from_env() -> Self {
+        let Ok(path) = std::env::var(REPLAY_ENVIRONMENT_VARIABLE) else {
+            return ReplayMode::Record;
+        };
+
+        match Replay::load_from(Path::new(&path)) {
+            Ok(Some(replay)) => {
+                info!("Playing back {} ({} ticks)", path, replay.tick_count());
+                ReplayMode::Playback(replay)
+            }
+            Ok(None) => {
+                warn!("Replay {} does not exist, recording instead", path);
+                ReplayMode::Record
+            }
+            Err(error) => {
+                warn!(
+                    "Could not load replay {}: {}, recording instead",
+                    path, error
+                );
+                ReplayMode::Record
+            }
+        }
+    }
+}
+
+/// Replay of the current run, being either recorded or played back.
+#[derive(Resource, Debug)]
+pub struct ActiveReplay {
+    pub replay: Replay,
+    span: usize,
+    tick_in_span: u32,
+}
+
+impl ActiveReplay {
+    pub fn new(replay: Replay) -> Self {
+        Self {
+            replay,
+            span: 0,
+            tick_in_span: 0,
+        }
+    }
+
+    /// Return the input of the next tick to play back, or `None` once the replay is exhausted
+    pub fn next_input(&mut self) -> Option<PlayerInput> {
+        let span = self.replay.spans.get(self.span)?;
+        let input = span.input;
+
+        self.tick_in_span += 1;
+        if self.tick_in_span >= span.ticks {
+            self.span += 1;
+            self.tick_in_span = 0;
+        }
+        Some(input)
+    }
+}
+
+/// Simulate a played back run with the seed and tick rate it was recorded with
+pub fn prepare_replay(
+    replay_mode: Res<ReplayMode>,
+    mut run_seed: ResMut<RunSeed>,
+    mut tick_rate: ResMut<TickRate>,
+) {
+    if let ReplayMode::Playback(replay) = replay_mode.as_ref() {
+        *run_seed = RunSeed::fixed(replay.seed);
+        tick_rate.hz = replay.tick_rate;
+    }
+}
+
+pub fn start_replay(
+    mut commands: Commands,
+    replay_mode: Res<ReplayMode>,
+    game_rng: Res<GameRng>,
+    tick_rate: Res<TickRate>,
+    config: Res<GameConfig>,
+    mut input_source: ResMut<PlayerInputSource>,
+) {
+    match replay_mode.as_ref() {
+        ReplayMode::Record => {
+            let replay = Replay::new(game_rng.seed(), tick_rate.hz, &config);
+            commands.insert_resource(ActiveReplay::new(replay));
+            *input_source = PlayerInputSource::Live;
+        }
+        ReplayMode::Playback(replay) => {
+            if !replay.is_recorded_with(&config) {
+                warn!(
+                    "Replay was not recorded with the current game config, it will likely diverge"
+                );
+            }
+            commands.insert_resource(ActiveReplay::new(replay.clone()));
+            *input_source = PlayerInputSource::Replay;
+        }
+    }
+}
+
+pub fn remove_replay_resource(mut commands: Commands) {
+    commands.remove_resource::<ActiveReplay>();
+}
+
+pub fn feed_replay_tick(
+    active_replay: Option<ResMut<ActiveReplay>>,
+    mut player_input: ResMut<PlayerInput>,
+) {
+    if let Some(mut active_replay) = active_replay {
+        *player_input = active_replay.next_input().unwrap_or_default();
+    }
+}
+
+pub fn record_replay_tick(
+    active_replay: Option<ResMut<ActiveReplay>>,
+    player_input: Res<PlayerInput>,
+) {
+    if let Some(mut active_replay) = active_replay {
+        active_replay.replay.push(*player_input);
+    }
+}
+
+pub fn on_collided_with_enemy_finish_replay(
+    mut event_reader: EventReader<CollidedWithEnemy>,
+    active_replay: Option<ResMut<ActiveReplay>>,
+    replay_mode: Res<ReplayMode>,
+    storage: Res<StorageDirectory>,
+) {
+    let Some(mut active_replay) = active_replay else {
+        return;
+    };
+
+    for event in event_reader.read() {
+        match replay_mode.as_ref() {
+            ReplayMode::Record => {
+                active_replay.replay.final_score = Some(event.score);
+                let path = storage.file(REPLAY_FILE_NAME);
+                match active_replay.replay.save_to(&path) {
+                    Ok(()) => info!("Replay saved to {}", path.display()),
+                    Err(error) => warn!("Could not save replay to {}: {}", path.display(), error),
+                }
+            }
+            ReplayMode::Playback(replay) => match replay.final_score {
+                Some(expected) if expected == event.score => {
+                    info!("Replay reproduced the final score of {}", event.score)
+                }
+                Some(expected) => warn!(
+                    "Replay diverged: final score {} but {} was recorded",
+                    event.score, expected
+                ),
+                None => info!("Replay ended with a final score of {}", event.score),
+            },
+        }
+    }
+}
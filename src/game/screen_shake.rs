@@ -0,0 +1,87 @@
+use bevy::math::Vec3;
+use bevy::prelude::{
+    App, EventReader, IntoSystemConfigs, Plugin, Query, Res, ResMut, Resource, Time, Transform,
+    Update, With,
+};
+
+use crate::arena::{fit_camera_to_window, Arena, ArenaCamera};
+use crate::game::lives::LostLife;
+use crate::game::player::CollidedWithEnemy;
+use crate::settings::Settings;
+
+/// Arena units the camera strays from the center at full trauma and full screen shake.
+const MAX_SHAKE_OFFSET: f32 = 16.0;
+/// Trauma lost per second.
+const SHAKE_DECAY: f32 = 1.5;
+/// Radians per second the shake direction turns.
+const SHAKE_FREQUENCY: f32 = 60.0;
+/// Trauma added by a hit costing a life, the last hit adding a full one.
+const LOST_LIFE_TRAUMA: f32 = 0.6;
+
+pub struct ScreenShakePlugin;
+
+impl Plugin for ScreenShakePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ScreenShake>().add_systems(
+            Update,
+            (on_hit_add_trauma, shake_camera)
+                .chain()
+                .after(fit_camera_to_window),
+        );
+    }
+}
+
+/// How shaken the camera is, from 0 to 1, wearing off over time.
+///
+/// The camera offset grows with the square of the trauma, so small hits barely show.
+#[derive(Resource, Debug, Default)]
+pub struct ScreenShake {
+    pub trauma: f32,
+}
+
+impl ScreenShake {
+    pub fn add_trauma(&mut self, trauma: f32) {
+        self.trauma = (self.trauma + trauma).min(1.0);
+    }
+}
+
+pub fn on_hit_add_trauma(
+    mut lost_life_reader: EventReader<LostLife>,
+    mut collided_with_enemy_reader: EventReader<CollidedWithEnemy>,
+    mut screen_shake: ResMut<ScreenShake>,
+) {
+    for _event in lost_life_reader.read() {
+        screen_shake.add_trauma(LOST_LIFE_TRAUMA);
+    }
+    for _event in collided_with_enemy_reader.read() {
+        screen_shake.add_trauma(1.0);
+    }
+}
+
+/// Move the camera off the center of the arena by the trauma and the screen shake setting
+pub fn shake_camera(
+    mut screen_shake: ResMut<ScreenShake>,
+    mut camera_query: Query<&mut Transform, With<ArenaCamera>>,
+    arena: Res<Arena>,
+    settings: Res<Settings>,
+    time: Res<Time>,
+) {
+    if screen_shake.trauma == 0.0 {
+        // Settle back on the center, wherever the last shaken frame left the camera.
+        for mut transform in camera_query.iter_mut() {
+            if transform.translation != arena.center() {
+                transform.translation = arena.center();
+            }
+        }
+        return;
+    }
+    screen_shake.trauma = (screen_shake.trauma - SHAKE_DECAY * time.delta_secs()).max(0.0);
+
+    // Kept away from `GameRng`, the shake is not part of the simulation.
+    let angle = time.elapsed_secs() * SHAKE_FREQUENCY;
+    let direction = Vec3::new(angle.sin(), (angle * 1.3).cos(), 0.0);
+    let offset = direction * MAX_SHAKE_OFFSET * settings.screen_shake * screen_shake.trauma.powi(2);
+    for mut transform in camera_query.iter_mut() {
+        transform.translation = arena.center() + offset;
+    }
+}
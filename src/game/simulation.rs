@@ -0,0 +1,147 @@
+use bevy::app::{RunFixedMainLoop, RunFixedMainLoopSystem};
+use bevy::ecs::component::ComponentId;
+use bevy::ecs::world::DeferredWorld;
+use bevy::math::Vec3;
+use bevy::prelude::{
+    App, Component, DetectChanges, Entity, Fixed, FixedFirst, FixedLast, FixedUpdate,
+    IntoSystemConfigs, IntoSystemSetConfigs, Plugin, Query, Res, ResMut, Resource, State,
+    SystemSet, Time, Transform,
+};
+
+use crate::game::wave::Wave;
+use crate::game::GameState;
+
+pub struct SimulationPlugin;
+
+impl Plugin for SimulationPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<TickRate>()
+            .configure_sets(
+                FixedUpdate,
+                (
+                    SimulationSet::Input,
+                    SimulationSet::Movement,
+                    SimulationSet::Confinement,
+                    SimulationSet::Indexing,
+                    SimulationSet::Collision,
+                )
+                    .chain(),
+            )
+            .add_systems(
+                RunFixedMainLoop,
+                (apply_tick_rate, restore_simulated_translation)
+                    .in_set(RunFixedMainLoopSystem::BeforeFixedMainLoop),
+            )
+            .add_systems(FixedFirst, store_previous_translation)
+            .add_systems(FixedLast, store_current_translation)
+            .add_systems(
+                RunFixedMainLoop,
+                interpolate_translation.in_set(RunFixedMainLoopSystem::AfterFixedMainLoop),
+            );
+    }
+}
+
+/// Steps of a simulation tick in `FixedUpdate`, run in this order.
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub enum SimulationSet {
+    /// Decide what the player asks for this tick
+    Input,
+    /// Move, bounce and spawn entities
+    Movement,
+    /// Keep confined entities inside the window
+    Confinement,
+    /// Index where the entities ended up, see [`crate::game::spatial_index::SpatialIndex`]
+    Indexing,
+    /// Detect collisions and react to them
+    Collision,
+}
+
+/// Run condition of the systems simulating a run: it is running, and its wave did not end on an
+/// earlier tick of this frame, see [`Wave::is_over`].
+pub fn is_simulating(state: Option<Res<State<GameState>>>, wave: Option<Res<Wave>>) -> bool {
+    state.is_some_and(|state| *state.get() == GameState::Running)
+        && !wave.is_some_and(|wave| wave.is_over)
+}
+
+pub const DEFAULT_TICK_RATE: f64 = 60.0;
+
+/// Number of simulation ticks per second, independent of the frame rate.
+#[derive(Resource, Debug, Clone, Copy, PartialEq)]
+pub struct TickRate {
+    pub hz: f64,
+}
+
+impl Default for TickRate {
+    fn default() -> Self {
+        Self {
+            hz: DEFAULT_TICK_RATE,
+        }
+    }
+}
+
+pub fn apply_tick_rate(tick_rate: Res<TickRate>, mut time: ResMut<Time<Fixed>>) {
+    if tick_rate.is_changed() {
+        time.set_timestep_hz(tick_rate.hz);
+    }
+}
+
+/// Smooths the rendered position of an entity between two simulation ticks.
+///
+/// Gameplay systems in `FixedUpdate` see the simulated translation in `Transform`, other schedules
+/// see it interpolated between the last two ticks.
+#[derive(Component, Debug, Default, Clone, Copy, PartialEq)]
+#[component(on_add = initialize_interpolated_transform)]
+pub struct InterpolatedTransform {
+    pub previous: Vec3,
+    pub current: Vec3,
+}
+
+impl InterpolatedTransform {
+    /// Translation at `fraction` of the way from the previous tick to the current one
+    pub fn translation_at(&self, fraction: f32) -> Vec3 {
+        self.previous.lerp(self.current, fraction)
+    }
+}
+
+/// Start from the spawn translation, so new entities are not drawn sliding in from the origin.
+fn initialize_interpolated_transform(mut world: DeferredWorld, entity: Entity, _: ComponentId) {
+    let Some(translation) = world
+        .get::<Transform>(entity)
+        .map(|transform| transform.translation)
+    else {
+        return;
+    };
+
+    if let Some(mut interpolated_transform) = world.get_mut::<InterpolatedTransform>(entity) {
+        interpolated_transform.previous = translation;
+        interpolated_transform.current = translation;
+    }
+}
+
+pub fn restore_simulated_translation(mut query: Query<(&mut Transform, &InterpolatedTransform)>) {
+    for (mut transform, interpolated_transform) in query.iter_mut() {
+        transform.translation = interpolated_transform.current;
+    }
+}
+
+pub fn store_previous_translation(mut query: Query<(&Transform, &mut InterpolatedTransform)>) {
+    for (transform, mut interpolated_transform) in query.iter_mut() {
+        interpolated_transform.previous = transform.translation;
+    }
+}
+
+pub fn store_current_translation(mut query: Query<(&Transform, &mut InterpolatedTransform)>) {
+    for (transform, mut interpolated_transform) in query.iter_mut() {
+        interpolated_transform.current = transform.translation;
+    }
+}
+
+pub fn interpolate_translation(
+    time: Res<Time<Fixed>>,
+    mut query: Query<(&mut Transform, &InterpolatedTransform)>,
+) {
+    let fraction = time.overstep_fraction();
+    for (mut transform, interpolated_transform) in query.iter_mut() {
+        transform.translation = interpolated_transform.translation_at(fraction);
+    }
+}
@@ -0,0 +1,185 @@
+use std::collections::HashMap;
+
+use bevy::math::{IVec2, Vec3};
+use bevy::prelude::{
+    in_state, App, Entity, FixedUpdate, IntoSystemConfigs, Plugin, Query, ResMut, Resource,
+    Transform,
+};
+
+use crate::game::simulation::{is_simulating, SimulationSet};
+use crate::game::Size;
+use crate::helpers::MovementHelper;
+use crate::ApplicationState;
+
+/// Side of a grid cell, around the size of the units so a query only looks at a few cells.
+pub const DEFAULT_CELL_SIZE: f32 = 64.0;
+
+pub struct SpatialIndexPlugin;
+
+impl Plugin for SpatialIndexPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SpatialIndex>().add_systems(
+            FixedUpdate,
+            rebuild_spatial_index
+                .in_set(SimulationSet::Indexing)
+                .run_if(in_state(ApplicationState::InGame))
+                .run_if(is_simulating),
+        );
+    }
+}
+
+/// Where a unit of the [`SpatialIndex`] stands.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SpatialEntry {
+    pub translation: Vec3,
+    pub size: f32,
+    cell: IVec2,
+}
+
+/// Uniform grid of every unit with a `Transform` and a [`Size`], the broad-phase of collisions.
+///
+/// Rebuilt at every tick once units are moved and confined. Collision systems moving units around
+/// keep it up to date with [`SpatialIndex::update`], so the systems after them see the new places.
+#[derive(Resource, Debug)]
+pub struct SpatialIndex {
+    cell_size: f32,
+    /// Units of each cell, by the cell of their center
+    cells: HashMap<IVec2, Vec<Entity>>,
+    entries: HashMap<Entity, SpatialEntry>,
+    /// Size of the biggest unit, how far a query looks past its own reach
+    max_size: f32,
+}
+
+impl Default for SpatialIndex {
+    fn default() -> Self {
+        Self::new(DEFAULT_CELL_SIZE)
+    }
+}
+
+impl SpatialIndex {
+    pub fn new(cell_size: f32) -> Self {
+        Self {
+            cell_size,
+            cells: HashMap::new(),
+            entries: HashMap::new(),
+            max_size: 0.0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn get(&self, entity: Entity) -> Option<&SpatialEntry> {
+        self.entries.get(&entity)
+    }
+
+    /// Forget every unit, keeping the cells allocated for the next rebuild
+    pub fn clear(&mut self) {
+        for units in self.cells.values_mut() {
+            units.clear();
+        }
+        self.entries.clear();
+        self.max_size = 0.0;
+    }
+
+    /// Replace every unit with `units`
+    pub fn rebuild(&mut self, units: impl IntoIterator<Item = (Entity, Vec3, f32)>) {
+        self.clear();
+        for (entity, translation, size) in units {
+            self.insert(entity, translation, size);
+        }
+    }
+
+    /// Add a unit, or move it if it is already indexed
+    pub fn insert(&mut self, entity: Entity, translation: Vec3, size: f32) {
+        self.remove(entity);
+
+        let cell = self.cell_of(translation);
+        self.cells.entry(cell).or_default().push(entity);
+        self.entries.insert(
+            entity,
+            SpatialEntry {
+                translation,
+                size,
+                cell,
+            },
+        );
+        self.max_size = self.max_size.max(size);
+    }
+
+    /// Move an indexed unit to `translation`, ignoring units that are not indexed
+    pub fn update(&mut self, entity: Entity, translation: Vec3) {
+        let cell = self.cell_of(translation);
+        let Some(entry) = self.entries.get_mut(&entity) else {
+            return;
+        };
+
+        let previous_cell = entry.cell;
+        entry.translation = translation;
+        entry.cell = cell;
+        if previous_cell != cell {
+            self.remove_from_cell(previous_cell, entity);
+            self.cells.entry(cell).or_default().push(entity);
+        }
+    }
+
+    pub fn remove(&mut self, entity: Entity) {
+        if let Some(entry) = self.entries.remove(&entity) {
+            self.remove_from_cell(entry.cell, entity);
+        }
+    }
+
+    /// Units colliding with a unit of `size` at `translation`, itself included if it is indexed
+    pub fn colliding_with(
+        &self,
+        translation: Vec3,
+        size: f32,
+    ) -> impl Iterator<Item = Entity> + '_ {
+        // A unit is indexed by its center, so look as far as the biggest unit can reach.
+        let reach = (size + self.max_size) / 2.0;
+        let min_cell = self.cell_of(translation - Vec3::new(reach, reach, 0.0));
+        let max_cell = self.cell_of(translation + Vec3::new(reach, reach, 0.0));
+
+        (min_cell.y..=max_cell.y)
+            .flat_map(move |y| (min_cell.x..=max_cell.x).map(move |x| IVec2::new(x, y)))
+            .filter_map(move |cell| self.cells.get(&cell))
+            .flatten()
+            .copied()
+            .filter(move |entity| {
+                let entry = &self.entries[entity];
+                MovementHelper::is_collided(size, translation, entry.size, entry.translation)
+            })
+    }
+
+    fn cell_of(&self, translation: Vec3) -> IVec2 {
+        IVec2::new(
+            (translation.x / self.cell_size).floor() as i32,
+            (translation.y / self.cell_size).floor() as i32,
+        )
+    }
+
+    fn remove_from_cell(&mut self, cell: IVec2, entity: Entity) {
+        if let Some(units) = self.cells.get_mut(&cell) {
+            if let Some(index) = units.iter().position(|unit| *unit == entity) {
+                // Keep the order of the cell, so queries stay deterministic.
+                units.remove(index);
+            }
+        }
+    }
+}
+
+pub fn rebuild_spatial_index(
+    mut spatial_index: ResMut<SpatialIndex>,
+    query: Query<(Entity, &Transform, &Size)>,
+) {
+    spatial_index.rebuild(
+        query
+            .iter()
+            .map(|(entity, transform, size)| (entity, transform.translation, size.value)),
+    );
+}
@@ -1,14 +1,19 @@
-use bevy::app::{App, Update};
+use std::time::Duration;
+
+use bevy::app::{App, FixedUpdate};
 use bevy::asset::AssetServer;
 use bevy::prelude::{
-    in_state, Commands, Component, Entity, IntoSystemConfigs, OnEnter, OnExit, Plugin,
-    Query, Res, ResMut, Resource, Sprite, Time, Timer, TimerMode, Transform, Window,
-    With,
+    in_state, resource_changed, Commands, Component, Entity, IntoSystemConfigs, OnEnter, OnExit,
+    Plugin, Query, Res, ResMut, Resource, Sprite, Time, Timer, TimerMode, Transform, Update, With,
 };
-use bevy::window::PrimaryWindow;
 
-use crate::game::GameState;
-use crate::helpers::RandomHelper;
+use crate::game::config::{on_game_config_loaded_apply, GameConfig};
+use crate::game::difficulty::DifficultyDirector;
+use crate::game::player::PlayerSpawnArea;
+use crate::game::random::{GameRng, RandomSet};
+use crate::game::simulation::{is_simulating, InterpolatedTransform};
+use crate::game::wave::{Wave, WAVE_START};
+use crate::game::Size;
 use crate::{ApplicationState, ScheduleDespawn};
 
 pub struct StarPlugin;
@@ -16,22 +21,41 @@ pub struct StarPlugin;
 impl Plugin for StarPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<StarSpawnTimer>()
-            .add_systems(OnEnter(ApplicationState::InGame), spawn_initial_stars)
+            .add_systems(
+                OnEnter(ApplicationState::InGame),
+                (spawn_initial_stars, reset_star_spawn_timer).in_set(RandomSet::Star),
+            )
+            .add_systems(
+                WAVE_START,
+                (
+                    on_wave_start_spawn_stars,
+                    on_wave_start_reset_star_spawn_timer,
+                )
+                    .in_set(RandomSet::Star),
+            )
             .add_systems(OnExit(ApplicationState::InGame), despawn_all_stars)
             .add_systems(
-                Update,
+                FixedUpdate,
                 (tick_spawn_stars_overtime, spawn_stars_overtime)
+                    .chain()
+                    .in_set(RandomSet::Star)
                     .run_if(in_state(ApplicationState::InGame))
-                    .run_if(in_state(GameState::Running)),
+                    .run_if(is_simulating),
+            )
+            .add_systems(
+                Update,
+                (
+                    on_game_config_changed_update_star_spawn_timer,
+                    on_game_config_changed_update_stars,
+                )
+                    .after(on_game_config_loaded_apply)
+                    .run_if(resource_changed::<GameConfig>),
             );
     }
 }
 
-pub const NUMBER_OF_STARS: usize = 10;
-pub const STAR_SIZE: f32 = 30.0;
-const STAR_SPAWN_TIME: f32 = 1.0;
-
 #[derive(Component)]
+#[require(InterpolatedTransform)]
 pub struct Star {}
 
 #[derive(Resource)]
@@ -41,52 +65,125 @@ pub struct StarSpawnTimer {
 
 impl Default for StarSpawnTimer {
     fn default() -> Self {
+        Self::new(GameConfig::default().wave(1).star_spawn_time)
+    }
+}
+
+impl StarSpawnTimer {
+    pub fn new(spawn_time: f32) -> Self {
         Self {
-            timer: Timer::from_seconds(STAR_SPAWN_TIME, TimerMode::Repeating),
+            timer: Timer::from_seconds(spawn_time, TimerMode::Repeating),
         }
     }
 }
 
 pub fn spawn_initial_stars(
     mut commands: Commands,
-    window_query: Query<&Window, With<PrimaryWindow>>,
+    player_spawn_area: PlayerSpawnArea,
+    config: Res<GameConfig>,
     asset_server: Res<AssetServer>,
+    mut rng: ResMut<GameRng>,
 ) {
-    if let Ok(window) = window_query.get_single() {
-        for _ in 0..NUMBER_OF_STARS {
-            let random_x = RandomHelper::random_f32() * window.width();
-            let random_y = RandomHelper::random_f32() * window.height();
-
-            commands.spawn((
-                Star {},
-                Sprite::from_image(asset_server.load("sprites/star.png")),
-                Transform::from_xyz(random_x, random_y, 0.0),
-            ));
-        }
+    let spawn_area = player_spawn_area.get();
+    for _ in 0..config.wave(1).star_count {
+        commands.spawn((
+            Star {},
+            Size::new(config.star.size),
+            Sprite::from_image(asset_server.load("sprites/star.png")),
+            Transform::from_translation(spawn_area.random_point(&mut rng)),
+        ));
+    }
+}
+
+/// Top the arena up to the stars of the new wave
+pub fn on_wave_start_spawn_stars(
+    mut commands: Commands,
+    player_spawn_area: PlayerSpawnArea,
+    config: Res<GameConfig>,
+    wave: Res<Wave>,
+    asset_server: Res<AssetServer>,
+    star_query: Query<(), With<Star>>,
+    mut rng: ResMut<GameRng>,
+) {
+    let spawn_area = player_spawn_area.get();
+    let wave_config = config.wave(wave.number);
+    for _ in star_query.iter().count()..wave_config.star_count {
+        commands.spawn((
+            Star {},
+            Size::new(config.star.size),
+            Sprite::from_image(asset_server.load("sprites/star.png")),
+            Transform::from_translation(spawn_area.random_point(&mut rng)),
+        ));
     }
 }
 
-pub fn tick_spawn_stars_overtime(time: Res<Time>, mut star_spawn_timer: ResMut<StarSpawnTimer>) {
-    star_spawn_timer.timer.tick(time.delta());
+/// Spawn the stars at the pace of the new wave
+pub fn on_wave_start_reset_star_spawn_timer(
+    mut star_spawn_timer: ResMut<StarSpawnTimer>,
+    config: Res<GameConfig>,
+    wave: Res<Wave>,
+) {
+    *star_spawn_timer = StarSpawnTimer::new(config.wave(wave.number).star_spawn_time);
+}
+
+/// Start every run with a fresh timer, so a run does not depend on the previous one
+pub fn reset_star_spawn_timer(
+    mut star_spawn_timer: ResMut<StarSpawnTimer>,
+    config: Res<GameConfig>,
+) {
+    *star_spawn_timer = StarSpawnTimer::new(config.wave(1).star_spawn_time);
+}
+
+pub fn tick_spawn_stars_overtime(
+    time: Res<Time>,
+    difficulty_director: Res<DifficultyDirector>,
+    mut star_spawn_timer: ResMut<StarSpawnTimer>,
+) {
+    // Running the timer slower spawns less often, without losing its progress as difficulty ramps.
+    star_spawn_timer.timer.tick(
+        time.delta()
+            .div_f32(difficulty_director.star_scarcity_multiplier),
+    );
 }
 
 pub fn spawn_stars_overtime(
     mut commands: Commands,
     star_spawn_timer: Res<StarSpawnTimer>,
-    window_query: Query<&Window, With<PrimaryWindow>>,
+    player_spawn_area: PlayerSpawnArea,
+    config: Res<GameConfig>,
     asset_server: Res<AssetServer>,
+    mut rng: ResMut<GameRng>,
 ) {
     if star_spawn_timer.timer.just_finished() {
-        if let Ok(window) = window_query.get_single() {
-            let random_x = RandomHelper::random_f32() * window.width();
-            let random_y = RandomHelper::random_f32() * window.height();
-
-            commands.spawn((
-                Star {},
-                Sprite::from_image(asset_server.load("sprites/star.png")),
-                Transform::from_xyz(random_x, random_y, 0.0),
-            ));
-        }
+        let spawn_area = player_spawn_area.get();
+        commands.spawn((
+            Star {},
+            Size::new(config.star.size),
+            Sprite::from_image(asset_server.load("sprites/star.png")),
+            Transform::from_translation(spawn_area.random_point(&mut rng)),
+        ));
+    }
+}
+
+/// Apply a reloaded config to the running timer, keeping the spawn progress
+pub fn on_game_config_changed_update_star_spawn_timer(
+    config: Res<GameConfig>,
+    wave: Option<Res<Wave>>,
+    mut star_spawn_timer: ResMut<StarSpawnTimer>,
+) {
+    let wave_number = wave.map_or(1, |wave| wave.number);
+    star_spawn_timer.timer.set_duration(Duration::from_secs_f32(
+        config.wave(wave_number).star_spawn_time,
+    ));
+}
+
+/// Apply a reloaded config to the stars already in the arena
+pub fn on_game_config_changed_update_stars(
+    config: Res<GameConfig>,
+    mut star_query: Query<&mut Size, With<Star>>,
+) {
+    for mut size in star_query.iter_mut() {
+        size.value = config.star.size;
     }
 }
 
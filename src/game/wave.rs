@@ -0,0 +1,152 @@
+use bevy::prelude::{
+    in_state, info, App, Commands, FixedUpdate, IntoSystemConfigs, NextState, OnEnter, OnExit,
+    OnTransition, Plugin, Res, ResMut, Resource, Time, Timer, TimerMode,
+};
+use bevy::time::Stopwatch;
+
+use crate::game::config::{GameConfig, WaveConfig};
+use crate::game::score::Score;
+use crate::game::simulation::{is_simulating, SimulationSet};
+use crate::game::GameState;
+use crate::ApplicationState;
+
+pub struct WavePlugin;
+
+impl Plugin for WavePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<IntermissionTimer>()
+            .add_systems(OnEnter(ApplicationState::InGame), insert_wave_resource)
+            .add_systems(OnExit(ApplicationState::InGame), remove_wave_resource)
+            .add_systems(WAVE_START, start_wave)
+            .add_systems(
+                FixedUpdate,
+                (tick_wave, on_wave_complete_start_intermission)
+                    .chain()
+                    .after(SimulationSet::Collision)
+                    .run_if(in_state(ApplicationState::InGame))
+                    .run_if(is_simulating),
+            )
+            .add_systems(
+                FixedUpdate,
+                tick_intermission
+                    .run_if(in_state(ApplicationState::InGame))
+                    .run_if(in_state(GameState::Intermission)),
+            );
+    }
+}
+
+/// Schedule run when a wave after the first one starts, once the intermission is over.
+pub const WAVE_START: OnTransition<GameState> = OnTransition {
+    exited: GameState::Intermission,
+    entered: GameState::Running,
+};
+
+/// Progress of the current run through the waves of the [`GameConfig`].
+///
+/// During an intermission, `number` is already the one of the coming wave.
+#[derive(Resource, Debug)]
+pub struct Wave {
+    /// Counted from 1
+    pub number: u32,
+    /// Simulated time since the wave started
+    pub elapsed: Stopwatch,
+    /// Score of the run when the wave started
+    pub score_at_start: u32,
+    /// The wave ended and the intermission starts with the next frame, the simulation holds still
+    /// for the ticks left in this one
+    pub is_over: bool,
+}
+
+impl Default for Wave {
+    fn default() -> Self {
+        Self {
+            number: 1,
+            elapsed: Stopwatch::new(),
+            score_at_start: 0,
+            is_over: false,
+        }
+    }
+}
+
+impl Wave {
+    /// Whether the wave reached one of the thresholds of its tuning, with `score` the run score
+    pub fn is_complete(&self, wave_config: &WaveConfig, score: u32) -> bool {
+        let is_duration_reached = wave_config
+            .duration
+            .is_some_and(|duration| self.elapsed.elapsed_secs() >= duration);
+        let is_score_reached = wave_config
+            .score
+            .is_some_and(|target| score.saturating_sub(self.score_at_start) >= target);
+        is_duration_reached || is_score_reached
+    }
+}
+
+#[derive(Resource)]
+pub struct IntermissionTimer {
+    pub timer: Timer,
+}
+
+impl Default for IntermissionTimer {
+    fn default() -> Self {
+        Self::new(GameConfig::default().intermission_time)
+    }
+}
+
+impl IntermissionTimer {
+    pub fn new(intermission_time: f32) -> Self {
+        Self {
+            timer: Timer::from_seconds(intermission_time, TimerMode::Once),
+        }
+    }
+}
+
+pub fn insert_wave_resource(mut commands: Commands) {
+    commands.insert_resource(Wave::default());
+}
+
+pub fn remove_wave_resource(mut commands: Commands) {
+    commands.remove_resource::<Wave>();
+}
+
+pub fn tick_wave(time: Res<Time>, mut wave: ResMut<Wave>) {
+    wave.elapsed.tick(time.delta());
+}
+
+pub fn on_wave_complete_start_intermission(
+    mut wave: ResMut<Wave>,
+    score: Res<Score>,
+    config: Res<GameConfig>,
+    mut intermission_timer: ResMut<IntermissionTimer>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if !wave.is_complete(config.wave(wave.number), score.value) {
+        return;
+    }
+
+    info!("Wave {} complete", wave.number);
+    wave.number += 1;
+    wave.elapsed.reset();
+    wave.score_at_start = score.value;
+    // States only change between frames, the ticks left in this one must not play the next wave.
+    wave.is_over = true;
+    *intermission_timer = IntermissionTimer::new(config.intermission_time);
+    next_state.set(GameState::Intermission);
+    info!("{:?}", GameState::Intermission);
+}
+
+pub fn tick_intermission(
+    time: Res<Time>,
+    mut intermission_timer: ResMut<IntermissionTimer>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    intermission_timer.timer.tick(time.delta());
+    if intermission_timer.timer.just_finished() {
+        next_state.set(GameState::Running);
+        info!("{:?}", GameState::Running);
+    }
+}
+
+pub fn start_wave(mut wave: ResMut<Wave>) {
+    wave.is_over = false;
+    info!("Wave {} started", wave.number);
+}
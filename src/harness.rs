@@ -0,0 +1,485 @@
+use std::marker::PhantomData;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use bevy::app::{App, Last};
+use bevy::asset::{AssetApp, AssetPlugin, AssetServer, Assets, LoadState};
+use bevy::input::gamepad::{
+    GamepadAxis, GamepadButton, GamepadConnection, GamepadConnectionEvent,
+    RawGamepadAxisChangedEvent, RawGamepadButtonChangedEvent, RawGamepadEvent,
+};
+use bevy::input::keyboard::{Key, KeyboardInput, NativeKey};
+use bevy::input::{ButtonState, InputPlugin};
+use bevy::math::Vec3;
+use bevy::prelude::{
+    default, AudioSource, Component, Entity, Event, EventReader, Image, KeyCode, Name, NextState,
+    ResMut, Resource, State, Transform, With, World,
+};
+use bevy::state::app::StatesPlugin;
+use bevy::time::TimeUpdateStrategy;
+use bevy::MinimalPlugins;
+use tempfile::TempDir;
+
+use crate::arena::Arena;
+use crate::game::config::{GameConfig, GameConfigHandle, GAME_CONFIG_PATH};
+use crate::game::enemy::Enemy;
+use crate::game::enemy_behaviour::EnemyKind;
+use crate::game::lives::Lives;
+use crate::game::player::Player;
+use crate::game::power_up::{PowerUp, PowerUpKind};
+use crate::game::random::RunSeed;
+use crate::game::score::Score;
+use crate::game::simulation::{InterpolatedTransform, TickRate};
+use crate::game::star::Star;
+use crate::game::wave::Wave;
+use crate::game::{Confined, GamePlugin, GameState, Size};
+use crate::input_bindings::PointerPosition;
+use crate::settings::{Settings, SteeringMode};
+use crate::storage::StorageDirectory;
+use crate::{ApplicationPlugin, ApplicationState};
+
+/// Seed of every run simulated by the harness, unless told otherwise.
+pub const HARNESS_SEED: u64 = 0;
+
+const GAME_CONFIG_LOAD_ATTEMPTS: usize = 5_000;
+
+/// Headless `GamePlugin` for integration tests, benchmarks and tools.
+///
+/// Runs without a GPU, audio device or window: gameplay only needs the [`Arena`], every
+/// [`SimulationHarness::tick`] advances the simulation by exactly one fixed tick, and input is
+/// scripted through keyboard and gamepad events or the [`PointerPosition`].
+pub struct SimulationHarness {
+    app: App,
+    /// Directory made by [`SimulationHarness::new`], deleted once the app is dropped
+    _storage: Option<TempDir>,
+}
+
+impl Default for SimulationHarness {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SimulationHarness {
+    /// Harness saving to a fresh temporary directory, deleted along with the harness
+    pub fn new() -> Self {
+        let storage = tempfile::Builder::new()
+            .prefix("bevy-ball-harness-")
+            .tempdir()
+            .expect("Could not create the harness storage directory");
+        let mut harness = Self::with_storage(storage.path());
+        harness._storage = Some(storage);
+        harness
+    }
+
+    /// Harness saving high scores and replays to `storage`, which it leaves in place
+    pub fn with_storage(storage: impl Into<PathBuf>) -> Self {
+        let tick = Self::tick_duration();
+
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_plugins(StatesPlugin)
+            // Tests edit the config through `Assets<GameConfig>`, never through the file.
+            .add_plugins(AssetPlugin {
+                watch_for_changes_override: Some(false),
+                ..default()
+            })
+            .add_plugins(InputPlugin)
+            .init_asset::<Image>()
+            .init_asset::<AudioSource>()
+            .insert_resource(TimeUpdateStrategy::ManualDuration(tick))
+            .insert_resource(StorageDirectory::new(storage))
+            .insert_resource(RunSeed::fixed(HARNESS_SEED))
+            .add_plugins(ApplicationPlugin)
+            .add_plugins(GamePlugin);
+
+        // Run the startup systems, no simulation tick happens outside of a run.
+        app.update();
+
+        let mut harness = Self {
+            app,
+            _storage: None,
+        };
+        harness.wait_for_game_config();
+        harness
+    }
+
+    /// Harness in a run with nothing in the arena but the player, e.g. to script it from a known
+    /// arena
+    pub fn empty_run() -> Self {
+        Self::empty_run_with(|_| {})
+    }
+
+    /// [`Self::empty_run`] with the tuning edited by `tune` before the run starts
+    pub fn empty_run_with(tune: impl FnOnce(&mut GameConfig)) -> Self {
+        let mut harness = Self::new();
+        tune(&mut harness.world_mut().resource_mut::<GameConfig>());
+        harness.start_run().clear_enemies().clear_stars();
+        harness
+    }
+
+    /// Time between two frames, one tick at the default tick rate
+    fn tick_duration() -> Duration {
+        Duration::from_secs_f64(1.0 / TickRate::default().hz)
+    }
+
+    /// Update until the tuning file is loaded and applied, as it is before the first run of a game
+    fn wait_for_game_config(&mut self) {
+        let handle = self.world().resource::<GameConfigHandle>().handle.clone();
+        for _ in 0..GAME_CONFIG_LOAD_ATTEMPTS {
+            let world = self.world();
+            let loaded_config = world.resource::<Assets<GameConfig>>().get(&handle);
+            if loaded_config == Some(world.resource::<GameConfig>()) {
+                return;
+            }
+            if let LoadState::Failed(error) = world.resource::<AssetServer>().load_state(&handle) {
+                panic!("Could not load {}: {}", GAME_CONFIG_PATH, error);
+            }
+
+            std::thread::sleep(Duration::from_millis(1));
+            self.app.update();
+        }
+        panic!("Timed out loading {}", GAME_CONFIG_PATH);
+    }
+
+    pub fn app(&mut self) -> &mut App {
+        &mut self.app
+    }
+
+    pub fn world(&self) -> &World {
+        self.app.world()
+    }
+
+    pub fn world_mut(&mut self) -> &mut World {
+        self.app.world_mut()
+    }
+
+    /// Use `seed` for the next runs
+    pub fn seed(&mut self, seed: u64) -> &mut Self {
+        self.world_mut().insert_resource(RunSeed::fixed(seed));
+        self
+    }
+
+    /// Enter `ApplicationState::InGame`, the run starts ticking from the next [`Self::tick`]
+    pub fn start_run(&mut self) -> &mut Self {
+        self.world_mut()
+            .resource_mut::<NextState<ApplicationState>>()
+            .set(ApplicationState::InGame);
+        self.app.update();
+        self
+    }
+
+    /// Advance by one frame, which runs exactly one simulation tick
+    pub fn tick(&mut self) -> &mut Self {
+        self.app.update();
+        self
+    }
+
+    /// Advance by one frame running `ticks` simulation ticks in a row, as on a display slower than
+    /// the tick rate. States only change once they all ran.
+    pub fn long_frame(&mut self, ticks: u32) -> &mut Self {
+        let tick = Self::tick_duration();
+        self.world_mut()
+            .insert_resource(TimeUpdateStrategy::ManualDuration(tick * ticks));
+        self.app.update();
+        self.world_mut()
+            .insert_resource(TimeUpdateStrategy::ManualDuration(tick));
+        self
+    }
+
+    pub fn ticks(&mut self, count: usize) -> &mut Self {
+        for _ in 0..count {
+            self.tick();
+        }
+        self
+    }
+
+    /// Tick until `condition` holds, at most `max_ticks` times. Return whether it ever held.
+    pub fn tick_until(&mut self, max_ticks: usize, condition: impl Fn(&World) -> bool) -> bool {
+        for _ in 0..max_ticks {
+            if condition(self.world()) {
+                return true;
+            }
+            self.tick();
+        }
+        condition(self.world())
+    }
+
+    /// Press `key`, as a keyboard would
+    pub fn press(&mut self, key_code: KeyCode) -> &mut Self {
+        self.send_key(key_code, ButtonState::Pressed)
+    }
+
+    /// Release `key`, as a keyboard would
+    pub fn release(&mut self, key_code: KeyCode) -> &mut Self {
+        self.send_key(key_code, ButtonState::Released)
+    }
+
+    fn send_key(&mut self, key_code: KeyCode, state: ButtonState) -> &mut Self {
+        // Gameplay does not care which window has the focus.
+        self.world_mut().send_event(KeyboardInput {
+            key_code,
+            logical_key: Key::Unidentified(NativeKey::Unidentified),
+            state,
+            repeat: false,
+            window: Entity::PLACEHOLDER,
+        });
+        self
+    }
+
+    /// Plug in a gamepad, which takes one frame to be recognized
+    pub fn connect_gamepad(&mut self) -> Entity {
+        let gamepad = self.world_mut().spawn_empty().id();
+        self.world_mut().send_event(GamepadConnectionEvent::new(
+            gamepad,
+            GamepadConnection::Connected {
+                name: "Harness Gamepad".to_string(),
+                vendor_id: None,
+                product_id: None,
+            },
+        ));
+        self.tick();
+        gamepad
+    }
+
+    /// Hold the left stick of `gamepad` at `x`, `y`, both in `-1.0..=1.0`
+    pub fn move_left_stick(&mut self, gamepad: Entity, x: f32, y: f32) -> &mut Self {
+        for (axis, value) in [(GamepadAxis::LeftStickX, x), (GamepadAxis::LeftStickY, y)] {
+            self.world_mut()
+                .send_event(RawGamepadEvent::Axis(RawGamepadAxisChangedEvent::new(
+                    gamepad, axis, value,
+                )));
+        }
+        self
+    }
+
+    /// Press `button` of `gamepad` all the way
+    pub fn press_button(&mut self, gamepad: Entity, button: GamepadButton) -> &mut Self {
+        self.send_button(gamepad, button, 1.0)
+    }
+
+    pub fn release_button(&mut self, gamepad: Entity, button: GamepadButton) -> &mut Self {
+        self.send_button(gamepad, button, 0.0)
+    }
+
+    fn send_button(&mut self, gamepad: Entity, button: GamepadButton, value: f32) -> &mut Self {
+        self.world_mut()
+            .send_event(RawGamepadEvent::Button(RawGamepadButtonChangedEvent::new(
+                gamepad, button, value,
+            )));
+        self
+    }
+
+    pub fn set_steering_mode(&mut self, steering_mode: SteeringMode) -> &mut Self {
+        self.world_mut().resource_mut::<Settings>().steering_mode = steering_mode;
+        self
+    }
+
+    /// Put the mouse cursor or a finger on `translation` of the arena
+    pub fn point_at(&mut self, translation: Vec3) -> &mut Self {
+        self.world_mut().resource_mut::<PointerPosition>().position = Some(translation.truncate());
+        self
+    }
+
+    /// Take the mouse cursor out of the window, or the finger off the screen
+    pub fn lift_pointer(&mut self) -> &mut Self {
+        self.world_mut().resource_mut::<PointerPosition>().position = None;
+        self
+    }
+
+    /// Count every `E` sent from now on, see [`Self::event_count`]
+    pub fn count_events<E: Event>(&mut self) -> &mut Self {
+        self.app
+            .insert_resource(EventCount::<E>::default())
+            .add_systems(Last, count_events::<E>);
+        self
+    }
+
+    pub fn event_count<E: Event>(&self) -> usize {
+        self.world()
+            .get_resource::<EventCount<E>>()
+            .map_or(0, |event_count| event_count.count)
+    }
+
+    pub fn application_state(&self) -> ApplicationState {
+        self.world()
+            .resource::<State<ApplicationState>>()
+            .get()
+            .clone()
+    }
+
+    pub fn game_state(&self) -> GameState {
+        self.world().resource::<State<GameState>>().get().clone()
+    }
+
+    pub fn score(&self) -> Option<u32> {
+        self.world()
+            .get_resource::<Score>()
+            .map(|score| score.value)
+    }
+
+    pub fn lives(&self) -> Option<u32> {
+        self.world()
+            .get_resource::<Lives>()
+            .map(|lives| lives.remaining)
+    }
+
+    /// Leave the run with `remaining` lives, e.g. `1` for the next hit to end it
+    pub fn set_lives(&mut self, remaining: u32) -> &mut Self {
+        self.world_mut().resource_mut::<Lives>().remaining = remaining;
+        self
+    }
+
+    /// Number of the current wave, or of the coming one during an intermission
+    pub fn wave(&self) -> Option<u32> {
+        self.world().get_resource::<Wave>().map(|wave| wave.number)
+    }
+
+    pub fn config(&self) -> &GameConfig {
+        self.world().resource::<GameConfig>()
+    }
+
+    pub fn arena(&self) -> Arena {
+        *self.world().resource::<Arena>()
+    }
+
+    pub fn player(&mut self) -> Option<Entity> {
+        self.world_mut()
+            .query_filtered::<Entity, With<Player>>()
+            .iter(self.app.world())
+            .next()
+    }
+
+    /// Simulated translation of `entity`, as opposed to its interpolated `Transform`
+    pub fn translation(&self, entity: Entity) -> Option<Vec3> {
+        let entity = self.world().get_entity(entity).ok()?;
+        match entity.get::<InterpolatedTransform>() {
+            Some(interpolated_transform) => Some(interpolated_transform.current),
+            None => entity
+                .get::<Transform>()
+                .map(|transform| transform.translation),
+        }
+    }
+
+    /// Move `entity` to `translation` without interpolating the jump
+    pub fn teleport(&mut self, entity: Entity, translation: Vec3) -> &mut Self {
+        let mut entity = self.world_mut().entity_mut(entity);
+        if let Some(mut transform) = entity.get_mut::<Transform>() {
+            transform.translation = translation;
+        }
+        if let Some(mut interpolated_transform) = entity.get_mut::<InterpolatedTransform>() {
+            interpolated_transform.previous = translation;
+            interpolated_transform.current = translation;
+        }
+        self
+    }
+
+    pub fn enemies(&mut self) -> Vec<Entity> {
+        self.entities_with::<Enemy>()
+    }
+
+    pub fn stars(&mut self) -> Vec<Entity> {
+        self.entities_with::<Star>()
+    }
+
+    pub fn power_ups(&mut self) -> Vec<Entity> {
+        self.entities_with::<PowerUp>()
+    }
+
+    fn entities_with<C: Component>(&mut self) -> Vec<Entity> {
+        self.world_mut()
+            .query_filtered::<Entity, With<C>>()
+            .iter(self.app.world())
+            .collect()
+    }
+
+    /// Despawn every enemy, e.g. to script a run from a known arena
+    pub fn clear_enemies(&mut self) -> &mut Self {
+        for enemy in self.enemies() {
+            self.world_mut().despawn(enemy);
+        }
+        self
+    }
+
+    /// Despawn every star, e.g. to script a run from a known arena
+    pub fn clear_stars(&mut self) -> &mut Self {
+        for star in self.stars() {
+            self.world_mut().despawn(star);
+        }
+        self
+    }
+
+    pub fn spawn_enemy(&mut self, translation: Vec3, direction: Vec3) -> Entity {
+        self.spawn_enemy_of_kind(EnemyKind::Bouncer, translation, direction)
+    }
+
+    pub fn spawn_enemy_of_kind(
+        &mut self,
+        kind: EnemyKind,
+        translation: Vec3,
+        direction: Vec3,
+    ) -> Entity {
+        let size = Size::new(self.config().enemy.size);
+        let enemy = self
+            .world_mut()
+            .spawn((
+                Name::new("Enemy"),
+                Enemy::new(direction),
+                kind,
+                Confined {},
+                size,
+                Transform::from_translation(translation),
+            ))
+            .id();
+        // Give the enemy the components of its kind right away.
+        self.world_mut().flush();
+        enemy
+    }
+
+    pub fn spawn_star(&mut self, translation: Vec3) -> Entity {
+        let size = Size::new(self.config().star.size);
+        self.world_mut()
+            .spawn((
+                Name::new("Star"),
+                Star {},
+                size,
+                Transform::from_translation(translation),
+            ))
+            .id()
+    }
+
+    pub fn spawn_power_up(&mut self, kind: PowerUpKind, translation: Vec3) -> Entity {
+        let size = Size::new(self.config().power_up.size);
+        self.world_mut()
+            .spawn((
+                Name::new("Power-Up"),
+                PowerUp { kind },
+                size,
+                Transform::from_translation(translation),
+            ))
+            .id()
+    }
+}
+
+/// Number of `E` sent since [`SimulationHarness::count_events`].
+#[derive(Resource)]
+pub struct EventCount<E: Event> {
+    pub count: usize,
+    event: PhantomData<E>,
+}
+
+impl<E: Event> Default for EventCount<E> {
+    fn default() -> Self {
+        Self {
+            count: 0,
+            event: PhantomData,
+        }
+    }
+}
+
+fn count_events<E: Event>(
+    mut event_reader: EventReader<E>,
+    mut event_count: ResMut<EventCount<E>>,
+) {
+    event_count.count += event_reader.read().count();
+}
@@ -1,38 +1,32 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
 use bevy::asset::AssetServer;
 use bevy::audio::AudioPlayer;
-use bevy::input::ButtonInput;
-use bevy::math::Vec3;
-use bevy::prelude::{AudioSource, KeyCode, Res, Transform, Window};
-use rand::random;
+use bevy::math::{Vec2, Vec3};
+use bevy::prelude::{AudioSource, Res};
 
-pub struct WindowHelper {}
-
-impl WindowHelper {
-    /// Given a &Window, return its center of the screen as Transform
-    pub fn center(window: &Window) -> Transform {
-        let middle_x = window.width() / 2.0;
-        let middle_y = window.height() / 2.0;
-        Transform::from_xyz(middle_x, middle_y, 0.0)
-    }
-}
+use crate::arena::Arena;
+use crate::game::config::{GamepadConfig, PointerConfig};
+use crate::game::random::GameRng;
+use crate::input_bindings::{ActionInput, InputAction};
 
 pub struct MovementHelper {}
 
 impl MovementHelper {
-    /// Given a keyboard_input, return the final normalized direction as Vec3
-    pub fn handle_input(keyboard_input: Res<ButtonInput<KeyCode>>) -> Vec3 {
+    /// Given the bound keys and gamepad buttons, return the final normalized direction as Vec3
+    pub fn handle_input(action_input: &ActionInput) -> Vec3 {
         let mut direction = Vec3::ZERO;
 
-        if keyboard_input.pressed(KeyCode::ArrowLeft) || keyboard_input.pressed(KeyCode::KeyA) {
+        if action_input.pressed(InputAction::MoveLeft) {
             direction += Vec3::new(-1.0, 0.0, 0.0)
         }
-        if keyboard_input.pressed(KeyCode::ArrowRight) || keyboard_input.pressed(KeyCode::KeyD) {
+        if action_input.pressed(InputAction::MoveRight) {
             direction += Vec3::new(1.0, 0.0, 0.0)
         }
-        if keyboard_input.pressed(KeyCode::ArrowUp) || keyboard_input.pressed(KeyCode::KeyW) {
+        if action_input.pressed(InputAction::MoveUp) {
             direction += Vec3::new(0.0, 1.0, 0.0)
         }
-        if keyboard_input.pressed(KeyCode::ArrowDown) || keyboard_input.pressed(KeyCode::KeyS) {
+        if action_input.pressed(InputAction::MoveDown) {
             direction += Vec3::new(0.0, -1.0, 0.0)
         }
 
@@ -43,12 +37,41 @@ impl MovementHelper {
         direction
     }
 
-    pub fn confine(window: &Window, unit_translation: Vec3, unit_size: f32) -> Vec3 {
+    /// Given a stick position, return a direction growing from 0 at the deadzone to 1 at full tilt
+    pub fn handle_stick(stick: Vec2, config: &GamepadConfig) -> Vec3 {
+        let tilt = stick.length().min(1.0);
+        if tilt <= config.stick_deadzone {
+            return Vec3::ZERO;
+        }
+
+        let speed = ((tilt - config.stick_deadzone) / (1.0 - config.stick_deadzone))
+            .powf(config.stick_response_exponent);
+        (stick.normalize() * speed).extend(0.0)
+    }
+
+    /// Given the player and pointer translations, return a direction slowing down on approach
+    pub fn steer_towards(
+        translation: Vec3,
+        target: Vec3,
+        speed: f32,
+        config: &PointerConfig,
+    ) -> Vec3 {
+        let offset = (target - translation).truncate();
+        if offset.length() <= config.dead_radius || speed <= 0.0 {
+            return Vec3::ZERO;
+        }
+
+        (offset / (config.smoothing_time * speed))
+            .clamp_length_max(1.0)
+            .extend(0.0)
+    }
+
+    pub fn confine(arena: &Arena, unit_translation: Vec3, unit_size: f32) -> Vec3 {
         let half_unit_size = unit_size / 2.0;
         let x_min = 0.0 + half_unit_size;
-        let x_max = window.width() - half_unit_size;
+        let x_max = arena.width - half_unit_size;
         let y_min = 0.0 + half_unit_size;
-        let y_max = window.height() - half_unit_size;
+        let y_max = arena.height - half_unit_size;
 
         let mut new_translation = unit_translation;
 
@@ -81,13 +104,56 @@ impl MovementHelper {
 
         distance_between_them <= (first_length + second_length)
     }
-}
 
-pub struct RandomHelper {}
+    /// Given two overlapping units, return their translations pushed apart until they touch.
+    /// The bigger unit is pushed less, as the mass of a unit grows with the square of its size.
+    pub fn separate(
+        first_size: f32,
+        first_transform: Vec3,
+        second_size: f32,
+        second_transform: Vec3,
+    ) -> (Vec3, Vec3) {
+        let overlap = (first_size + second_size) / 2.0 - first_transform.distance(second_transform);
+        if overlap <= 0.0 {
+            return (first_transform, second_transform);
+        }
+
+        // Units on top of each other are pushed apart horizontally.
+        let normal = (second_transform - first_transform).normalize_or(Vec3::X);
+        let (first_mass, second_mass) = (first_size * first_size, second_size * second_size);
+        let total_mass = first_mass + second_mass;
+
+        (
+            first_transform - normal * overlap * second_mass / total_mass,
+            second_transform + normal * overlap * first_mass / total_mass,
+        )
+    }
+
+    /// Given two colliding units, return their velocities after an elastic collision, or `None`
+    /// when they already move apart. The mass of a unit grows with the square of its size.
+    ///
+    /// Momentum and kinetic energy are kept: a fast unit hands speed to a slow one.
+    pub fn elastic_bounce(
+        first_size: f32,
+        first_transform: Vec3,
+        first_velocity: Vec3,
+        second_size: f32,
+        second_transform: Vec3,
+        second_velocity: Vec3,
+    ) -> Option<(Vec3, Vec3)> {
+        let normal = (second_transform - first_transform).normalize_or(Vec3::X);
+        let approach_speed = (first_velocity - second_velocity).dot(normal);
+        if approach_speed <= 0.0 {
+            return None;
+        }
+
+        let (first_mass, second_mass) = (first_size * first_size, second_size * second_size);
+        let impulse = 2.0 * approach_speed / (first_mass + second_mass);
 
-impl RandomHelper {
-    pub fn random_f32() -> f32 {
-        random::<f32>()
+        Some((
+            first_velocity - normal * impulse * second_mass,
+            second_velocity + normal * impulse * first_mass,
+        ))
     }
 }
 
@@ -98,8 +164,8 @@ impl AudioHelper {
         AudioPlayer::<AudioSource>(asset_server.load(file_name))
     }
 
-    pub fn play_bounce_sound(asset_server: &Res<AssetServer>) -> AudioPlayer {
-        Self::play_once(&asset_server, AudioHelper::bounce_sound())
+    pub fn play_bounce_sound(asset_server: &Res<AssetServer>, rng: &mut GameRng) -> AudioPlayer {
+        Self::play_once(asset_server, AudioHelper::bounce_sound(rng))
     }
 
     pub fn play_obtain_star_sound(asset_server: &Res<AssetServer>) -> AudioPlayer {
@@ -110,8 +176,8 @@ impl AudioHelper {
         Self::play_once(&asset_server, AudioHelper::game_over_sound())
     }
 
-    fn bounce_sound() -> String {
-        if RandomHelper::random_f32() < 0.5 {
+    fn bounce_sound(rng: &mut GameRng) -> String {
+        if rng.random_f32() < 0.5 {
             "audio/pluck_001.ogg".to_string()
         } else {
             "audio/pluck_002.ogg".to_string()
@@ -126,3 +192,36 @@ impl AudioHelper {
         "audio/explosionCrunch_000.ogg".to_string()
     }
 }
+
+pub struct TimeHelper {}
+
+impl TimeHelper {
+    /// Return the current wall-clock time as seconds since the Unix epoch
+    pub fn now_unix_seconds() -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+            .unwrap_or(0)
+    }
+
+    /// Given seconds since the Unix epoch, return the UTC date as `YYYY-MM-DD`
+    pub fn format_date(unix_seconds: u64) -> String {
+        // Days to civil date, from http://howardhinnant.github.io/date_algorithms.html
+        let days = (unix_seconds / 86_400) as i64 + 719_468;
+        let era = days.div_euclid(146_097);
+        let day_of_era = days.rem_euclid(146_097);
+        let year_of_era =
+            (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let shifted_month = (5 * day_of_year + 2) / 153;
+        let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+        let month = if shifted_month < 10 {
+            shifted_month + 3
+        } else {
+            shifted_month - 9
+        };
+        let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+
+        format!("{:04}-{:02}-{:02}", year, month, day)
+    }
+}
@@ -0,0 +1,353 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use bevy::ecs::system::SystemParam;
+use bevy::input::gamepad::{Gamepad, GamepadButton};
+use bevy::input::touch::Touches;
+use bevy::input::{ButtonInput, InputSystem};
+use bevy::math::Vec2;
+use bevy::prelude::{
+    info, warn, App, Camera, GlobalTransform, IntoSystemConfigs, KeyCode, Plugin, PreUpdate, Query,
+    Res, ResMut, Resource, Startup, Window, With,
+};
+use bevy::window::PrimaryWindow;
+use serde::{Deserialize, Serialize};
+
+use crate::arena::ArenaCamera;
+use crate::storage::{StorageDirectory, StorageError, StorageHelper};
+
+pub struct InputBindingsPlugin;
+
+impl Plugin for InputBindingsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<StorageDirectory>()
+            .init_resource::<InputBindings>()
+            .init_resource::<PointerPosition>()
+            .add_systems(Startup, load_input_bindings)
+            .add_systems(PreUpdate, track_pointer.after(InputSystem));
+    }
+}
+
+pub const INPUT_BINDINGS_FILE_NAME: &str = "input_bindings.ron";
+pub const INPUT_BINDINGS_SAVE_VERSION: u32 = 2;
+
+/// What the player can ask for, whatever key it is bound to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum InputAction {
+    MoveUp,
+    MoveDown,
+    MoveLeft,
+    MoveRight,
+    /// Pause or resume the run
+    Pause,
+    /// Press the focused menu button
+    Confirm,
+    /// Leave the current menu, pausing the run or closing the game from the main menu
+    #[serde(alias = "Quit")]
+    Back,
+    /// Start a run from the main menu or the game over screen
+    StartGame,
+    /// Go back to the main menu
+    MainMenu,
+    /// Show or hide the difficulty overlay during a run
+    ToggleDifficultyOverlay,
+}
+
+impl InputAction {
+    pub const ALL: [InputAction; 10] = [
+        InputAction::MoveUp,
+        InputAction::MoveDown,
+        InputAction::MoveLeft,
+        InputAction::MoveRight,
+        InputAction::Pause,
+        InputAction::Confirm,
+        InputAction::Back,
+        InputAction::StartGame,
+        InputAction::MainMenu,
+        InputAction::ToggleDifficultyOverlay,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            InputAction::MoveUp => "Move Up",
+            InputAction::MoveDown => "Move Down",
+            InputAction::MoveLeft => "Move Left",
+            InputAction::MoveRight => "Move Right",
+            InputAction::Pause => "Pause",
+            InputAction::Confirm => "Confirm",
+            InputAction::Back => "Back",
+            InputAction::StartGame => "Start Game",
+            InputAction::MainMenu => "Main Menu",
+            InputAction::ToggleDifficultyOverlay => "Difficulty",
+        }
+    }
+
+    /// Keys the action is bound to until the player rebinds it
+    pub fn default_keys(&self) -> Vec<KeyCode> {
+        match self {
+            InputAction::MoveUp => vec![KeyCode::ArrowUp, KeyCode::KeyW],
+            InputAction::MoveDown => vec![KeyCode::ArrowDown, KeyCode::KeyS],
+            InputAction::MoveLeft => vec![KeyCode::ArrowLeft, KeyCode::KeyA],
+            InputAction::MoveRight => vec![KeyCode::ArrowRight, KeyCode::KeyD],
+            InputAction::Pause => vec![KeyCode::Space],
+            InputAction::Confirm => vec![KeyCode::Enter, KeyCode::NumpadEnter],
+            InputAction::Back => vec![KeyCode::Escape],
+            InputAction::StartGame => vec![KeyCode::KeyG],
+            InputAction::MainMenu => vec![KeyCode::KeyM],
+            InputAction::ToggleDifficultyOverlay => vec![KeyCode::F3],
+        }
+    }
+
+    /// Gamepad buttons the action is bound to, the left stick moving the player as well
+    pub fn default_buttons(&self) -> Vec<GamepadButton> {
+        match self {
+            InputAction::MoveUp => vec![GamepadButton::DPadUp],
+            InputAction::MoveDown => vec![GamepadButton::DPadDown],
+            InputAction::MoveLeft => vec![GamepadButton::DPadLeft],
+            InputAction::MoveRight => vec![GamepadButton::DPadRight],
+            InputAction::Pause => vec![GamepadButton::Start],
+            InputAction::Confirm => vec![GamepadButton::South],
+            InputAction::Back => vec![GamepadButton::East],
+            InputAction::StartGame => vec![GamepadButton::Start],
+            InputAction::MainMenu => vec![GamepadButton::Select],
+            InputAction::ToggleDifficultyOverlay => vec![],
+        }
+    }
+}
+
+/// Keys and gamepad buttons of every [`InputAction`], a key being bound to one action at most.
+#[derive(Resource, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InputBindings {
+    bindings: BTreeMap<InputAction, Vec<KeyCode>>,
+    /// Saves from before gamepads were supported have none, they get the defaults.
+    #[serde(default = "InputBindings::default_gamepad_bindings")]
+    gamepad_bindings: BTreeMap<InputAction, Vec<GamepadButton>>,
+}
+
+impl Default for InputBindings {
+    fn default() -> Self {
+        Self {
+            bindings: InputAction::ALL
+                .iter()
+                .map(|action| (*action, action.default_keys()))
+                .collect(),
+            gamepad_bindings: Self::default_gamepad_bindings(),
+        }
+    }
+}
+
+impl InputBindings {
+    fn default_gamepad_bindings() -> BTreeMap<InputAction, Vec<GamepadButton>> {
+        InputAction::ALL
+            .iter()
+            .map(|action| (*action, action.default_buttons()))
+            .collect()
+    }
+
+    pub fn keys(&self, action: InputAction) -> &[KeyCode] {
+        self.bindings
+            .get(&action)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Action `key` is bound to, if any
+    pub fn action_of(&self, key: KeyCode) -> Option<InputAction> {
+        self.bindings
+            .iter()
+            .find(|(_, keys)| keys.contains(&key))
+            .map(|(action, _)| *action)
+    }
+
+    pub fn buttons(&self, action: InputAction) -> &[GamepadButton] {
+        self.gamepad_bindings
+            .get(&action)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    pub fn pressed(&self, action: InputAction, keyboard_input: &ButtonInput<KeyCode>) -> bool {
+        keyboard_input.any_pressed(self.keys(action).iter().copied())
+    }
+
+    pub fn just_pressed(&self, action: InputAction, keyboard_input: &ButtonInput<KeyCode>) -> bool {
+        keyboard_input.any_just_pressed(self.keys(action).iter().copied())
+    }
+
+    pub fn gamepad_pressed(&self, action: InputAction, gamepad: &Gamepad) -> bool {
+        gamepad.any_pressed(self.buttons(action).iter().copied())
+    }
+
+    pub fn gamepad_just_pressed(&self, action: InputAction, gamepad: &Gamepad) -> bool {
+        gamepad.any_just_pressed(self.buttons(action).iter().copied())
+    }
+
+    /// Bind `key` to `action` alone, in place of every key it had
+    pub fn rebind(&mut self, action: InputAction, key: KeyCode) {
+        for keys in self.bindings.values_mut() {
+            keys.retain(|bound_key| *bound_key != key);
+        }
+        self.bindings.insert(action, vec![key]);
+    }
+
+    /// Add `key` to the keys of `action`, taking it away from the action it was bound to
+    pub fn bind(&mut self, action: InputAction, key: KeyCode) {
+        for keys in self.bindings.values_mut() {
+            keys.retain(|bound_key| *bound_key != key);
+        }
+        self.bindings.entry(action).or_default().push(key);
+    }
+
+    /// Load the bindings saved at `path`, or `None` when nothing was saved yet
+    pub fn load_from(path: &Path) -> Result<Option<InputBindings>, StorageError> {
+        let Some(content) = StorageHelper::read(path)? else {
+            return Ok(None);
+        };
+
+        let mut input_bindings = match StorageHelper::read_version(&content)? {
+            // Gamepad buttons could not be rebound yet, and the menu navigation actions were saved
+            // without any: start over from the default buttons.
+            1 => InputBindings {
+                gamepad_bindings: Self::default_gamepad_bindings(),
+                ..StorageHelper::parse::<InputBindings>(&content)?
+            },
+            INPUT_BINDINGS_SAVE_VERSION => StorageHelper::parse::<InputBindings>(&content)?,
+            version => {
+                return Err(StorageError::UnsupportedVersion {
+                    found: version,
+                    supported: INPUT_BINDINGS_SAVE_VERSION,
+                })
+            }
+        };
+
+        // Actions added since the file was saved start with their default keys, unless taken, and
+        // their default buttons.
+        for action in InputAction::ALL {
+            if !input_bindings.bindings.contains_key(&action) {
+                for key in action.default_keys() {
+                    if input_bindings.action_of(key).is_none() {
+                        input_bindings.bind(action, key);
+                    }
+                }
+            }
+            input_bindings
+                .gamepad_bindings
+                .entry(action)
+                .or_insert_with(|| action.default_buttons());
+        }
+        Ok(Some(input_bindings))
+    }
+
+    pub fn save_to(&self, path: &Path) -> Result<(), StorageError> {
+        StorageHelper::save(path, INPUT_BINDINGS_SAVE_VERSION, self)
+    }
+}
+
+/// The keyboard and every connected gamepad, read as [`InputAction`]s.
+#[derive(SystemParam)]
+pub struct ActionInput<'w, 's> {
+    keyboard_input: Res<'w, ButtonInput<KeyCode>>,
+    gamepads: Query<'w, 's, &'static Gamepad>,
+    input_bindings: Res<'w, InputBindings>,
+}
+
+impl ActionInput<'_, '_> {
+    pub fn pressed(&self, action: InputAction) -> bool {
+        self.input_bindings.pressed(action, &self.keyboard_input)
+            || self
+                .gamepads
+                .iter()
+                .any(|gamepad| self.input_bindings.gamepad_pressed(action, gamepad))
+    }
+
+    pub fn just_pressed(&self, action: InputAction) -> bool {
+        self.input_bindings
+            .just_pressed(action, &self.keyboard_input)
+            || self
+                .gamepads
+                .iter()
+                .any(|gamepad| self.input_bindings.gamepad_just_pressed(action, gamepad))
+    }
+
+    /// Left stick of the first gamepad pushed away from its center, if any
+    pub fn left_stick(&self) -> Vec2 {
+        self.gamepads
+            .iter()
+            .map(Gamepad::left_stick)
+            .find(|stick| *stick != Vec2::ZERO)
+            .unwrap_or(Vec2::ZERO)
+    }
+}
+
+/// Where the mouse cursor or the first touch is, in arena units, `None` when there is neither.
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq)]
+pub struct PointerPosition {
+    pub position: Option<Vec2>,
+}
+
+/// Follow the touch point, or the cursor when the screen is not touched
+pub fn track_pointer(
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<ArenaCamera>>,
+    touches: Res<Touches>,
+    mut pointer_position: ResMut<PointerPosition>,
+) {
+    // Without a window, e.g. in the harness, whoever set the position keeps it.
+    let Ok(window) = window_query.get_single() else {
+        return;
+    };
+    let Ok((camera, camera_transform)) = camera_query.get_single() else {
+        return;
+    };
+
+    let Some(viewport) = camera.logical_viewport_rect() else {
+        return;
+    };
+
+    let window_position = touches
+        .iter()
+        .next()
+        .map(|touch| touch.position())
+        .or_else(|| window.cursor_position());
+    // Pointers over the letterbox borders are not over the arena.
+    let position = window_position
+        .filter(|window_position| viewport.contains(*window_position))
+        .and_then(|window_position| {
+            camera
+                .viewport_to_world_2d(camera_transform, window_position - viewport.min)
+                .ok()
+        });
+    if pointer_position.position != position {
+        pointer_position.position = position;
+    }
+}
+
+pub fn load_input_bindings(
+    mut input_bindings: ResMut<InputBindings>,
+    storage: Res<StorageDirectory>,
+) {
+    let path = storage.file(INPUT_BINDINGS_FILE_NAME);
+    match InputBindings::load_from(&path) {
+        Ok(Some(loaded)) => {
+            info!("Input bindings loaded from {}", path.display());
+            *input_bindings = loaded;
+        }
+        Ok(None) => info!("No input bindings saved yet at {}", path.display()),
+        Err(error) => {
+            warn!(
+                "Could not load input bindings from {}: {}",
+                path.display(),
+                error
+            );
+            // Keep the unreadable file around instead of overwriting it on the next save.
+            if path.exists() {
+                match StorageHelper::quarantine(&path) {
+                    Ok(moved_to) => {
+                        warn!("Moved unreadable input bindings to {}", moved_to.display())
+                    }
+                    Err(error) => warn!("Could not move unreadable input bindings: {}", error),
+                }
+            }
+        }
+    }
+}
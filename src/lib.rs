@@ -1,19 +1,28 @@
-use bevy::app::{App, AppExit, Plugin, PostUpdate, Startup, Update};
-use bevy::input::ButtonInput;
+use bevy::app::{App, Plugin, PostUpdate, Startup, Update};
 use bevy::prelude::{
-    default, info, AppExtStates, Camera2dBundle, Commands, Component, Entity, EventReader,
-    EventWriter, KeyCode, NextState, Query, Res, ResMut, State, States, Window, With,
+    default, info, AppExtStates, Camera, Camera2d, ClearColorConfig, Color, Commands, Component,
+    Entity, EventReader, IntoSystemConfigs, NextState, Query, Res, ResMut, State, States,
+    Transform, With,
 };
-use bevy::window::PrimaryWindow;
+use bevy::render::view::RenderLayers;
 
+use game::high_score::{on_collided_with_enemy_record_last_run, LastRun};
 use game::player::CollidedWithEnemy;
 
+use crate::arena::{Arena, ArenaCamera, ArenaPlugin, LetterboxCamera, LETTERBOX_RENDER_LAYER};
 use crate::asset_handler::AssetHandlerPlugin;
-use crate::helpers::WindowHelper;
+use crate::input_bindings::{ActionInput, InputAction, InputBindingsPlugin};
+use crate::settings::SettingsPlugin;
 
+pub mod arena;
 mod asset_handler;
 pub mod game;
+#[cfg(any(test, feature = "harness"))]
+pub mod harness;
 pub mod helpers;
+pub mod input_bindings;
+pub mod settings;
+pub mod storage;
 pub mod ui;
 
 pub struct ApplicationPlugin;
@@ -21,12 +30,17 @@ pub struct ApplicationPlugin;
 impl Plugin for ApplicationPlugin {
     fn build(&self, app: &mut App) {
         app.add_plugins(AssetHandlerPlugin)
+            .add_plugins(ArenaPlugin)
             .init_state::<ApplicationState>()
             .add_systems(Startup, spawn_camera)
-            .add_systems(Update, exit_on_escape)
+            .add_plugins(InputBindingsPlugin)
+            .add_plugins(SettingsPlugin)
             .add_systems(Update, transition_in_game_state)
             .add_systems(Update, transition_main_menu_state)
-            .add_systems(Update, transition_game_over)
+            .add_systems(
+                Update,
+                transition_game_over.after(on_collided_with_enemy_record_last_run),
+            )
             .add_systems(PostUpdate, cleanup_entity);
     }
 }
@@ -36,7 +50,13 @@ pub enum ApplicationState {
     #[default]
     MainMenu,
     InGame,
+    NameEntry,
     GameOver,
+    Leaderboard,
+    /// Screen listing the key of every action, to rebind them
+    Controls,
+    /// Settings screen opened from the main menu, the pause menu opens it as `GameState::Settings`
+    Settings,
 }
 
 #[derive(Component)]
@@ -48,22 +68,23 @@ impl Default for ScheduleDespawn {
     }
 }
 
-pub fn spawn_camera(mut commands: Commands, window_query: Query<&Window, With<PrimaryWindow>>) {
-    if let Ok(window) = window_query.get_single() {
-        commands.spawn(Camera2dBundle {
-            transform: WindowHelper::center(window),
+pub fn spawn_camera(mut commands: Commands, arena: Res<Arena>) {
+    commands.spawn((
+        Camera2d,
+        ArenaCamera {},
+        arena.projection(),
+        Transform::from_translation(arena.center()),
+    ));
+    commands.spawn((
+        Camera2d,
+        Camera {
+            order: -1,
+            clear_color: ClearColorConfig::Custom(Color::BLACK),
             ..default()
-        });
-    }
-}
-
-pub fn exit_on_escape(
-    keyboard_input: Res<ButtonInput<KeyCode>>,
-    mut event_writter: EventWriter<AppExit>,
-) {
-    if keyboard_input.pressed(KeyCode::Escape) {
-        event_writter.send(AppExit::Success);
-    }
+        },
+        RenderLayers::layer(LETTERBOX_RENDER_LAYER),
+        LetterboxCamera {},
+    ));
 }
 
 pub fn cleanup_entity(mut commands: Commands, query: Query<Entity, With<ScheduleDespawn>>) {
@@ -75,10 +96,10 @@ pub fn cleanup_entity(mut commands: Commands, query: Query<Entity, With<Schedule
 
 pub fn transition_in_game_state(
     mut next_state: ResMut<NextState<ApplicationState>>,
-    button_input: Res<ButtonInput<KeyCode>>,
+    action_input: ActionInput,
     current_state: Res<State<ApplicationState>>,
 ) {
-    if button_input.just_pressed(KeyCode::KeyG) {
+    if action_input.just_pressed(InputAction::StartGame) {
         match current_state.get() {
             ApplicationState::MainMenu | ApplicationState::GameOver => {
                 next_state.set(ApplicationState::InGame);
@@ -91,12 +112,14 @@ pub fn transition_in_game_state(
 
 pub fn transition_main_menu_state(
     mut next_state: ResMut<NextState<ApplicationState>>,
-    button_input: Res<ButtonInput<KeyCode>>,
+    action_input: ActionInput,
     current_state: Res<State<ApplicationState>>,
 ) {
-    if button_input.just_pressed(KeyCode::KeyM) {
+    if action_input.just_pressed(InputAction::MainMenu) {
         match current_state.get() {
-            ApplicationState::InGame | ApplicationState::GameOver => {
+            ApplicationState::InGame
+            | ApplicationState::GameOver
+            | ApplicationState::Leaderboard => {
                 next_state.set(ApplicationState::MainMenu);
                 info!("{:?}", ApplicationState::MainMenu);
             }
@@ -108,9 +131,19 @@ pub fn transition_main_menu_state(
 pub fn transition_game_over(
     mut next_state: ResMut<NextState<ApplicationState>>,
     mut event_reader: EventReader<CollidedWithEnemy>,
+    last_run: Option<Res<LastRun>>,
 ) {
     for _event in event_reader.read() {
-        next_state.set(ApplicationState::GameOver);
-        info!("{:?}", ApplicationState::GameOver);
+        // Runs that make the leaderboard ask for a name before the game over screen.
+        let is_ranked = last_run
+            .as_ref()
+            .is_some_and(|last_run| last_run.rank.is_some());
+        let state = if is_ranked {
+            ApplicationState::NameEntry
+        } else {
+            ApplicationState::GameOver
+        };
+        next_state.set(state.clone());
+        info!("{:?}", state);
     }
 }
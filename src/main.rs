@@ -5,6 +5,8 @@ use bevy_ball::ApplicationPlugin;
 use bevy_ball::game::enemy::EnemyPlugin;
 use bevy_ball::game::GamePlugin;
 use bevy_ball::game::player::PlayerPlugin;
+use bevy_ball::game::random::RunSeed;
+use bevy_ball::game::replay::ReplayMode;
 use bevy_ball::game::score::ScorePlugin;
 use bevy_ball::game::star::StarPlugin;
 use bevy_ball::ui::UIPlugin;
@@ -32,6 +34,8 @@ impl Debug {
             .add_plugins(ApplicationPlugin)
             .add_plugins(UIPlugin)
             .add_plugins(GamePlugin)
+            .insert_resource(RunSeed::from_env())
+            .insert_resource(ReplayMode::from_env())
             .run();
     }
 
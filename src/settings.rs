@@ -0,0 +1,211 @@
+use std::path::Path;
+
+use bevy::audio::{AudioPlayer, AudioSink, AudioSinkPlayback, PlaybackSettings, Volume};
+use bevy::prelude::{
+    info, resource_changed, warn, App, Component, Has, IntoSystemConfigs, OnAdd, Plugin, Query,
+    Res, ResMut, Resource, Startup, Trigger, Update, Window, With,
+};
+use bevy::window::{MonitorSelection, PresentMode, PrimaryWindow, WindowMode};
+use serde::{Deserialize, Serialize};
+
+use crate::storage::{StorageDirectory, StorageError, StorageHelper};
+
+pub struct SettingsPlugin;
+
+impl Plugin for SettingsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<StorageDirectory>()
+            .init_resource::<Settings>()
+            .add_systems(Startup, load_settings)
+            .add_systems(
+                Update,
+                (apply_window_settings, apply_volume_to_playing_sounds)
+                    .run_if(resource_changed::<Settings>),
+            )
+            .add_observer(apply_volume_to_new_sound);
+    }
+}
+
+pub const SETTINGS_FILE_NAME: &str = "settings.ron";
+pub const SETTINGS_SAVE_VERSION: u32 = 1;
+
+/// How the player is steered during a run.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SteeringMode {
+    /// Bound keys, gamepad buttons and the left stick
+    #[default]
+    Buttons,
+    /// Towards the mouse cursor or the touch point
+    Pointer,
+}
+
+impl SteeringMode {
+    pub fn label(&self) -> &'static str {
+        match self {
+            SteeringMode::Buttons => "Keys & Gamepad",
+            SteeringMode::Pointer => "Mouse & Touch",
+        }
+    }
+
+    pub fn toggled(&self) -> SteeringMode {
+        match self {
+            SteeringMode::Buttons => SteeringMode::Pointer,
+            SteeringMode::Pointer => SteeringMode::Buttons,
+        }
+    }
+}
+
+/// Options picked on the settings screen, applied live and kept between sessions.
+///
+/// Volumes and the screen shake go from 0, off, to 1, full.
+#[derive(Resource, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    /// Multiplier of the sound effects and music volumes
+    pub master_volume: f32,
+    pub sfx_volume: f32,
+    pub music_volume: f32,
+    pub fullscreen: bool,
+    pub vsync: bool,
+    pub screen_shake: f32,
+    pub steering_mode: SteeringMode,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            master_volume: 1.0,
+            sfx_volume: 1.0,
+            music_volume: 0.8,
+            fullscreen: false,
+            vsync: true,
+            screen_shake: 1.0,
+            steering_mode: SteeringMode::default(),
+        }
+    }
+}
+
+impl Settings {
+    /// Volume a sound plays at, music or sound effect
+    pub fn volume(&self, is_music: bool) -> f32 {
+        let volume = if is_music {
+            self.music_volume
+        } else {
+            self.sfx_volume
+        };
+        self.master_volume * volume
+    }
+
+    /// Bring every fraction back within `0..=1`, e.g. after a hand edit of the file
+    pub fn clamped(mut self) -> Self {
+        for fraction in [
+            &mut self.master_volume,
+            &mut self.sfx_volume,
+            &mut self.music_volume,
+            &mut self.screen_shake,
+        ] {
+            *fraction = if fraction.is_nan() {
+                0.0
+            } else {
+                fraction.clamp(0.0, 1.0)
+            };
+        }
+        self
+    }
+
+    /// Load the settings saved at `path`, or `None` when nothing was saved yet
+    pub fn load_from(path: &Path) -> Result<Option<Settings>, StorageError> {
+        let Some(content) = StorageHelper::read(path)? else {
+            return Ok(None);
+        };
+
+        match StorageHelper::read_version(&content)? {
+            SETTINGS_SAVE_VERSION => {
+                Ok(Some(StorageHelper::parse::<Settings>(&content)?.clamped()))
+            }
+            version => Err(StorageError::UnsupportedVersion {
+                found: version,
+                supported: SETTINGS_SAVE_VERSION,
+            }),
+        }
+    }
+
+    pub fn save_to(&self, path: &Path) -> Result<(), StorageError> {
+        StorageHelper::save(path, SETTINGS_SAVE_VERSION, self)
+    }
+}
+
+/// Sound played at the music volume, every other sound being a sound effect.
+#[derive(Component)]
+pub struct Music {}
+
+pub fn steering_mode_is(steering_mode: SteeringMode) -> impl Fn(Res<Settings>) -> bool {
+    move |settings: Res<Settings>| settings.steering_mode == steering_mode
+}
+
+pub fn load_settings(mut settings: ResMut<Settings>, storage: Res<StorageDirectory>) {
+    let path = storage.file(SETTINGS_FILE_NAME);
+    match Settings::load_from(&path) {
+        Ok(Some(loaded)) => {
+            info!("Settings loaded from {}", path.display());
+            *settings = loaded;
+        }
+        Ok(None) => info!("No settings saved yet at {}", path.display()),
+        Err(error) => {
+            warn!("Could not load settings from {}: {}", path.display(), error);
+            // Keep the unreadable file around instead of overwriting it on the next save.
+            if path.exists() {
+                match StorageHelper::quarantine(&path) {
+                    Ok(moved_to) => warn!("Moved unreadable settings to {}", moved_to.display()),
+                    Err(error) => warn!("Could not move unreadable settings: {}", error),
+                }
+            }
+        }
+    }
+}
+
+pub fn apply_window_settings(
+    settings: Res<Settings>,
+    mut window_query: Query<&mut Window, With<PrimaryWindow>>,
+) {
+    let Ok(mut window) = window_query.get_single_mut() else {
+        return;
+    };
+
+    let mode = if settings.fullscreen {
+        WindowMode::BorderlessFullscreen(MonitorSelection::Current)
+    } else {
+        WindowMode::Windowed
+    };
+    let present_mode = if settings.vsync {
+        PresentMode::AutoVsync
+    } else {
+        PresentMode::AutoNoVsync
+    };
+    if window.mode != mode {
+        window.mode = mode;
+    }
+    if window.present_mode != present_mode {
+        window.present_mode = present_mode;
+    }
+}
+
+/// Start every sound at the volume of its kind, before it gets to play
+pub fn apply_volume_to_new_sound(
+    trigger: Trigger<OnAdd, AudioPlayer>,
+    mut sound_query: Query<(&mut PlaybackSettings, Has<Music>)>,
+    settings: Res<Settings>,
+) {
+    if let Ok((mut playback_settings, is_music)) = sound_query.get_mut(trigger.entity()) {
+        playback_settings.volume = Volume::new(settings.volume(is_music));
+    }
+}
+
+pub fn apply_volume_to_playing_sounds(
+    settings: Res<Settings>,
+    sink_query: Query<(&AudioSink, Has<Music>)>,
+) {
+    for (sink, is_music) in sink_query.iter() {
+        sink.set_volume(settings.volume(is_music));
+    }
+}
@@ -0,0 +1,147 @@
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use bevy::prelude::Resource;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+const APPLICATION_DIRECTORY: &str = "bevy-ball";
+
+/// Directory where every save file of the game is written to.
+///
+/// Defaults to the platform data directory (e.g. `~/.local/share/bevy-ball` on Linux).
+/// Insert a custom one before adding the plugins to redirect saves, e.g. to a temp directory.
+#[derive(Resource, Debug, Clone)]
+pub struct StorageDirectory {
+    pub path: PathBuf,
+}
+
+impl Default for StorageDirectory {
+    fn default() -> Self {
+        let path = dirs::data_dir()
+            .map(|data_dir| data_dir.join(APPLICATION_DIRECTORY))
+            .unwrap_or_else(|| PathBuf::from("."));
+        Self { path }
+    }
+}
+
+impl StorageDirectory {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// Given a file name, return its full path inside the storage directory
+    pub fn file(&self, file_name: &str) -> PathBuf {
+        self.path.join(file_name)
+    }
+}
+
+#[derive(Debug)]
+pub enum StorageError {
+    Io(io::Error),
+    Parse(ron::error::SpannedError),
+    Serialize(ron::Error),
+    UnsupportedVersion { found: u32, supported: u32 },
+}
+
+impl Display for StorageError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StorageError::Io(error) => write!(f, "io error: {}", error),
+            StorageError::Parse(error) => write!(f, "malformed save file: {}", error),
+            StorageError::Serialize(error) => write!(f, "could not serialize: {}", error),
+            StorageError::UnsupportedVersion { found, supported } => write!(
+                f,
+                "unsupported save version {} (supported up to {})",
+                found, supported
+            ),
+        }
+    }
+}
+
+impl std::error::Error for StorageError {}
+
+impl From<io::Error> for StorageError {
+    fn from(error: io::Error) -> Self {
+        StorageError::Io(error)
+    }
+}
+
+impl From<ron::error::SpannedError> for StorageError {
+    fn from(error: ron::error::SpannedError) -> Self {
+        StorageError::Parse(error)
+    }
+}
+
+impl From<ron::Error> for StorageError {
+    fn from(error: ron::Error) -> Self {
+        StorageError::Serialize(error)
+    }
+}
+
+/// On-disk envelope of every save file: a format version followed by the payload.
+#[derive(Serialize, Deserialize)]
+struct SaveFile<T> {
+    version: u32,
+    data: T,
+}
+
+/// Only the version of a save file, used to pick how to read the rest of it.
+#[derive(Deserialize)]
+struct SaveFileVersion {
+    version: u32,
+}
+
+pub struct StorageHelper {}
+
+impl StorageHelper {
+    /// Read the content of a save file, or `None` when it does not exist yet
+    pub fn read(path: &Path) -> Result<Option<String>, StorageError> {
+        match fs::read_to_string(path) {
+            Ok(content) => Ok(Some(content)),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(error) => Err(error.into()),
+        }
+    }
+
+    /// Read the format version of a save file, without parsing its payload
+    pub fn read_version(content: &str) -> Result<u32, StorageError> {
+        let header: SaveFileVersion = ron::from_str(content)?;
+        Ok(header.version)
+    }
+
+    /// Parse the payload of a save file, once its version is known to match `T`
+    pub fn parse<T: DeserializeOwned>(content: &str) -> Result<T, StorageError> {
+        let save_file: SaveFile<T> = ron::from_str(content)?;
+        Ok(save_file.data)
+    }
+
+    /// Atomically write `data` to `path`, tagged with the given format version.
+    ///
+    /// The content goes to a sibling temporary file first and is then renamed over the target,
+    /// so a crash in the middle never leaves a half-written save behind.
+    pub fn save<T: Serialize>(path: &Path, version: u32, data: &T) -> Result<(), StorageError> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let content = ron::ser::to_string_pretty(
+            &SaveFile { version, data },
+            ron::ser::PrettyConfig::default(),
+        )?;
+
+        let temporary_path = path.with_extension("tmp");
+        fs::write(&temporary_path, content)?;
+        fs::rename(&temporary_path, path)?;
+        Ok(())
+    }
+
+    /// Move an unreadable save file out of the way so it is not overwritten by the next save
+    pub fn quarantine(path: &Path) -> Result<PathBuf, StorageError> {
+        let quarantine_path = path.with_extension("corrupt");
+        fs::rename(path, &quarantine_path)?;
+        Ok(quarantine_path)
+    }
+}
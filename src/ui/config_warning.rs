@@ -0,0 +1,85 @@
+use bevy::prelude::{
+    default, resource_changed, AlignItems, App, BackgroundColor, BuildChildren, ChildBuild, Color,
+    Commands, Component, DespawnRecursiveExt, Entity, GlobalZIndex, IntoSystemConfigs,
+    JustifyContent, Name, Node, Plugin, PositionType, Query, Res, Text, TextColor, TextFont,
+    UiRect, Update, Val, With,
+};
+
+use crate::game::config::{GameConfigWarning, GAME_CONFIG_PATH};
+
+pub struct ConfigWarningPlugin;
+
+impl Plugin for ConfigWarningPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            on_game_config_warning_changed_update_banner
+                .run_if(resource_changed::<GameConfigWarning>),
+        );
+    }
+}
+
+pub const BANNER_COLOR: Color = Color::srgba(0.6, 0.1, 0.1, 0.85);
+
+pub const BANNER_STYLE: Node = {
+    let mut style = Node::DEFAULT;
+    // Pinned to the bottom, out of the way of the HUD and the menus.
+    style.position_type = PositionType::Absolute;
+    style.bottom = Val::Px(0.0);
+    style.left = Val::Px(0.0);
+    style.width = Val::Percent(100.0);
+    style.justify_content = JustifyContent::Center;
+    style.align_items = AlignItems::Center;
+    style.padding = UiRect::new(Val::Px(16.0), Val::Px(16.0), Val::Px(8.0), Val::Px(8.0));
+    style
+};
+
+/// Tells why the last edit of the game config was ignored, in every state.
+#[derive(Component)]
+pub struct ConfigWarning {}
+
+pub fn on_game_config_warning_changed_update_banner(
+    mut commands: Commands,
+    game_config_warning: Res<GameConfigWarning>,
+    banner_query: Query<Entity, With<ConfigWarning>>,
+) {
+    despawn_config_warning(&mut commands, &banner_query);
+    if let Some(message) = &game_config_warning.message {
+        build_config_warning(&mut commands, message);
+    }
+}
+
+pub fn despawn_config_warning(
+    commands: &mut Commands,
+    banner_query: &Query<Entity, With<ConfigWarning>>,
+) {
+    for entity in banner_query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+pub fn build_config_warning(commands: &mut Commands, message: &str) -> Entity {
+    commands
+        .spawn((
+            Name::new("Config Warning"),
+            ConfigWarning {},
+            BANNER_STYLE,
+            BackgroundColor(BANNER_COLOR),
+            // Above every menu, which are spawned later and would otherwise cover it.
+            GlobalZIndex(1),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(format!(
+                    "{} was not applied, keeping the previous values.\n{}",
+                    GAME_CONFIG_PATH, message
+                )),
+                TextFont {
+                    font_size: 20.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(1.0, 1.0, 1.0)),
+            ));
+        })
+        .id()
+}
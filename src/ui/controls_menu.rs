@@ -0,0 +1,352 @@
+use bevy::prelude::{
+    default, in_state, warn, AlignItems, App, BuildChildren, Button, ButtonInput, Changed,
+    ChildBuild, Color, Commands, Component, DespawnRecursiveExt, DetectChanges, Display, Entity,
+    FlexDirection, Interaction, IntoSystemConfigs, JustifyContent, JustifyText, KeyCode, Name,
+    NextState, Node, OnEnter, OnExit, Plugin, PositionType, Query, Res, ResMut, Resource, Text,
+    TextColor, TextFont, TextLayout, UiRect, Update, Val, With,
+};
+
+use crate::input_bindings::{InputAction, InputBindings, INPUT_BINDINGS_FILE_NAME};
+use crate::storage::StorageDirectory;
+use crate::ui::navigation::MenuBack;
+use crate::ui::{UIButton, BUTTON_STYLE};
+use crate::ApplicationState;
+
+pub struct ControlsMenuPlugin;
+
+impl Plugin for ControlsMenuPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            OnEnter(ApplicationState::Controls),
+            (insert_rebind_target_resource, spawn_controls_menu),
+        )
+        .add_systems(
+            OnExit(ApplicationState::Controls),
+            (remove_rebind_target_resource, despawn_controls_menu),
+        )
+        .add_systems(
+            Update,
+            (
+                (
+                    on_keyboard_input_rebind,
+                    on_binding_button_pressed,
+                    on_reset_button_pressed,
+                    on_back_button_pressed,
+                ),
+                update_binding_text,
+            )
+                .chain()
+                .run_if(in_state(ApplicationState::Controls)),
+        );
+    }
+}
+
+/// Action waiting for the next key press to be bound to it.
+#[derive(Resource, Default)]
+pub struct RebindTarget {
+    pub action: Option<InputAction>,
+}
+
+pub fn insert_rebind_target_resource(mut commands: Commands) {
+    commands.insert_resource(RebindTarget::default());
+}
+
+pub fn remove_rebind_target_resource(mut commands: Commands) {
+    commands.remove_resource::<RebindTarget>();
+}
+
+/// Whether the next key press goes to an action rather than to the menu
+pub fn is_rebinding(rebind_target: Option<Res<RebindTarget>>) -> bool {
+    rebind_target.is_some_and(|rebind_target| rebind_target.action.is_some())
+}
+
+fn save_input_bindings(input_bindings: &InputBindings, storage: &StorageDirectory) {
+    let path = storage.file(INPUT_BINDINGS_FILE_NAME);
+    if let Err(error) = input_bindings.save_to(&path) {
+        warn!(
+            "Could not save input bindings to {}: {}",
+            path.display(),
+            error
+        );
+    }
+}
+
+pub fn on_keyboard_input_rebind(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut rebind_target: ResMut<RebindTarget>,
+    mut input_bindings: ResMut<InputBindings>,
+    storage: Res<StorageDirectory>,
+) {
+    let Some(action) = rebind_target.action else {
+        return;
+    };
+    // The key confirming the binding button, if any, is not the one to bind.
+    if rebind_target.is_changed() {
+        return;
+    }
+    let Some(key) = keyboard_input.get_just_pressed().next() else {
+        return;
+    };
+
+    input_bindings.rebind(action, *key);
+    save_input_bindings(&input_bindings, &storage);
+    rebind_target.action = None;
+}
+
+pub fn on_binding_button_pressed(
+    query: Query<(&Interaction, &BindingButton), Changed<Interaction>>,
+    mut rebind_target: ResMut<RebindTarget>,
+) {
+    for (interaction, binding_button) in query.iter() {
+        if *interaction == Interaction::Pressed {
+            rebind_target.action = Some(binding_button.action);
+        }
+    }
+}
+
+pub fn on_reset_button_pressed(
+    query: Query<&Interaction, (Changed<Interaction>, With<ResetButton>)>,
+    mut rebind_target: ResMut<RebindTarget>,
+    mut input_bindings: ResMut<InputBindings>,
+    storage: Res<StorageDirectory>,
+) {
+    for interaction in query.iter() {
+        if *interaction == Interaction::Pressed {
+            *input_bindings = InputBindings::default();
+            save_input_bindings(&input_bindings, &storage);
+            rebind_target.action = None;
+        }
+    }
+}
+
+pub fn on_back_button_pressed(
+    query: Query<&Interaction, (Changed<Interaction>, With<BackButton>)>,
+    mut application_state: ResMut<NextState<ApplicationState>>,
+) {
+    for interaction in query.iter() {
+        if *interaction == Interaction::Pressed {
+            application_state.set(ApplicationState::MainMenu);
+        }
+    }
+}
+
+pub fn update_binding_text(
+    input_bindings: Res<InputBindings>,
+    rebind_target: Res<RebindTarget>,
+    mut text_query: Query<(&mut Text, &BindingText)>,
+) {
+    if !input_bindings.is_changed() && !rebind_target.is_changed() {
+        return;
+    }
+
+    for (mut text, binding_text) in text_query.iter_mut() {
+        text.0 = binding_label(&input_bindings, &rebind_target, binding_text.action);
+    }
+}
+
+/// Name of a key as printed on the keyboard, e.g. `W` rather than `KeyW`
+pub fn key_label(key: KeyCode) -> String {
+    let name = format!("{:?}", key);
+    match name
+        .strip_prefix("Key")
+        .or_else(|| name.strip_prefix("Digit"))
+    {
+        Some(stripped) => stripped.to_string(),
+        None => name,
+    }
+}
+
+fn binding_label(
+    input_bindings: &InputBindings,
+    rebind_target: &RebindTarget,
+    action: InputAction,
+) -> String {
+    if rebind_target.action == Some(action) {
+        return "Press a key...".to_string();
+    }
+
+    let keys = input_bindings.keys(action);
+    if keys.is_empty() {
+        "-".to_string()
+    } else {
+        keys.iter()
+            .map(|key| key_label(*key))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+pub fn spawn_controls_menu(mut commands: Commands, input_bindings: Res<InputBindings>) {
+    build_controls_menu(&mut commands, &input_bindings);
+}
+
+pub fn despawn_controls_menu(mut commands: Commands, query: Query<Entity, With<ControlsMenu>>) {
+    if let Ok(controls_menu_entity) = query.get_single() {
+        commands.entity(controls_menu_entity).despawn_recursive();
+    }
+}
+
+#[derive(Component)]
+pub struct ControlsMenu {}
+
+/// Button starting the rebind of its action.
+#[derive(Component)]
+pub struct BindingButton {
+    pub action: InputAction,
+}
+
+/// Keys of an action, or the prompt to press one while it is being rebound.
+#[derive(Component)]
+pub struct BindingText {
+    pub action: InputAction,
+}
+
+#[derive(Component)]
+pub struct ResetButton {}
+
+#[derive(Component)]
+pub struct BackButton {}
+
+const ACTION_COLUMN_WIDTH: f32 = 240.0;
+
+pub const CONTROLS_MENU_STYLE: Node = {
+    let mut style = Node::DEFAULT;
+    style.position_type = PositionType::Absolute;
+    style.display = Display::Flex;
+    style.flex_direction = FlexDirection::Column;
+    style.justify_content = JustifyContent::Center;
+    style.align_items = AlignItems::Center;
+    style.width = Val::Percent(100.0);
+    style.height = Val::Percent(100.0);
+    style.row_gap = Val::Px(8.0);
+    style
+};
+
+pub const ROW_STYLE: Node = {
+    let mut style = Node::DEFAULT;
+    style.display = Display::Flex;
+    style.flex_direction = FlexDirection::Row;
+    style.align_items = AlignItems::Center;
+    style.column_gap = Val::Px(16.0);
+    style
+};
+
+pub const BINDING_BUTTON_STYLE: Node = {
+    let mut style = Node::DEFAULT;
+    style.width = Val::Px(320.0);
+    style.height = Val::Px(44.0);
+    style.justify_content = JustifyContent::Center;
+    style.align_items = AlignItems::Center;
+    style
+};
+
+pub const FOOTER_STYLE: Node = {
+    let mut style = Node::DEFAULT;
+    style.display = Display::Flex;
+    style.flex_direction = FlexDirection::Row;
+    style.column_gap = Val::Px(16.0);
+    style.margin = UiRect::new(Val::Px(0.0), Val::Px(0.0), Val::Px(16.0), Val::Px(0.0));
+    style
+};
+
+pub fn build_controls_menu(commands: &mut Commands, input_bindings: &InputBindings) -> Entity {
+    let rebind_target = RebindTarget::default();
+    commands
+        .spawn((
+            Name::new("Controls Menu"),
+            ControlsMenu {},
+            CONTROLS_MENU_STYLE,
+        ))
+        .with_children(|parent| {
+            // Title
+            parent.spawn((
+                Text::new("Controls"),
+                TextFont {
+                    font_size: 64.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(1.0, 1.0, 1.0)),
+                TextLayout::new_with_justify(JustifyText::Center),
+            ));
+            // One row per action
+            for action in InputAction::ALL {
+                parent.spawn(ROW_STYLE).with_children(|parent| {
+                    parent.spawn((
+                        Node {
+                            width: Val::Px(ACTION_COLUMN_WIDTH),
+                            ..default()
+                        },
+                        Text::new(action.label()),
+                        TextFont {
+                            font_size: 28.0,
+                            ..default()
+                        },
+                        TextColor(Color::srgb(1.0, 1.0, 1.0)),
+                        TextLayout::new_with_justify(JustifyText::Left),
+                    ));
+                    parent
+                        .spawn((
+                            Button {},
+                            BINDING_BUTTON_STYLE,
+                            BindingButton { action },
+                            UIButton {},
+                        ))
+                        .with_children(|parent| {
+                            parent.spawn((
+                                Text::new(binding_label(input_bindings, &rebind_target, action)),
+                                TextFont {
+                                    font_size: 24.0,
+                                    ..default()
+                                },
+                                TextColor(Color::srgb(1.0, 1.0, 1.0)),
+                                TextLayout::new_with_justify(JustifyText::Center),
+                                BindingText { action },
+                            ));
+                        });
+                });
+            }
+            // Reset and Back Buttons
+            parent.spawn(FOOTER_STYLE).with_children(|parent| {
+                parent
+                    .spawn((
+                        Name::new("Reset Button"),
+                        Button {},
+                        BUTTON_STYLE,
+                        ResetButton {},
+                        UIButton {},
+                    ))
+                    .with_children(|parent| {
+                        parent.spawn((
+                            Text::new("Defaults"),
+                            TextFont {
+                                font_size: 32.0,
+                                ..default()
+                            },
+                            TextColor(Color::srgb(1.0, 1.0, 1.0)),
+                            TextLayout::new_with_justify(JustifyText::Center),
+                        ));
+                    });
+                parent
+                    .spawn((
+                        Name::new("Back Button"),
+                        Button {},
+                        BUTTON_STYLE,
+                        BackButton {},
+                        MenuBack {},
+                        UIButton {},
+                    ))
+                    .with_children(|parent| {
+                        parent.spawn((
+                            Text::new("Back"),
+                            TextFont {
+                                font_size: 32.0,
+                                ..default()
+                            },
+                            TextColor(Color::srgb(1.0, 1.0, 1.0)),
+                            TextLayout::new_with_justify(JustifyText::Center),
+                        ));
+                    });
+            });
+        })
+        .id()
+}
@@ -0,0 +1,111 @@
+use bevy::prelude::{
+    default, in_state, resource_exists_and_changed, App, BackgroundColor, Color, Commands,
+    Component, DespawnRecursiveExt, Entity, IntoSystemConfigs, Name, Node, OnExit, Plugin,
+    PositionType, Query, Res, Text, TextColor, TextFont, UiRect, Update, Val, With,
+};
+
+use crate::game::difficulty::DifficultyDirector;
+use crate::input_bindings::{ActionInput, InputAction};
+use crate::ApplicationState;
+
+pub struct DifficultyOverlayPlugin;
+
+impl Plugin for DifficultyOverlayPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (
+                toggle_difficulty_overlay,
+                update_difficulty_overlay_text
+                    .run_if(resource_exists_and_changed::<DifficultyDirector>),
+            )
+                .chain()
+                .run_if(in_state(ApplicationState::InGame)),
+        )
+        .add_systems(OnExit(ApplicationState::InGame), despawn_difficulty_overlay);
+    }
+}
+
+pub const OVERLAY_COLOR: Color = Color::srgba(0.0, 0.0, 0.0, 0.6);
+
+pub const OVERLAY_STYLE: Node = {
+    let mut style = Node::DEFAULT;
+    // Under the HUD, on the left, so the arena stays visible.
+    style.position_type = PositionType::Absolute;
+    style.top = Val::Px(112.0);
+    style.left = Val::Px(16.0);
+    style.padding = UiRect::new(Val::Px(8.0), Val::Px(8.0), Val::Px(8.0), Val::Px(8.0));
+    style
+};
+
+/// Debug view of the [`DifficultyDirector`] of the current run.
+#[derive(Component)]
+pub struct DifficultyOverlay {}
+
+pub fn toggle_difficulty_overlay(
+    mut commands: Commands,
+    action_input: ActionInput,
+    difficulty_director: Option<Res<DifficultyDirector>>,
+    overlay_query: Query<Entity, With<DifficultyOverlay>>,
+) {
+    if !action_input.just_pressed(InputAction::ToggleDifficultyOverlay) {
+        return;
+    }
+
+    if overlay_query.is_empty() {
+        let difficulty_director = difficulty_director.as_deref().copied().unwrap_or_default();
+        build_difficulty_overlay(&mut commands, &difficulty_director);
+    } else {
+        for entity in overlay_query.iter() {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
+
+pub fn despawn_difficulty_overlay(
+    mut commands: Commands,
+    overlay_query: Query<Entity, With<DifficultyOverlay>>,
+) {
+    for entity in overlay_query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+pub fn update_difficulty_overlay_text(
+    mut overlay_query: Query<&mut Text, With<DifficultyOverlay>>,
+    difficulty_director: Res<DifficultyDirector>,
+) {
+    for mut text in overlay_query.iter_mut() {
+        text.0 = format_difficulty(&difficulty_director);
+    }
+}
+
+pub fn format_difficulty(difficulty_director: &DifficultyDirector) -> String {
+    format!(
+        "Difficulty {:.0}%\nEnemy speed x{:.2}\nEnemy spawn rate x{:.2}\nStar scarcity x{:.2}",
+        difficulty_director.level * 100.0,
+        difficulty_director.enemy_speed_multiplier,
+        difficulty_director.enemy_spawn_rate_multiplier,
+        difficulty_director.star_scarcity_multiplier,
+    )
+}
+
+pub fn build_difficulty_overlay(
+    commands: &mut Commands,
+    difficulty_director: &DifficultyDirector,
+) -> Entity {
+    commands
+        .spawn((
+            Name::new("Difficulty Overlay"),
+            DifficultyOverlay {},
+            OVERLAY_STYLE,
+            BackgroundColor(OVERLAY_COLOR),
+            Text::new(format_difficulty(difficulty_director)),
+            TextFont {
+                font_size: 18.0,
+                ..default()
+            },
+            TextColor(Color::srgb(1.0, 1.0, 1.0)),
+        ))
+        .id()
+}
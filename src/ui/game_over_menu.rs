@@ -7,8 +7,9 @@ use bevy::prelude::{
     With,
 };
 
-use crate::game::high_score::HighScore;
+use crate::game::high_score::LastRun;
 use crate::game::GameState;
+use crate::ui::navigation::MenuBack;
 use crate::ui::UIButton;
 use crate::ApplicationState;
 
@@ -110,6 +111,9 @@ pub struct GameOverMenu {}
 #[derive(Component)]
 pub struct FinalScoreText {}
 
+#[derive(Component)]
+pub struct RankText {}
+
 #[derive(Component)]
 pub struct RestartButton {}
 
@@ -119,20 +123,13 @@ pub struct MainMenuButton {}
 #[derive(Component)]
 pub struct QuitButton {}
 
-pub fn spawn_game_over_menu(mut commands: Commands, high_score: Option<Res<HighScore>>) {
-    let final_score: i32;
-    if let Some(high_score) = &high_score {
-        let last_value = high_score.scores.last();
-        if last_value.is_some() {
-            final_score = last_value.unwrap().1 as i32;
-        } else {
-            final_score = 0;
-        }
-    } else {
-        final_score = 0;
-    }
+pub fn spawn_game_over_menu(mut commands: Commands, last_run: Option<Res<LastRun>>) {
+    let (final_score, rank, is_personal_best) = match &last_run {
+        Some(last_run) => (last_run.score, last_run.rank, last_run.is_personal_best),
+        None => (0, None, false),
+    };
 
-    build_game_over_menu(&mut commands, final_score);
+    build_game_over_menu(&mut commands, final_score, rank, is_personal_best);
 }
 
 pub fn despawn_game_over_menu(
@@ -144,7 +141,18 @@ pub fn despawn_game_over_menu(
     }
 }
 
-pub fn build_game_over_menu(commands: &mut Commands, final_score: i32) -> Entity {
+pub fn build_game_over_menu(
+    commands: &mut Commands,
+    final_score: u32,
+    rank: Option<usize>,
+    is_personal_best: bool,
+) -> Entity {
+    let rank_text = match rank {
+        Some(rank) if is_personal_best => format!("Rank #{} - New personal best!", rank),
+        Some(rank) => format!("Rank #{}", rank),
+        None => "Not ranked".to_string(),
+    };
+
     let game_over_menu_entity = commands
         .spawn((GAME_OVER_MENU_STYLE, GameOverMenu {}))
         .with_children(|parent| {
@@ -163,7 +171,7 @@ pub fn build_game_over_menu(commands: &mut Commands, final_score: i32) -> Entity
                     ));
                     // Final Score Text
                     parent.spawn((
-                        Text::new(format!("Final Score: {}", final_score.to_string())),
+                        Text::new(format!("Final Score: {}", final_score)),
                         TextFont {
                             font_size: 48.0,
                             ..default()
@@ -172,6 +180,17 @@ pub fn build_game_over_menu(commands: &mut Commands, final_score: i32) -> Entity
                         TextLayout::new_with_justify(JustifyText::Center),
                         FinalScoreText {},
                     ));
+                    // Rank Text
+                    parent.spawn((
+                        Text::new(rank_text),
+                        TextFont {
+                            font_size: 32.0,
+                            ..default()
+                        },
+                        TextColor(Color::srgb(1.0, 1.0, 1.0)),
+                        TextLayout::new_with_justify(JustifyText::Center),
+                        RankText {},
+                    ));
                     // Restart Button
                     parent
                         .spawn((
@@ -205,6 +224,7 @@ pub fn build_game_over_menu(commands: &mut Commands, final_score: i32) -> Entity
                             //     ..default()
                             // },
                             MainMenuButton {},
+                            MenuBack {},
                             UIButton {},
                         ))
                         .with_children(|parent| {
@@ -1,13 +1,20 @@
 use bevy::prelude::{
-    default, in_state, AlignItems, App, BuildChildren, ChildBuild, Color, Commands, Component,
-    DespawnRecursiveExt, DetectChanges, Display, Entity, FlexDirection, ImageNode,
-    IntoSystemConfigs, JustifyContent, JustifyText, Node, OnEnter, OnExit, Plugin, Query, Res,
-    Text, TextColor, TextFont, TextLayout, UiRect, Update, Val, With,
+    default, in_state, resource_exists_and_changed, AlignItems, App, BuildChildren, ChildBuild,
+    Color, Commands, Component, DespawnRecursiveExt, DetectChanges, Display, Entity, FlexDirection,
+    ImageNode, IntoSystemConfigs, JustifyContent, JustifyText, Name, Node, OnEnter, OnExit, Plugin,
+    PositionType, Query, Res, Text, TextColor, TextFont, TextLayout, UiRect, Update, Val, With,
 };
 
 use crate::asset_handler::AssetHandler;
+use crate::game::elapsed_time::ElapsedTime;
 use crate::game::enemy::Enemy;
+use crate::game::lives::Lives;
+use crate::game::player::Player;
+use crate::game::power_up::{
+    Magnet, PowerUpEffect, PowerUpKind, ScoreMultiplier, Shield, SlowMotion,
+};
 use crate::game::score::Score;
+use crate::game::wave::Wave;
 use crate::game::GameState;
 use crate::ApplicationState;
 
@@ -19,10 +26,29 @@ impl Plugin for InGameHUDPlugin {
             .add_systems(OnExit(ApplicationState::InGame), despawn_hud)
             .add_systems(
                 Update,
-                (update_score_text, update_enemy_text)
+                (
+                    update_score_text,
+                    update_enemy_text,
+                    update_elapsed_time_text,
+                    update_power_up_text,
+                )
                     .run_if(in_state(ApplicationState::InGame))
                     .run_if(in_state(GameState::Running)),
-            );
+            )
+            .add_systems(
+                Update,
+                update_wave_text
+                    .run_if(in_state(ApplicationState::InGame))
+                    .run_if(resource_exists_and_changed::<Wave>),
+            )
+            .add_systems(
+                Update,
+                update_lives_text
+                    .run_if(in_state(ApplicationState::InGame))
+                    .run_if(resource_exists_and_changed::<Lives>),
+            )
+            .add_systems(OnEnter(GameState::Intermission), spawn_wave_banner)
+            .add_systems(OnExit(GameState::Intermission), despawn_wave_banner);
     }
 }
 
@@ -39,7 +65,7 @@ pub fn despawn_hud(mut commands: Commands, hud_query: Query<Entity, With<HUD>>)
 pub fn update_score_text(mut text_query: Query<&mut Text, With<ScoreText>>, score: Res<Score>) {
     if score.is_changed() {
         for mut text in text_query.iter_mut() {
-            // text.sections[0].value = format!("{}", score.value.to_string());
+            text.0 = score.value.to_string();
         }
     }
 }
@@ -48,22 +74,114 @@ pub fn update_enemy_text(
     mut text_query: Query<&mut Text, With<EnemyText>>,
     enemy_query: Query<Entity, With<Enemy>>,
 ) {
-    let count = enemy_query.iter().count();
+    let count = enemy_query.iter().count().to_string();
+    for mut text in text_query.iter_mut() {
+        // Only write on change, so the text is not re-laid out every frame.
+        if text.0 != count {
+            text.0 = count.clone();
+        }
+    }
+}
+
+pub fn update_wave_text(mut text_query: Query<&mut Text, With<WaveText>>, wave: Res<Wave>) {
+    let formatted = format!("Wave {}", wave.number);
     for mut text in text_query.iter_mut() {
-        // text.sections[0].value = format!("{}", count.to_string());
+        if text.0 != formatted {
+            text.0 = formatted.clone();
+        }
+    }
+}
+
+pub fn update_lives_text(mut text_query: Query<&mut Text, With<LivesText>>, lives: Res<Lives>) {
+    let formatted = format!("Lives {}", lives.remaining);
+    for mut text in text_query.iter_mut() {
+        if text.0 != formatted {
+            text.0 = formatted.clone();
+        }
     }
 }
 
-pub const BACKGROUND_COLOR: Color = Color::rgba(0.25, 0.25, 0.25, 0.5);
+pub fn spawn_wave_banner(mut commands: Commands, wave: Option<Res<Wave>>) {
+    if let Some(wave) = wave {
+        build_wave_banner(&mut commands, wave.number);
+    }
+}
+
+pub fn despawn_wave_banner(mut commands: Commands, banner_query: Query<Entity, With<WaveBanner>>) {
+    for entity in banner_query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+pub fn update_elapsed_time_text(
+    mut text_query: Query<&mut Text, With<ElapsedTimeText>>,
+    elapsed_time: Res<ElapsedTime>,
+) {
+    let total_seconds = elapsed_time.seconds() as u32;
+    let formatted = format!("{:02}:{:02}", total_seconds / 60, total_seconds % 60);
+    for mut text in text_query.iter_mut() {
+        if text.0 != formatted {
+            text.0 = formatted.clone();
+        }
+    }
+}
+
+/// Effects a player may have at once, each absent when not active
+type PowerUpEffects<'a> = (
+    Option<&'a Shield>,
+    Option<&'a SlowMotion>,
+    Option<&'a Magnet>,
+    Option<&'a ScoreMultiplier>,
+);
+
+/// Show a countdown for each effect the player has, hiding the others
+pub fn update_power_up_text(
+    mut text_query: Query<(&mut Text, &mut Node, &PowerUpText)>,
+    player_query: Query<PowerUpEffects, With<Player>>,
+) {
+    let (shield, slow_motion, magnet, score_multiplier) = player_query
+        .get_single()
+        .unwrap_or((None, None, None, None));
+    for (mut text, mut node, power_up_text) in text_query.iter_mut() {
+        let remaining_secs = match power_up_text.kind {
+            PowerUpKind::Shield => shield.map(|effect| effect.remaining_secs()),
+            PowerUpKind::SlowMotion => slow_motion.map(|effect| effect.remaining_secs()),
+            PowerUpKind::Magnet => magnet.map(|effect| effect.remaining_secs()),
+            PowerUpKind::ScoreMultiplier => score_multiplier.map(|effect| effect.remaining_secs()),
+        };
+
+        let (formatted, display) = match remaining_secs {
+            Some(secs) => (
+                format!("{} {}s", power_up_text.kind.label(), secs.ceil() as u32),
+                Display::Flex,
+            ),
+            None => (String::new(), Display::None),
+        };
+        if text.0 != formatted {
+            text.0 = formatted;
+        }
+        if node.display != display {
+            node.display = display;
+        }
+    }
+}
+
+pub const BACKGROUND_COLOR: Color = Color::srgba(0.25, 0.25, 0.25, 0.5);
 
 pub const HUD_STYLE: Node = {
     let mut style = Node::DEFAULT;
+    // Pinned to the top and sized by its content, so resizing the window never squashes it.
+    style.position_type = PositionType::Absolute;
+    style.top = Val::Px(0.0);
+    style.left = Val::Px(0.0);
     style.display = Display::Flex;
     style.flex_direction = FlexDirection::Row;
     style.justify_content = JustifyContent::SpaceBetween;
     style.align_items = AlignItems::Center;
     style.width = Val::Percent(100.0);
-    style.height = Val::Percent(15.0);
+    style.height = Val::Auto;
+    style.padding = UiRect::new(Val::Px(32.0), Val::Px(32.0), Val::Px(8.0), Val::Px(8.0));
+    style.column_gap = Val::Px(16.0);
     style
 };
 
@@ -71,11 +189,21 @@ pub const LHS_STYLE: Node = {
     let mut style = Node::DEFAULT;
     style.display = Display::Flex;
     style.flex_direction = FlexDirection::Row;
+    style.justify_content = JustifyContent::FlexStart;
+    style.align_items = AlignItems::Center;
+    style.min_width = Val::Px(160.0);
+    style.height = Val::Px(80.0);
+    style
+};
+
+pub const CENTER_STYLE: Node = {
+    let mut style = Node::DEFAULT;
+    style.display = Display::Flex;
+    style.flex_direction = FlexDirection::Column;
     style.justify_content = JustifyContent::Center;
     style.align_items = AlignItems::Center;
-    style.width = Val::Px(200.0);
+    style.flex_shrink = 1.0;
     style.height = Val::Px(80.0);
-    style.margin = UiRect::new(Val::Px(32.0), Val::Px(0.0), Val::Px(0.0), Val::Px(0.0));
     style
 };
 
@@ -83,11 +211,33 @@ pub const RHS_STYLE: Node = {
     let mut style = Node::DEFAULT;
     style.display = Display::Flex;
     style.flex_direction = FlexDirection::Row;
-    style.justify_content = JustifyContent::Center;
+    style.justify_content = JustifyContent::FlexEnd;
     style.align_items = AlignItems::Center;
-    style.width = Val::Px(200.0);
+    style.min_width = Val::Px(160.0);
     style.height = Val::Px(80.0);
-    style.margin = UiRect::new(Val::Px(0.0), Val::Px(32.0), Val::Px(0.0), Val::Px(0.0));
+    style
+};
+
+/// Row under the elapsed time, holding the lives and active shield indicators.
+pub const STATUS_STYLE: Node = {
+    let mut style = Node::DEFAULT;
+    style.display = Display::Flex;
+    style.flex_direction = FlexDirection::Row;
+    style.justify_content = JustifyContent::Center;
+    style.align_items = AlignItems::Center;
+    style.column_gap = Val::Px(8.0);
+    style.min_height = Val::Px(24.0);
+    style
+};
+
+/// Centered over the arena, announcing the coming wave during an intermission.
+pub const WAVE_BANNER_STYLE: Node = {
+    let mut style = Node::DEFAULT;
+    style.position_type = PositionType::Absolute;
+    style.width = Val::Percent(100.0);
+    style.height = Val::Percent(100.0);
+    style.justify_content = JustifyContent::Center;
+    style.align_items = AlignItems::Center;
     style
 };
 
@@ -108,9 +258,31 @@ pub struct ScoreText {}
 #[derive(Component)]
 pub struct EnemyText {}
 
+#[derive(Component)]
+pub struct ElapsedTimeText {}
+
+#[derive(Component)]
+pub struct WaveText {}
+
+#[derive(Component)]
+pub struct WaveBanner {}
+
+#[derive(Component)]
+pub struct LivesText {}
+
+/// Countdown of a power-up effect, hidden while the player does not have it.
+#[derive(Component)]
+pub struct PowerUpText {
+    pub kind: PowerUpKind,
+}
+
+/// Slot for the lives and shield indicators of the player.
+#[derive(Component)]
+pub struct StatusSection {}
+
 pub fn build_hud(commands: &mut Commands, asset_handler: &Res<AssetHandler>) -> Entity {
     let hud_entity = commands
-        .spawn(HUD_STYLE)
+        .spawn((Name::new("HUD"), HUD {}, HUD_STYLE))
         .with_children(|parent| {
             // LHS
             parent.spawn(LHS_STYLE).with_children(|parent| {
@@ -131,6 +303,62 @@ pub fn build_hud(commands: &mut Commands, asset_handler: &Res<AssetHandler>) ->
                     ScoreText {},
                 ));
             });
+            // Center
+            parent.spawn(CENTER_STYLE).with_children(|parent| {
+                // Elapsed Time Text
+                parent.spawn((
+                    Text::new("00:00"),
+                    TextFont {
+                        font_size: 40.0,
+                        ..default()
+                    },
+                    TextColor(Color::srgb(1.0, 1.0, 1.0)),
+                    TextLayout::new_with_justify(JustifyText::Center),
+                    ElapsedTimeText {},
+                ));
+                // Wave Text
+                parent.spawn((
+                    Text::new("Wave 1"),
+                    TextFont {
+                        font_size: 24.0,
+                        ..default()
+                    },
+                    TextColor(Color::srgb(1.0, 1.0, 1.0)),
+                    TextLayout::new_with_justify(JustifyText::Center),
+                    WaveText {},
+                ));
+                // Lives / Shield Status
+                parent
+                    .spawn((STATUS_STYLE, StatusSection {}))
+                    .with_children(|parent| {
+                        // Lives Text
+                        parent.spawn((
+                            Text::new(""),
+                            TextFont {
+                                font_size: 20.0,
+                                ..default()
+                            },
+                            TextColor(Color::srgb(1.0, 1.0, 1.0)),
+                            LivesText {},
+                        ));
+                        // Power-Up Countdowns
+                        for kind in PowerUpKind::ALL {
+                            parent.spawn((
+                                Text::new(""),
+                                TextFont {
+                                    font_size: 20.0,
+                                    ..default()
+                                },
+                                TextColor(kind.color()),
+                                Node {
+                                    display: Display::None,
+                                    ..default()
+                                },
+                                PowerUpText { kind },
+                            ));
+                        }
+                    });
+            });
             // RHS
             parent
                 .spawn((
@@ -151,8 +379,8 @@ pub fn build_hud(commands: &mut Commands, asset_handler: &Res<AssetHandler>) ->
                     ));
                     // Enemy Image
                     parent.spawn((
+                        IMAGE_STYLE,
                         ImageNode::new(asset_handler.enemy_texture.clone()),
-                        // style: crate::ui::hud_menu::IMAGE_STYLE,
                     ));
                 });
         })
@@ -160,3 +388,20 @@ pub fn build_hud(commands: &mut Commands, asset_handler: &Res<AssetHandler>) ->
 
     hud_entity
 }
+
+pub fn build_wave_banner(commands: &mut Commands, wave_number: u32) -> Entity {
+    commands
+        .spawn((Name::new("Wave Banner"), WaveBanner {}, WAVE_BANNER_STYLE))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(format!("Wave {}", wave_number)),
+                TextFont {
+                    font_size: 96.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(1.0, 1.0, 1.0)),
+                TextLayout::new_with_justify(JustifyText::Center),
+            ));
+        })
+        .id()
+}
@@ -0,0 +1,256 @@
+use bevy::input::mouse::{MouseScrollUnit, MouseWheel};
+use bevy::prelude::{
+    default, in_state, AlignItems, App, BuildChildren, Button, Changed, ChildBuild, ChildBuilder,
+    Color, Commands, Component, DespawnRecursiveExt, Display, Entity, EventReader, FlexDirection,
+    Interaction, IntoSystemConfigs, JustifyContent, JustifyText, Name, NextState, Node, OnEnter,
+    OnExit, Overflow, Plugin, PositionType, Query, Res, ResMut, ScrollPosition, Text, TextColor,
+    TextFont, TextLayout, Update, Val, With,
+};
+
+use crate::game::high_score::{HighScore, HighScoreEntry};
+use crate::helpers::TimeHelper;
+use crate::ui::navigation::MenuBack;
+use crate::ui::{UIButton, BUTTON_STYLE};
+use crate::ApplicationState;
+
+pub struct LeaderboardMenuPlugin;
+
+impl Plugin for LeaderboardMenuPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            OnEnter(ApplicationState::Leaderboard),
+            spawn_leaderboard_menu,
+        )
+        .add_systems(
+            OnExit(ApplicationState::Leaderboard),
+            despawn_leaderboard_menu,
+        )
+        .add_systems(
+            Update,
+            (on_back_button_pressed, scroll_leaderboard)
+                .run_if(in_state(ApplicationState::Leaderboard)),
+        );
+    }
+}
+
+pub fn on_back_button_pressed(
+    query: Query<&Interaction, (Changed<Interaction>, With<BackButton>)>,
+    mut application_state: ResMut<NextState<ApplicationState>>,
+) {
+    for interaction in query.iter() {
+        if *interaction == Interaction::Pressed {
+            application_state.set(ApplicationState::MainMenu);
+        }
+    }
+}
+
+pub fn scroll_leaderboard(
+    mut mouse_wheel_events: EventReader<MouseWheel>,
+    mut query: Query<&mut ScrollPosition, With<LeaderboardList>>,
+) {
+    for mouse_wheel_event in mouse_wheel_events.read() {
+        let scrolled_pixels = match mouse_wheel_event.unit {
+            MouseScrollUnit::Line => mouse_wheel_event.y * ROW_HEIGHT,
+            MouseScrollUnit::Pixel => mouse_wheel_event.y,
+        };
+
+        // The layout clamps the offset to the content, no need to bound it here.
+        for mut scroll_position in query.iter_mut() {
+            scroll_position.offset_y -= scrolled_pixels;
+        }
+    }
+}
+
+pub fn spawn_leaderboard_menu(mut commands: Commands, high_score: Option<Res<HighScore>>) {
+    match &high_score {
+        Some(high_score) => build_leaderboard_menu(&mut commands, high_score),
+        None => build_leaderboard_menu(&mut commands, &HighScore::default()),
+    };
+}
+
+pub fn despawn_leaderboard_menu(
+    mut commands: Commands,
+    query: Query<Entity, With<LeaderboardMenu>>,
+) {
+    if let Ok(leaderboard_menu_entity) = query.get_single() {
+        commands.entity(leaderboard_menu_entity).despawn_recursive();
+    }
+}
+
+#[derive(Component)]
+pub struct LeaderboardMenu {}
+
+#[derive(Component)]
+pub struct LeaderboardList {}
+
+#[derive(Component)]
+pub struct BackButton {}
+
+const ROW_HEIGHT: f32 = 40.0;
+
+const HEADER_COLOR: Color = Color::srgb(0.7, 0.7, 0.7);
+
+pub const LEADERBOARD_MENU_STYLE: Node = {
+    let mut style = Node::DEFAULT;
+    style.position_type = PositionType::Absolute;
+    style.display = Display::Flex;
+    style.flex_direction = FlexDirection::Column;
+    style.justify_content = JustifyContent::Center;
+    style.align_items = AlignItems::Center;
+    style.width = Val::Percent(100.0);
+    style.height = Val::Percent(100.0);
+    style.row_gap = Val::Px(16.0);
+    style
+};
+
+pub const LEADERBOARD_LIST_STYLE: Node = {
+    let mut style = Node::DEFAULT;
+    style.display = Display::Flex;
+    style.flex_direction = FlexDirection::Column;
+    style.align_items = AlignItems::Center;
+    style.width = Val::Px(640.0);
+    style.max_height = Val::Percent(50.0);
+    style.overflow = Overflow::scroll_y();
+    style
+};
+
+pub const ROW_STYLE: Node = {
+    let mut style = Node::DEFAULT;
+    style.display = Display::Flex;
+    style.flex_direction = FlexDirection::Row;
+    style.align_items = AlignItems::Center;
+    style.flex_shrink = 0.0;
+    style.height = Val::Px(ROW_HEIGHT);
+    style
+};
+
+const RANK_COLUMN_WIDTH: f32 = 80.0;
+const NAME_COLUMN_WIDTH: f32 = 240.0;
+const SCORE_COLUMN_WIDTH: f32 = 120.0;
+const DATE_COLUMN_WIDTH: f32 = 180.0;
+
+fn spawn_row(parent: &mut ChildBuilder, cells: [String; 4], color: Color) {
+    let widths = [
+        RANK_COLUMN_WIDTH,
+        NAME_COLUMN_WIDTH,
+        SCORE_COLUMN_WIDTH,
+        DATE_COLUMN_WIDTH,
+    ];
+
+    parent.spawn(ROW_STYLE).with_children(|parent| {
+        for (cell, width) in cells.into_iter().zip(widths) {
+            parent.spawn((
+                Node {
+                    width: Val::Px(width),
+                    ..default()
+                },
+                Text::new(cell),
+                TextFont {
+                    font_size: 28.0,
+                    ..default()
+                },
+                TextColor(color),
+                TextLayout::new_with_justify(JustifyText::Left),
+            ));
+        }
+    });
+}
+
+fn entry_cells(rank: usize, entry: &HighScoreEntry) -> [String; 4] {
+    // Entries migrated from the first save format do not know when they were played.
+    let date = if entry.timestamp == 0 {
+        "-".to_string()
+    } else {
+        TimeHelper::format_date(entry.timestamp)
+    };
+
+    [
+        format!("#{}", rank),
+        entry.name.clone(),
+        entry.score.to_string(),
+        date,
+    ]
+}
+
+pub fn build_leaderboard_menu(commands: &mut Commands, high_score: &HighScore) -> Entity {
+    let leaderboard_menu_entity = commands
+        .spawn((
+            Name::new("Leaderboard Menu"),
+            LeaderboardMenu {},
+            LEADERBOARD_MENU_STYLE,
+        ))
+        .with_children(|parent| {
+            // Title
+            parent.spawn((
+                Text::new("Leaderboard"),
+                TextFont {
+                    font_size: 64.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(1.0, 1.0, 1.0)),
+                TextLayout::new_with_justify(JustifyText::Center),
+            ));
+            // Header
+            spawn_row(
+                parent,
+                [
+                    "Rank".to_string(),
+                    "Name".to_string(),
+                    "Score".to_string(),
+                    "Date".to_string(),
+                ],
+                HEADER_COLOR,
+            );
+            // Entries
+            parent
+                .spawn((
+                    Name::new("Leaderboard List"),
+                    LeaderboardList {},
+                    LEADERBOARD_LIST_STYLE,
+                ))
+                .with_children(|parent| {
+                    if high_score.entries().is_empty() {
+                        parent.spawn((
+                            Text::new("No scores yet"),
+                            TextFont {
+                                font_size: 28.0,
+                                ..default()
+                            },
+                            TextColor(HEADER_COLOR),
+                            TextLayout::new_with_justify(JustifyText::Center),
+                        ));
+                    }
+                    for (index, entry) in high_score.entries().iter().enumerate() {
+                        spawn_row(
+                            parent,
+                            entry_cells(index + 1, entry),
+                            Color::srgb(1.0, 1.0, 1.0),
+                        );
+                    }
+                });
+            // Back Button
+            parent
+                .spawn((
+                    Name::new("Back Button"),
+                    Button {},
+                    BUTTON_STYLE,
+                    BackButton {},
+                    MenuBack {},
+                    UIButton {},
+                ))
+                .with_children(|parent| {
+                    parent.spawn((
+                        Text::new("Back"),
+                        TextFont {
+                            font_size: 32.0,
+                            ..default()
+                        },
+                        TextColor(Color::srgb(1.0, 1.0, 1.0)),
+                        TextLayout::new_with_justify(JustifyText::Center),
+                    ));
+                });
+        })
+        .id();
+
+    leaderboard_menu_entity
+}
@@ -9,6 +9,7 @@ use bevy::prelude::{
 
 use crate::asset_handler::AssetHandler;
 use crate::game::GameState;
+use crate::ui::navigation::MenuBack;
 use crate::ui::UIButton;
 use crate::ApplicationState;
 
@@ -20,7 +21,13 @@ impl Plugin for MainMenuPlugin {
             .add_systems(OnExit(ApplicationState::MainMenu), despawn_main_menu)
             .add_systems(
                 Update,
-                (on_play_button_pressed, on_quit_button_pressed)
+                (
+                    on_play_button_pressed,
+                    on_leaderboard_button_pressed,
+                    on_controls_button_pressed,
+                    on_settings_button_pressed,
+                    on_quit_button_pressed,
+                )
                     .run_if(in_state(ApplicationState::MainMenu)),
             );
     }
@@ -39,6 +46,39 @@ pub fn on_play_button_pressed(
     }
 }
 
+pub fn on_leaderboard_button_pressed(
+    query: Query<&Interaction, (Changed<Interaction>, With<LeaderboardButton>)>,
+    mut application_state: ResMut<NextState<ApplicationState>>,
+) {
+    if let Ok(interaction) = query.get_single() {
+        if *interaction == Interaction::Pressed {
+            application_state.set(ApplicationState::Leaderboard);
+        }
+    }
+}
+
+pub fn on_controls_button_pressed(
+    query: Query<&Interaction, (Changed<Interaction>, With<ControlsButton>)>,
+    mut application_state: ResMut<NextState<ApplicationState>>,
+) {
+    if let Ok(interaction) = query.get_single() {
+        if *interaction == Interaction::Pressed {
+            application_state.set(ApplicationState::Controls);
+        }
+    }
+}
+
+pub fn on_settings_button_pressed(
+    query: Query<&Interaction, (Changed<Interaction>, With<SettingsButton>)>,
+    mut application_state: ResMut<NextState<ApplicationState>>,
+) {
+    if let Ok(interaction) = query.get_single() {
+        if *interaction == Interaction::Pressed {
+            application_state.set(ApplicationState::Settings);
+        }
+    }
+}
+
 pub fn on_quit_button_pressed(
     query: Query<&Interaction, (Changed<Interaction>, With<QuitButton>)>,
     mut event_writer: EventWriter<AppExit>,
@@ -69,6 +109,15 @@ pub struct TitleSection;
 #[derive(Component)]
 pub struct PlayButton;
 
+#[derive(Component)]
+pub struct LeaderboardButton;
+
+#[derive(Component)]
+pub struct ControlsButton;
+
+#[derive(Component)]
+pub struct SettingsButton;
+
 #[derive(Component)]
 pub struct QuitButton;
 
@@ -156,10 +205,68 @@ pub fn build_main_menu(commands: &mut Commands, asset_handler: &Res<AssetHandler
                         TextLayout::new_with_justify(JustifyText::Center),
                     ));
                 });
+            parent
+                .spawn((
+                    Name::new("Leaderboard Button"),
+                    LeaderboardButton {},
+                    UIButton {},
+                    Button {},
+                    crate::ui::BUTTON_STYLE,
+                ))
+                .with_children(|parent| {
+                    parent.spawn((
+                        Text::new("Scores"),
+                        TextFont {
+                            font_size: 35.0,
+                            ..default()
+                        },
+                        TextColor(Color::srgb(1.0, 1.0, 1.0)),
+                        TextLayout::new_with_justify(JustifyText::Center),
+                    ));
+                });
+            parent
+                .spawn((
+                    Name::new("Controls Button"),
+                    ControlsButton {},
+                    UIButton {},
+                    Button {},
+                    crate::ui::BUTTON_STYLE,
+                ))
+                .with_children(|parent| {
+                    parent.spawn((
+                        Text::new("Controls"),
+                        TextFont {
+                            font_size: 35.0,
+                            ..default()
+                        },
+                        TextColor(Color::srgb(1.0, 1.0, 1.0)),
+                        TextLayout::new_with_justify(JustifyText::Center),
+                    ));
+                });
+            parent
+                .spawn((
+                    Name::new("Settings Button"),
+                    SettingsButton {},
+                    UIButton {},
+                    Button {},
+                    crate::ui::BUTTON_STYLE,
+                ))
+                .with_children(|parent| {
+                    parent.spawn((
+                        Text::new("Settings"),
+                        TextFont {
+                            font_size: 35.0,
+                            ..default()
+                        },
+                        TextColor(Color::srgb(1.0, 1.0, 1.0)),
+                        TextLayout::new_with_justify(JustifyText::Center),
+                    ));
+                });
             parent
                 .spawn((
                     Name::new("Quit Button"),
                     QuitButton {},
+                    MenuBack {},
                     UIButton {},
                     Button {},
                     crate::ui::BUTTON_STYLE,
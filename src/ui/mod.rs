@@ -2,19 +2,34 @@ use std::convert::Into;
 
 use bevy::app::App;
 use bevy::prelude::{
-    AlignItems, BackgroundColor, Changed, Color, Component, Interaction, IntoSystemConfigs,
-    JustifyContent, Node, Plugin, Query, Update, Val, With,
+    resource_changed, AlignItems, BackgroundColor, Changed, Color, Component, Condition,
+    DetectChanges, Entity, Interaction, IntoSystemConfigs, JustifyContent, Node, Plugin, Query,
+    Ref, Res, Update, Val, With,
 };
 
+use crate::ui::config_warning::ConfigWarningPlugin;
+use crate::ui::controls_menu::ControlsMenuPlugin;
+use crate::ui::difficulty_overlay::DifficultyOverlayPlugin;
 use crate::ui::game_over_menu::GameOverMenuPlugin;
 use crate::ui::hud_menu::InGameHUDPlugin;
+use crate::ui::leaderboard_menu::LeaderboardMenuPlugin;
 use crate::ui::main_menu::MainMenuPlugin;
+use crate::ui::name_entry_menu::NameEntryMenuPlugin;
+use crate::ui::navigation::{MenuFocus, MenuNavigationPlugin};
 use crate::ui::pause_menu::PauseMenuPlugin;
+use crate::ui::settings_menu::SettingsMenuPlugin;
 
+mod config_warning;
+mod controls_menu;
+mod difficulty_overlay;
 mod game_over_menu;
 mod hud_menu;
+mod leaderboard_menu;
 mod main_menu;
+mod name_entry_menu;
+mod navigation;
 mod pause_menu;
+mod settings_menu;
 
 const BUTTON_STYLE: Node = {
     let mut style = Node::DEFAULT;
@@ -36,21 +51,41 @@ impl Plugin for UIPlugin {
         app.add_plugins(MainMenuPlugin)
             .add_plugins(InGameHUDPlugin)
             .add_plugins(PauseMenuPlugin)
+            .add_plugins(NameEntryMenuPlugin)
             .add_plugins(GameOverMenuPlugin)
-            .add_systems(Update, handle_button_color_change.run_if(ui_button_present));
+            .add_plugins(LeaderboardMenuPlugin)
+            .add_plugins(ControlsMenuPlugin)
+            .add_plugins(SettingsMenuPlugin)
+            .add_plugins(ConfigWarningPlugin)
+            .add_plugins(DifficultyOverlayPlugin)
+            .add_plugins(MenuNavigationPlugin)
+            .add_systems(
+                Update,
+                handle_button_color_change
+                    .run_if(ui_button_present.or(resource_changed::<MenuFocus>)),
+            );
     }
 }
 
 #[derive(Component)]
 pub struct UIButton;
 
+/// Color buttons by their interaction, the focused button looking hovered
 pub fn handle_button_color_change(
-    mut query: Query<(&Interaction, &mut BackgroundColor), (Changed<Interaction>, With<UIButton>)>,
+    mut query: Query<(Entity, Ref<Interaction>, &mut BackgroundColor), With<UIButton>>,
+    menu_focus: Res<MenuFocus>,
 ) {
-    for (interaction, mut background_color) in query.iter_mut() {
+    for (entity, interaction, mut background_color) in query.iter_mut() {
+        if !interaction.is_changed() && !menu_focus.is_changed() {
+            continue;
+        }
+
         match *interaction {
             Interaction::Pressed => *background_color = BUTTON_COLOR_PRESSED.into(),
             Interaction::Hovered => *background_color = BUTTON_COLOR_HOVERED.into(),
+            Interaction::None if menu_focus.button == Some(entity) => {
+                *background_color = BUTTON_COLOR_HOVERED.into()
+            }
             Interaction::None => *background_color = BUTTON_COLOR_NORMAL.into(),
         }
     }
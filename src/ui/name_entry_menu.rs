@@ -0,0 +1,302 @@
+use bevy::input::keyboard::{Key, KeyboardInput};
+use bevy::prelude::{
+    default, in_state, AlignItems, App, BackgroundColor, BuildChildren, Button, Changed,
+    ChildBuild, Color, Commands, Component, DespawnRecursiveExt, DetectChanges, Display, Entity,
+    EventReader, EventWriter, FlexDirection, Interaction, IntoSystemConfigs, JustifyContent,
+    JustifyText, Name, Node, OnEnter, OnExit, Plugin, PositionType, Query, Res, ResMut, Resource,
+    Text, TextColor, TextFont, TextLayout, Update, Val, With, Without,
+};
+
+use crate::game::high_score::{
+    HighScoreEntry, HighScoreNameEntered, LastRun, PLAYER_NAME_MAX_LENGTH,
+};
+use crate::ui::{UIButton, BUTTON_STYLE};
+use crate::ApplicationState;
+
+pub struct NameEntryMenuPlugin;
+
+impl Plugin for NameEntryMenuPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            OnEnter(ApplicationState::NameEntry),
+            (insert_name_entry_resource, spawn_name_entry_menu),
+        )
+        .add_systems(
+            OnExit(ApplicationState::NameEntry),
+            (remove_name_entry_resource, despawn_name_entry_menu),
+        )
+        .add_systems(
+            Update,
+            (
+                (on_keyboard_input_edit_name, on_confirm_button_pressed),
+                update_name_text,
+            )
+                .chain()
+                .run_if(in_state(ApplicationState::NameEntry)),
+        );
+    }
+}
+
+/// Name being typed, along with the reason it was last rejected.
+#[derive(Resource, Default)]
+pub struct NameEntry {
+    pub value: String,
+    pub error: Option<String>,
+}
+
+impl NameEntry {
+    /// Validate the typed name and send it off, or keep the reason it was rejected
+    fn submit(&mut self, event_writer: &mut EventWriter<HighScoreNameEntered>) {
+        match HighScoreEntry::validate_name(&self.value) {
+            Ok(name) => {
+                event_writer.send(HighScoreNameEntered { name });
+            }
+            Err(error) => self.error = Some(error.to_string()),
+        }
+    }
+}
+
+pub fn insert_name_entry_resource(mut commands: Commands) {
+    commands.insert_resource(NameEntry::default());
+}
+
+pub fn remove_name_entry_resource(mut commands: Commands) {
+    commands.remove_resource::<NameEntry>();
+}
+
+pub fn on_keyboard_input_edit_name(
+    mut keyboard_events: EventReader<KeyboardInput>,
+    mut name_entry: ResMut<NameEntry>,
+    mut event_writer: EventWriter<HighScoreNameEntered>,
+) {
+    // Keys pressed during the last frames of the run are not part of the name.
+    if name_entry.is_added() {
+        keyboard_events.clear();
+        return;
+    }
+
+    for event in keyboard_events.read() {
+        // Holding a key down does not type it again.
+        if !event.state.is_pressed() || event.repeat {
+            continue;
+        }
+
+        match &event.logical_key {
+            Key::Enter => name_entry.submit(&mut event_writer),
+            Key::Backspace => {
+                name_entry.value.pop();
+                name_entry.error = None;
+            }
+            Key::Space => push_name_character(&mut name_entry, ' '),
+            Key::Character(characters) => {
+                for character in characters.chars() {
+                    push_name_character(&mut name_entry, character);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn push_name_character(name_entry: &mut NameEntry, character: char) {
+    if !HighScoreEntry::is_name_character(character) {
+        name_entry.error = Some(format!("'{}' is not allowed in a name", character));
+        return;
+    }
+    if name_entry.value.chars().count() >= PLAYER_NAME_MAX_LENGTH {
+        return;
+    }
+
+    name_entry.value.push(character);
+    name_entry.error = None;
+}
+
+pub fn on_confirm_button_pressed(
+    query: Query<&Interaction, (Changed<Interaction>, With<ConfirmButton>)>,
+    mut name_entry: ResMut<NameEntry>,
+    mut event_writer: EventWriter<HighScoreNameEntered>,
+) {
+    for interaction in query.iter() {
+        if *interaction == Interaction::Pressed {
+            name_entry.submit(&mut event_writer);
+        }
+    }
+}
+
+pub fn update_name_text(
+    name_entry: Res<NameEntry>,
+    mut name_text_query: Query<&mut Text, (With<NameText>, Without<HintText>)>,
+    mut hint_text_query: Query<(&mut Text, &mut TextColor), With<HintText>>,
+) {
+    if !name_entry.is_changed() {
+        return;
+    }
+
+    for mut text in name_text_query.iter_mut() {
+        text.0 = format!("{}_", name_entry.value);
+    }
+    for (mut text, mut text_color) in hint_text_query.iter_mut() {
+        match &name_entry.error {
+            Some(error) => {
+                text.0 = error.clone();
+                text_color.0 = HINT_ERROR_COLOR;
+            }
+            None => {
+                text.0 = NAME_ENTRY_HINT.to_string();
+                text_color.0 = HINT_COLOR;
+            }
+        }
+    }
+}
+
+pub fn spawn_name_entry_menu(mut commands: Commands, last_run: Res<LastRun>) {
+    build_name_entry_menu(&mut commands, &last_run);
+}
+
+pub fn despawn_name_entry_menu(
+    mut commands: Commands,
+    name_entry_menu_query: Query<Entity, With<NameEntryMenu>>,
+) {
+    if let Ok(name_entry_menu_entity) = name_entry_menu_query.get_single() {
+        commands.entity(name_entry_menu_entity).despawn_recursive();
+    }
+}
+
+#[derive(Component)]
+pub struct NameEntryMenu {}
+
+#[derive(Component)]
+pub struct NameText {}
+
+#[derive(Component)]
+pub struct HintText {}
+
+#[derive(Component)]
+pub struct ConfirmButton {}
+
+const NAME_ENTRY_HINT: &str = "Letters, digits, space, - and _";
+
+const HINT_COLOR: Color = Color::srgb(0.7, 0.7, 0.7);
+const HINT_ERROR_COLOR: Color = Color::srgb(0.9, 0.3, 0.3);
+const NAME_FIELD_COLOR: Color = Color::srgb(0.15, 0.15, 0.15);
+
+pub const NAME_ENTRY_MENU_STYLE: Node = {
+    let mut style = Node::DEFAULT;
+    style.position_type = PositionType::Absolute;
+    style.display = Display::Flex;
+    style.justify_content = JustifyContent::Center;
+    style.align_items = AlignItems::Center;
+    style.width = Val::Percent(100.0);
+    style.height = Val::Percent(100.0);
+    style
+};
+
+pub const NAME_ENTRY_MENU_CONTAINER_STYLE: Node = {
+    let mut style = Node::DEFAULT;
+    style.display = Display::Flex;
+    style.flex_direction = FlexDirection::Column;
+    style.justify_content = JustifyContent::Center;
+    style.align_items = AlignItems::Center;
+    style.width = Val::Px(500.0);
+    style.height = Val::Px(400.0);
+    style.row_gap = Val::Px(8.0);
+    style
+};
+
+pub const NAME_FIELD_STYLE: Node = {
+    let mut style = Node::DEFAULT;
+    style.justify_content = JustifyContent::Center;
+    style.align_items = AlignItems::Center;
+    style.width = Val::Px(400.0);
+    style.height = Val::Px(80.0);
+    style
+};
+
+pub fn build_name_entry_menu(commands: &mut Commands, last_run: &LastRun) -> Entity {
+    let rank_text = match last_run.rank {
+        Some(rank) => format!("Score {} - Rank #{}", last_run.score, rank),
+        None => format!("Score {}", last_run.score),
+    };
+
+    let name_entry_menu_entity = commands
+        .spawn((
+            Name::new("Name Entry Menu"),
+            NameEntryMenu {},
+            NAME_ENTRY_MENU_STYLE,
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn(NAME_ENTRY_MENU_CONTAINER_STYLE)
+                .with_children(|parent| {
+                    // Title
+                    parent.spawn((
+                        Text::new("New High Score!"),
+                        TextFont {
+                            font_size: 64.0,
+                            ..default()
+                        },
+                        TextColor(Color::srgb(1.0, 1.0, 1.0)),
+                        TextLayout::new_with_justify(JustifyText::Center),
+                    ));
+                    // Rank Text
+                    parent.spawn((
+                        Text::new(rank_text),
+                        TextFont {
+                            font_size: 32.0,
+                            ..default()
+                        },
+                        TextColor(Color::srgb(1.0, 1.0, 1.0)),
+                        TextLayout::new_with_justify(JustifyText::Center),
+                    ));
+                    // Name Field
+                    parent
+                        .spawn((NAME_FIELD_STYLE, BackgroundColor(NAME_FIELD_COLOR)))
+                        .with_children(|parent| {
+                            parent.spawn((
+                                Text::new("_"),
+                                TextFont {
+                                    font_size: 48.0,
+                                    ..default()
+                                },
+                                TextColor(Color::srgb(1.0, 1.0, 1.0)),
+                                TextLayout::new_with_justify(JustifyText::Center),
+                                NameText {},
+                            ));
+                        });
+                    // Hint Text
+                    parent.spawn((
+                        Text::new(NAME_ENTRY_HINT),
+                        TextFont {
+                            font_size: 20.0,
+                            ..default()
+                        },
+                        TextColor(HINT_COLOR),
+                        TextLayout::new_with_justify(JustifyText::Center),
+                        HintText {},
+                    ));
+                    // Confirm Button
+                    parent
+                        .spawn((
+                            Name::new("Confirm Button"),
+                            Button {},
+                            BUTTON_STYLE,
+                            ConfirmButton {},
+                            UIButton {},
+                        ))
+                        .with_children(|parent| {
+                            parent.spawn((
+                                Text::new("Confirm"),
+                                TextFont {
+                                    font_size: 32.0,
+                                    ..default()
+                                },
+                                TextColor(Color::srgb(1.0, 1.0, 1.0)),
+                                TextLayout::new_with_justify(JustifyText::Center),
+                            ));
+                        });
+                });
+        })
+        .id();
+
+    name_entry_menu_entity
+}
@@ -0,0 +1,188 @@
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::{
+    in_state, not, App, Changed, Children, Commands, Component, Entity, Interaction,
+    IntoSystemConfigs, Node, Parent, Plugin, PostUpdate, PreUpdate, Query, Res, ResMut, Resource,
+    With, Without,
+};
+use bevy::ui::UiSystem;
+
+use crate::input_bindings::{ActionInput, InputAction};
+use crate::ui::controls_menu::is_rebinding;
+use crate::ui::UIButton;
+use crate::ApplicationState;
+
+pub struct MenuNavigationPlugin;
+
+impl Plugin for MenuNavigationPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<MenuFocus>()
+            .add_systems(
+                PreUpdate,
+                (
+                    on_button_hovered_focus_button,
+                    keep_focus_on_a_button,
+                    on_move_input_move_focus,
+                    on_confirm_input_press_focused_button,
+                    on_back_input_press_back_button,
+                )
+                    .chain()
+                    .after(UiSystem::Focus)
+                    // The name entry and a pending rebind take the keyboard for themselves.
+                    .run_if(not(in_state(ApplicationState::NameEntry)))
+                    .run_if(not(is_rebinding)),
+            )
+            .add_systems(PostUpdate, release_navigation_press);
+    }
+}
+
+/// Button the keyboard and gamepads act on, highlighted like a hovered one.
+#[derive(Resource, Debug, Default)]
+pub struct MenuFocus {
+    pub button: Option<Entity>,
+}
+
+/// Button pressed by the back action of its menu.
+#[derive(Component)]
+pub struct MenuBack {}
+
+/// Button pressed by the confirm or back action, released at the end of the frame.
+#[derive(Component)]
+pub struct NavigationPress {}
+
+/// Every [`UIButton`] on screen, in the order of the layout
+#[derive(SystemParam)]
+pub struct MenuButtons<'w, 's> {
+    root_query: Query<'w, 's, Entity, (With<Node>, Without<Parent>)>,
+    children_query: Query<'w, 's, &'static Children>,
+    button_query: Query<'w, 's, (), With<UIButton>>,
+}
+
+impl MenuButtons<'_, '_> {
+    /// Buttons depth first, a node coming before its children and its next siblings
+    pub fn in_layout_order(&self) -> Vec<Entity> {
+        let mut buttons = Vec::new();
+        let mut stack: Vec<Entity> = self.root_query.iter().collect();
+        stack.reverse();
+        while let Some(entity) = stack.pop() {
+            if self.button_query.contains(entity) {
+                buttons.push(entity);
+            }
+            if let Ok(children) = self.children_query.get(entity) {
+                stack.extend(children.iter().rev());
+            }
+        }
+        buttons
+    }
+}
+
+/// Filter of the menu buttons the mouse just moved over, off or pressed.
+type InteractedButton = (Changed<Interaction>, With<UIButton>);
+
+/// Move the focus to the button under the mouse, so a single button is ever highlighted
+pub fn on_button_hovered_focus_button(
+    query: Query<(Entity, &Interaction), InteractedButton>,
+    mut menu_focus: ResMut<MenuFocus>,
+) {
+    for (entity, interaction) in query.iter() {
+        if *interaction == Interaction::Hovered && menu_focus.button != Some(entity) {
+            menu_focus.button = Some(entity);
+        }
+    }
+}
+
+/// Focus the first button of a menu once it shows up, or once the focused one goes away
+pub fn keep_focus_on_a_button(mut menu_focus: ResMut<MenuFocus>, menu_buttons: MenuButtons) {
+    let buttons = menu_buttons.in_layout_order();
+    if menu_focus
+        .button
+        .is_some_and(|button| buttons.contains(&button))
+    {
+        return;
+    }
+
+    let first_button = buttons.first().copied();
+    if menu_focus.button != first_button {
+        menu_focus.button = first_button;
+    }
+}
+
+/// Up and left focus the previous button, down and right the next one, wrapping around
+pub fn on_move_input_move_focus(
+    action_input: ActionInput,
+    mut menu_focus: ResMut<MenuFocus>,
+    menu_buttons: MenuButtons,
+) {
+    let step = if action_input.just_pressed(InputAction::MoveUp)
+        || action_input.just_pressed(InputAction::MoveLeft)
+    {
+        -1
+    } else if action_input.just_pressed(InputAction::MoveDown)
+        || action_input.just_pressed(InputAction::MoveRight)
+    {
+        1
+    } else {
+        return;
+    };
+
+    let buttons = menu_buttons.in_layout_order();
+    if buttons.is_empty() {
+        return;
+    }
+    let index = menu_focus
+        .button
+        .and_then(|button| buttons.iter().position(|entity| *entity == button))
+        .map_or(0, |index| {
+            (index as isize + step).rem_euclid(buttons.len() as isize) as usize
+        });
+    menu_focus.button = Some(buttons[index]);
+}
+
+pub fn on_confirm_input_press_focused_button(
+    mut commands: Commands,
+    action_input: ActionInput,
+    menu_focus: Res<MenuFocus>,
+    mut interaction_query: Query<&mut Interaction, With<UIButton>>,
+) {
+    if !action_input.just_pressed(InputAction::Confirm) {
+        return;
+    }
+    let Some(button) = menu_focus.button else {
+        return;
+    };
+
+    if let Ok(mut interaction) = interaction_query.get_mut(button) {
+        press_button(&mut commands, button, &mut interaction);
+    }
+}
+
+pub fn on_back_input_press_back_button(
+    mut commands: Commands,
+    action_input: ActionInput,
+    mut back_button_query: Query<(Entity, &mut Interaction), With<MenuBack>>,
+) {
+    if !action_input.just_pressed(InputAction::Back) {
+        return;
+    }
+
+    if let Some((button, mut interaction)) = back_button_query.iter_mut().next() {
+        press_button(&mut commands, button, &mut interaction);
+    }
+}
+
+/// Press `button` as a click would, for the menu to react to it during this frame's `Update`
+fn press_button(commands: &mut Commands, button: Entity, interaction: &mut Interaction) {
+    *interaction = Interaction::Pressed;
+    commands.entity(button).insert(NavigationPress {});
+}
+
+pub fn release_navigation_press(
+    mut commands: Commands,
+    mut query: Query<(Entity, &mut Interaction), With<NavigationPress>>,
+) {
+    for (button, mut interaction) in query.iter_mut() {
+        if *interaction == Interaction::Pressed {
+            *interaction = Interaction::None;
+        }
+        commands.entity(button).remove::<NavigationPress>();
+    }
+}
@@ -8,6 +8,7 @@ use bevy::prelude::{
 };
 
 use crate::game::GameState;
+use crate::ui::navigation::MenuBack;
 use crate::ui::{UIButton, BUTTON_COLOR_NORMAL, BUTTON_STYLE};
 use crate::ApplicationState;
 
@@ -25,6 +26,7 @@ impl Plugin for PauseMenuPlugin {
             (
                 on_resume_button_pressed,
                 on_main_menu_button_pressed,
+                on_settings_button_pressed,
                 on_quit_button_pressed,
             )
                 .run_if(in_state(ApplicationState::InGame))
@@ -57,6 +59,17 @@ pub fn on_main_menu_button_pressed(
     }
 }
 
+pub fn on_settings_button_pressed(
+    mut next_state: ResMut<NextState<GameState>>,
+    query: Query<&Interaction, (Changed<Interaction>, With<SettingsButton>)>,
+) {
+    for interaction in query.iter() {
+        if *interaction == Interaction::Pressed {
+            next_state.set(GameState::Settings);
+        }
+    }
+}
+
 pub fn on_quit_button_pressed(
     mut event_writer: EventWriter<AppExit>,
     query: Query<&Interaction, (Changed<Interaction>, With<QuitButton>)>,
@@ -92,6 +105,9 @@ pub struct ResumeButton {}
 #[derive(Component)]
 pub struct MainMenuButton {}
 
+#[derive(Component)]
+pub struct SettingsButton {}
+
 #[derive(Component)]
 pub struct QuitButton {}
 
@@ -115,7 +131,7 @@ pub const PAUSE_MENU_CONTAINER_STYLE: Node = {
     style.justify_content = JustifyContent::Center;
     style.align_items = AlignItems::Center;
     style.width = Val::Px(400.0);
-    style.height = Val::Px(400.0);
+    style.height = Val::Px(480.0);
     style.row_gap = Val::Px(8.0);
     style.column_gap = Val::Px(8.0);
     style
@@ -153,6 +169,7 @@ pub fn build_pause_menu(commands: &mut Commands) -> Entity {
                             BUTTON_STYLE,
                             // background_color: crate::ui::BUTTON_COLOR_NORMAL.into(),
                             ResumeButton {},
+                            MenuBack {},
                             UIButton {},
                         ))
                         .with_children(|parent| {
@@ -186,6 +203,20 @@ pub fn build_pause_menu(commands: &mut Commands) -> Entity {
                                 TextLayout::new_with_justify(JustifyText::Center),
                             ));
                         });
+                    // Settings Button
+                    parent
+                        .spawn((Button {}, BUTTON_STYLE, SettingsButton {}, UIButton {}))
+                        .with_children(|parent| {
+                            parent.spawn((
+                                Text::new("Settings"),
+                                TextFont {
+                                    font_size: 32.0,
+                                    ..default()
+                                },
+                                TextColor(Color::srgb(1.0, 1.0, 1.0)),
+                                TextLayout::new_with_justify(JustifyText::Center),
+                            ));
+                        });
                     // Quit Button
                     parent
                         .spawn((
@@ -0,0 +1,422 @@
+use bevy::prelude::{
+    default, in_state, warn, AlignItems, App, BackgroundColor, BuildChildren, Button, Changed,
+    ChildBuild, ChildBuilder, Color, Commands, Component, Condition, DespawnRecursiveExt,
+    DetectChanges, Display, Entity, FlexDirection, Interaction, IntoSystemConfigs, JustifyContent,
+    JustifyText, Name, NextState, Node, OnEnter, OnExit, Plugin, PositionType, Query, Res, ResMut,
+    State, Text, TextColor, TextFont, TextLayout, UiRect, Update, Val, With,
+};
+
+use crate::game::GameState;
+use crate::settings::{Settings, SETTINGS_FILE_NAME};
+use crate::storage::StorageDirectory;
+use crate::ui::navigation::MenuBack;
+use crate::ui::{UIButton, BUTTON_STYLE};
+use crate::ApplicationState;
+
+/// Fraction a slider button adds or takes away.
+const SLIDER_STEP: f32 = 0.1;
+
+const LABEL_COLUMN_WIDTH: f32 = 240.0;
+
+pub struct SettingsMenuPlugin;
+
+impl Plugin for SettingsMenuPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(ApplicationState::Settings), spawn_settings_menu)
+            .add_systems(OnExit(ApplicationState::Settings), despawn_settings_menu)
+            .add_systems(
+                OnEnter(GameState::Settings),
+                spawn_settings_menu.run_if(in_state(ApplicationState::InGame)),
+            )
+            .add_systems(OnExit(GameState::Settings), despawn_settings_menu)
+            .add_systems(
+                Update,
+                (
+                    (
+                        on_slider_button_pressed,
+                        on_toggle_button_pressed,
+                        on_back_button_pressed,
+                    ),
+                    update_setting_text,
+                )
+                    .chain()
+                    .run_if(in_state(ApplicationState::Settings).or(in_state(GameState::Settings))),
+            );
+    }
+}
+
+/// Line of the settings menu, either a slider from 0 to 1 or a toggle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SettingsField {
+    MasterVolume,
+    SfxVolume,
+    MusicVolume,
+    ScreenShake,
+    Fullscreen,
+    Vsync,
+    SteeringMode,
+}
+
+impl SettingsField {
+    pub const SLIDERS: [SettingsField; 4] = [
+        SettingsField::MasterVolume,
+        SettingsField::SfxVolume,
+        SettingsField::MusicVolume,
+        SettingsField::ScreenShake,
+    ];
+
+    pub const TOGGLES: [SettingsField; 3] = [
+        SettingsField::Fullscreen,
+        SettingsField::Vsync,
+        SettingsField::SteeringMode,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            SettingsField::MasterVolume => "Master Volume",
+            SettingsField::SfxVolume => "Effects Volume",
+            SettingsField::MusicVolume => "Music Volume",
+            SettingsField::ScreenShake => "Screen Shake",
+            SettingsField::Fullscreen => "Display",
+            SettingsField::Vsync => "VSync",
+            SettingsField::SteeringMode => "Controls",
+        }
+    }
+
+    /// Value of the field as shown next to its label
+    pub fn value_label(&self, settings: &Settings) -> String {
+        match self {
+            SettingsField::Fullscreen if settings.fullscreen => "Fullscreen".to_string(),
+            SettingsField::Fullscreen => "Windowed".to_string(),
+            SettingsField::Vsync if settings.vsync => "On".to_string(),
+            SettingsField::Vsync => "Off".to_string(),
+            SettingsField::SteeringMode => settings.steering_mode.label().to_string(),
+            slider => {
+                let fraction = slider.fraction(settings).unwrap_or_default();
+                format!("{}%", (fraction * 100.0).round())
+            }
+        }
+    }
+
+    fn fraction(&self, settings: &Settings) -> Option<f32> {
+        match self {
+            SettingsField::MasterVolume => Some(settings.master_volume),
+            SettingsField::SfxVolume => Some(settings.sfx_volume),
+            SettingsField::MusicVolume => Some(settings.music_volume),
+            SettingsField::ScreenShake => Some(settings.screen_shake),
+            _ => None,
+        }
+    }
+
+    fn fraction_mut<'a>(&self, settings: &'a mut Settings) -> Option<&'a mut f32> {
+        match self {
+            SettingsField::MasterVolume => Some(&mut settings.master_volume),
+            SettingsField::SfxVolume => Some(&mut settings.sfx_volume),
+            SettingsField::MusicVolume => Some(&mut settings.music_volume),
+            SettingsField::ScreenShake => Some(&mut settings.screen_shake),
+            _ => None,
+        }
+    }
+
+    /// Flip a toggle, or move a slider by `step` while keeping it within `0..=1`
+    pub fn change(&self, settings: &mut Settings, step: f32) {
+        match self {
+            SettingsField::Fullscreen => settings.fullscreen = !settings.fullscreen,
+            SettingsField::Vsync => settings.vsync = !settings.vsync,
+            SettingsField::SteeringMode => {
+                settings.steering_mode = settings.steering_mode.toggled()
+            }
+            slider => {
+                if let Some(fraction) = slider.fraction_mut(settings) {
+                    // Rounded to the step, for repeated steps to land on 0 and 1 exactly.
+                    *fraction =
+                        (((*fraction + step) / SLIDER_STEP).round() * SLIDER_STEP).clamp(0.0, 1.0);
+                }
+            }
+        }
+    }
+}
+
+fn save_settings(settings: &Settings, storage: &StorageDirectory) {
+    let path = storage.file(SETTINGS_FILE_NAME);
+    if let Err(error) = settings.save_to(&path) {
+        warn!("Could not save settings to {}: {}", path.display(), error);
+    }
+}
+
+pub fn on_slider_button_pressed(
+    query: Query<(&Interaction, &SliderButton), Changed<Interaction>>,
+    mut settings: ResMut<Settings>,
+    storage: Res<StorageDirectory>,
+) {
+    for (interaction, slider_button) in query.iter() {
+        if *interaction == Interaction::Pressed {
+            slider_button
+                .field
+                .change(&mut settings, slider_button.step);
+            save_settings(&settings, &storage);
+        }
+    }
+}
+
+pub fn on_toggle_button_pressed(
+    query: Query<(&Interaction, &ToggleButton), Changed<Interaction>>,
+    mut settings: ResMut<Settings>,
+    storage: Res<StorageDirectory>,
+) {
+    for (interaction, toggle_button) in query.iter() {
+        if *interaction == Interaction::Pressed {
+            toggle_button.field.change(&mut settings, 0.0);
+            save_settings(&settings, &storage);
+        }
+    }
+}
+
+/// Go back to the menu the settings were opened from
+pub fn on_back_button_pressed(
+    query: Query<&Interaction, (Changed<Interaction>, With<BackButton>)>,
+    current_application_state: Res<State<ApplicationState>>,
+    mut application_state: ResMut<NextState<ApplicationState>>,
+    mut game_state: ResMut<NextState<GameState>>,
+) {
+    for interaction in query.iter() {
+        if *interaction == Interaction::Pressed {
+            if *current_application_state.get() == ApplicationState::Settings {
+                application_state.set(ApplicationState::MainMenu);
+            } else {
+                game_state.set(GameState::Paused);
+            }
+        }
+    }
+}
+
+pub fn update_setting_text(
+    settings: Res<Settings>,
+    mut text_query: Query<(&mut Text, &SettingText)>,
+) {
+    if !settings.is_changed() {
+        return;
+    }
+
+    for (mut text, setting_text) in text_query.iter_mut() {
+        text.0 = setting_text.field.value_label(&settings);
+    }
+}
+
+pub fn spawn_settings_menu(mut commands: Commands, settings: Res<Settings>) {
+    build_settings_menu(&mut commands, &settings);
+}
+
+pub fn despawn_settings_menu(mut commands: Commands, query: Query<Entity, With<SettingsMenu>>) {
+    if let Ok(settings_menu_entity) = query.get_single() {
+        commands.entity(settings_menu_entity).despawn_recursive();
+    }
+}
+
+#[derive(Component)]
+pub struct SettingsMenu {}
+
+/// Button moving a slider by `step`.
+#[derive(Component)]
+pub struct SliderButton {
+    pub field: SettingsField,
+    pub step: f32,
+}
+
+/// Button flipping a toggle.
+#[derive(Component)]
+pub struct ToggleButton {
+    pub field: SettingsField,
+}
+
+/// Current value of a field.
+#[derive(Component)]
+pub struct SettingText {
+    pub field: SettingsField,
+}
+
+#[derive(Component)]
+pub struct BackButton {}
+
+/// Covers the arena when the settings are opened during a run.
+pub const SETTINGS_MENU_BACKGROUND_COLOR: Color = Color::srgba(0.1, 0.1, 0.1, 0.9);
+
+pub const SETTINGS_MENU_STYLE: Node = {
+    let mut style = Node::DEFAULT;
+    style.position_type = PositionType::Absolute;
+    style.display = Display::Flex;
+    style.flex_direction = FlexDirection::Column;
+    style.justify_content = JustifyContent::Center;
+    style.align_items = AlignItems::Center;
+    style.width = Val::Percent(100.0);
+    style.height = Val::Percent(100.0);
+    style.row_gap = Val::Px(8.0);
+    style
+};
+
+pub const ROW_STYLE: Node = {
+    let mut style = Node::DEFAULT;
+    style.display = Display::Flex;
+    style.flex_direction = FlexDirection::Row;
+    style.align_items = AlignItems::Center;
+    style.column_gap = Val::Px(16.0);
+    style
+};
+
+pub const SLIDER_BUTTON_STYLE: Node = {
+    let mut style = Node::DEFAULT;
+    style.width = Val::Px(44.0);
+    style.height = Val::Px(44.0);
+    style.justify_content = JustifyContent::Center;
+    style.align_items = AlignItems::Center;
+    style
+};
+
+pub const SLIDER_VALUE_STYLE: Node = {
+    let mut style = Node::DEFAULT;
+    style.width = Val::Px(200.0);
+    style.justify_content = JustifyContent::Center;
+    style
+};
+
+pub const TOGGLE_BUTTON_STYLE: Node = {
+    let mut style = Node::DEFAULT;
+    style.width = Val::Px(320.0);
+    style.height = Val::Px(44.0);
+    style.justify_content = JustifyContent::Center;
+    style.align_items = AlignItems::Center;
+    style
+};
+
+pub const FOOTER_STYLE: Node = {
+    let mut style = Node::DEFAULT;
+    style.margin = UiRect::new(Val::Px(0.0), Val::Px(0.0), Val::Px(16.0), Val::Px(0.0));
+    style
+};
+
+fn spawn_label(parent: &mut ChildBuilder, field: SettingsField) {
+    parent.spawn((
+        Node {
+            width: Val::Px(LABEL_COLUMN_WIDTH),
+            ..default()
+        },
+        Text::new(field.label()),
+        TextFont {
+            font_size: 28.0,
+            ..default()
+        },
+        TextColor(Color::srgb(1.0, 1.0, 1.0)),
+        TextLayout::new_with_justify(JustifyText::Left),
+    ));
+}
+
+fn spawn_slider_button(parent: &mut ChildBuilder, field: SettingsField, step: f32) {
+    parent
+        .spawn((
+            Button {},
+            SLIDER_BUTTON_STYLE,
+            SliderButton { field, step },
+            UIButton {},
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(if step < 0.0 { "-" } else { "+" }),
+                TextFont {
+                    font_size: 28.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(1.0, 1.0, 1.0)),
+                TextLayout::new_with_justify(JustifyText::Center),
+            ));
+        });
+}
+
+pub fn build_settings_menu(commands: &mut Commands, settings: &Settings) -> Entity {
+    commands
+        .spawn((
+            Name::new("Settings Menu"),
+            SettingsMenu {},
+            SETTINGS_MENU_STYLE,
+            BackgroundColor(SETTINGS_MENU_BACKGROUND_COLOR),
+        ))
+        .with_children(|parent| {
+            // Title
+            parent.spawn((
+                Text::new("Settings"),
+                TextFont {
+                    font_size: 64.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(1.0, 1.0, 1.0)),
+                TextLayout::new_with_justify(JustifyText::Center),
+            ));
+            // Sliders, as - value +
+            for field in SettingsField::SLIDERS {
+                parent.spawn(ROW_STYLE).with_children(|parent| {
+                    spawn_label(parent, field);
+                    spawn_slider_button(parent, field, -SLIDER_STEP);
+                    parent.spawn((
+                        SLIDER_VALUE_STYLE,
+                        Text::new(field.value_label(settings)),
+                        TextFont {
+                            font_size: 24.0,
+                            ..default()
+                        },
+                        TextColor(Color::srgb(1.0, 1.0, 1.0)),
+                        TextLayout::new_with_justify(JustifyText::Center),
+                        SettingText { field },
+                    ));
+                    spawn_slider_button(parent, field, SLIDER_STEP);
+                });
+            }
+            // Toggles
+            for field in SettingsField::TOGGLES {
+                parent.spawn(ROW_STYLE).with_children(|parent| {
+                    spawn_label(parent, field);
+                    parent
+                        .spawn((
+                            Button {},
+                            TOGGLE_BUTTON_STYLE,
+                            ToggleButton { field },
+                            UIButton {},
+                        ))
+                        .with_children(|parent| {
+                            parent.spawn((
+                                Text::new(field.value_label(settings)),
+                                TextFont {
+                                    font_size: 24.0,
+                                    ..default()
+                                },
+                                TextColor(Color::srgb(1.0, 1.0, 1.0)),
+                                TextLayout::new_with_justify(JustifyText::Center),
+                                SettingText { field },
+                            ));
+                        });
+                });
+            }
+            // Back Button
+            parent.spawn(FOOTER_STYLE).with_children(|parent| {
+                parent
+                    .spawn((
+                        Name::new("Back Button"),
+                        Button {},
+                        BUTTON_STYLE,
+                        BackButton {},
+                        MenuBack {},
+                        UIButton {},
+                    ))
+                    .with_children(|parent| {
+                        parent.spawn((
+                            Text::new("Back"),
+                            TextFont {
+                                font_size: 32.0,
+                                ..default()
+                            },
+                            TextColor(Color::srgb(1.0, 1.0, 1.0)),
+                            TextLayout::new_with_justify(JustifyText::Center),
+                        ));
+                    });
+            });
+        })
+        .id()
+}
@@ -0,0 +1,92 @@
+use bevy::math::{UVec2, Vec3};
+use bevy::prelude::{Camera, KeyCode, Window, With};
+use bevy::window::{PrimaryWindow, WindowResolution};
+
+use bevy_ball::arena::{Arena, ArenaCamera, LetterboxCamera};
+use bevy_ball::harness::SimulationHarness;
+
+#[test]
+fn arena_fills_a_window_of_the_same_aspect_ratio() {
+    let arena = Arena::new(1280.0, 720.0);
+
+    assert_eq!(
+        arena.letterbox(UVec2::new(1920, 1080)),
+        (UVec2::ZERO, UVec2::new(1920, 1080))
+    );
+}
+
+#[test]
+fn wide_window_gets_borders_on_the_sides() {
+    let arena = Arena::new(1280.0, 720.0);
+
+    assert_eq!(
+        arena.letterbox(UVec2::new(2000, 720)),
+        (UVec2::new(360, 0), UVec2::new(1280, 720))
+    );
+}
+
+#[test]
+fn tall_window_gets_borders_above_and_below() {
+    let arena = Arena::new(1280.0, 720.0);
+
+    assert_eq!(
+        arena.letterbox(UVec2::new(640, 1000)),
+        (UVec2::new(0, 320), UVec2::new(640, 360))
+    );
+}
+
+#[test]
+fn game_runs_without_a_window() {
+    let mut harness = SimulationHarness::new();
+    harness.start_run().ticks(10);
+
+    let player = harness.player().unwrap();
+    assert_eq!(harness.translation(player), Some(harness.arena().center()));
+}
+
+#[test]
+fn resizing_the_window_mid_run_does_not_move_the_walls() {
+    let mut harness = SimulationHarness::new();
+    harness
+        .world_mut()
+        .spawn((Window::default(), PrimaryWindow));
+    harness.start_run().clear_enemies().clear_stars().tick();
+    let player = harness.player().unwrap();
+
+    harness
+        .world_mut()
+        .query_filtered::<&mut Window, With<PrimaryWindow>>()
+        .single_mut(harness.world_mut())
+        .resolution = WindowResolution::new(400.0, 900.0);
+    harness
+        .press(KeyCode::ArrowRight)
+        .press(KeyCode::ArrowUp)
+        .ticks(300);
+
+    let arena = harness.arena();
+    let half_size = harness.config().player.size / 2.0;
+    assert_eq!(
+        harness.translation(player),
+        Some(Vec3::new(
+            arena.width - half_size,
+            arena.height - half_size,
+            0.0
+        ))
+    );
+
+    let viewport = harness
+        .world_mut()
+        .query_filtered::<&Camera, With<ArenaCamera>>()
+        .single(harness.world())
+        .viewport
+        .clone()
+        .unwrap();
+    assert_eq!(viewport.physical_position, UVec2::new(0, 337));
+    assert_eq!(viewport.physical_size, UVec2::new(400, 225));
+    // The borders are cleared by a camera over the whole window.
+    let letterbox_camera = harness
+        .world_mut()
+        .query_filtered::<&Camera, With<LetterboxCamera>>()
+        .single(harness.world());
+    assert!(letterbox_camera.viewport.is_none());
+}
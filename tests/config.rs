@@ -0,0 +1,176 @@
+use std::time::Duration;
+
+use bevy::asset::io::AssetReaderError;
+use bevy::asset::{AssetLoadError, AssetLoadFailedEvent, Assets};
+
+use bevy_ball::game::config::{
+    GameConfig, GameConfigError, GameConfigHandle, GameConfigWarning, GAME_CONFIG_PATH,
+};
+use bevy_ball::game::enemy::EnemySpawnTimer;
+use bevy_ball::game::star::StarSpawnTimer;
+use bevy_ball::harness::SimulationHarness;
+
+fn shipped_config() -> Vec<u8> {
+    let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("assets")
+        .join(GAME_CONFIG_PATH);
+    std::fs::read(path).unwrap()
+}
+
+#[test]
+fn shipped_config_is_valid() {
+    let config = GameConfig::from_ron(&shipped_config());
+
+    assert!(config.is_ok(), "{}", config.unwrap_err());
+}
+
+#[test]
+fn default_config_is_valid() {
+    assert!(GameConfig::default().validate().is_ok());
+}
+
+#[test]
+fn malformed_config_is_a_parse_error() {
+    let config = GameConfig::from_ron(b"(player: (speed: 500.0))");
+
+    assert!(matches!(config, Err(GameConfigError::Parse(_))));
+}
+
+#[test]
+fn out_of_range_value_names_the_field() {
+    let mut config = GameConfig::default();
+    config.waves[1].enemy_spawn_time = 0.0;
+
+    let error = config.validate().unwrap_err();
+
+    assert!(matches!(
+        &error,
+        GameConfigError::Invalid { field, .. } if field == "waves[1].enemy_spawn_time"
+    ));
+    assert_eq!(
+        error.to_string(),
+        "invalid game config: waves[1].enemy_spawn_time must be greater than 0, got 0"
+    );
+}
+
+#[test]
+fn negative_speed_is_rejected() {
+    let mut config = GameConfig::default();
+    config.player.speed = -1.0;
+
+    assert!(config.validate().is_err());
+}
+
+#[test]
+fn full_stick_deadzone_is_rejected() {
+    let mut config = GameConfig::default();
+    config.gamepad.stick_deadzone = 1.0;
+
+    assert!(matches!(
+        config.validate(),
+        Err(GameConfigError::Invalid { field, .. }) if field == "gamepad.stick_deadzone"
+    ));
+}
+
+#[test]
+fn wave_ending_at_no_score_is_rejected() {
+    let mut config = GameConfig::default();
+    config.waves[2].score = Some(0);
+
+    assert!(matches!(
+        config.validate(),
+        Err(GameConfigError::Invalid { field, .. }) if field == "waves[2].score"
+    ));
+}
+
+#[test]
+fn config_without_waves_is_rejected() {
+    let mut config = GameConfig::default();
+    config.waves.clear();
+
+    assert!(config.validate().is_err());
+}
+
+#[test]
+fn waves_past_the_last_repeat_it() {
+    let config = GameConfig::default();
+    let last_wave = config.waves.len() as u32;
+
+    assert_eq!(config.wave(last_wave + 10), config.wave(last_wave));
+    assert_eq!(config.wave(0), config.wave(1));
+}
+
+#[test]
+fn config_round_trips_through_ron() {
+    let mut config = GameConfig::default();
+    config.waves[0].star_count = 3;
+
+    let content = ron::to_string(&config).unwrap();
+
+    assert_eq!(GameConfig::from_ron(content.as_bytes()).unwrap(), config);
+}
+
+fn edit_loaded_config(harness: &mut SimulationHarness, edit: impl FnOnce(&mut GameConfig)) {
+    let handle = harness
+        .world()
+        .resource::<GameConfigHandle>()
+        .handle
+        .clone();
+    let mut game_configs = harness.world_mut().resource_mut::<Assets<GameConfig>>();
+    edit(game_configs.get_mut(&handle).unwrap());
+}
+
+#[test]
+fn reloaded_config_updates_the_running_game() {
+    let mut harness = SimulationHarness::new();
+    harness.start_run().tick();
+
+    edit_loaded_config(&mut harness, |config| {
+        config.waves[0].enemy_spawn_time = 2.5;
+        config.waves[0].star_spawn_time = 0.5;
+    });
+    // The asset event is only sent at the end of the frame.
+    harness.ticks(2);
+
+    assert_eq!(harness.config().wave(1).enemy_spawn_time, 2.5);
+    let enemy_spawn_timer = &harness.world().resource::<EnemySpawnTimer>().timer;
+    assert_eq!(enemy_spawn_timer.duration(), Duration::from_secs_f32(2.5));
+    let star_spawn_timer = &harness.world().resource::<StarSpawnTimer>().timer;
+    assert_eq!(star_spawn_timer.duration(), Duration::from_secs_f32(0.5));
+}
+
+#[test]
+fn failed_reload_keeps_the_previous_config_and_warns() {
+    let mut harness = SimulationHarness::new();
+    let previous_config = harness.config().clone();
+    let handle = harness
+        .world()
+        .resource::<GameConfigHandle>()
+        .handle
+        .clone();
+
+    harness.world_mut().send_event(AssetLoadFailedEvent {
+        id: handle.id(),
+        path: GAME_CONFIG_PATH.into(),
+        error: AssetLoadError::AssetReaderError(AssetReaderError::NotFound(
+            GAME_CONFIG_PATH.into(),
+        )),
+    });
+    harness.tick();
+
+    assert_eq!(*harness.config(), previous_config);
+    let warning = &harness.world().resource::<GameConfigWarning>().message;
+    assert!(warning
+        .as_ref()
+        .is_some_and(|message| message.contains(GAME_CONFIG_PATH)));
+
+    edit_loaded_config(&mut harness, |config| config.player.speed = 300.0);
+    harness.ticks(2);
+
+    assert_eq!(harness.config().player.speed, 300.0);
+    assert!(harness
+        .world()
+        .resource::<GameConfigWarning>()
+        .message
+        .is_none());
+}
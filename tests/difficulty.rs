@@ -0,0 +1,124 @@
+use bevy::math::Vec3;
+
+use bevy_ball::game::config::{DifficultyConfig, GameConfig};
+use bevy_ball::game::difficulty::{DifficultyCurve, DifficultyDirector, MAX_CURVE_STEEPNESS};
+use bevy_ball::game::high_score::LastRun;
+use bevy_ball::harness::SimulationHarness;
+
+fn assert_close(actual: f32, expected: f32) {
+    assert!((actual - expected).abs() < 1e-4, "{actual} != {expected}");
+}
+
+#[test]
+fn linear_curve_reaches_full_difficulty_at_ramp_time() {
+    let curve = DifficultyCurve::Linear { ramp_time: 100.0 };
+
+    assert_close(curve.level(0.0), 0.0);
+    assert_close(curve.level(25.0), 0.25);
+    assert_close(curve.level(100.0), 1.0);
+}
+
+#[test]
+fn exponential_curve_starts_slow_and_ends_at_full_difficulty() {
+    let curve = DifficultyCurve::Exponential {
+        ramp_time: 100.0,
+        steepness: 3.0,
+    };
+
+    assert_close(curve.level(0.0), 0.0);
+    assert!(curve.level(50.0) < 0.5);
+    assert_close(curve.level(100.0), 1.0);
+}
+
+#[test]
+fn piecewise_curve_interpolates_and_holds_past_the_last_point() {
+    let curve = DifficultyCurve::Piecewise {
+        points: vec![(0.0, 0.0), (10.0, 0.5), (30.0, 0.7)],
+    };
+
+    assert_close(curve.level(5.0), 0.25);
+    assert_close(curve.level(20.0), 0.6);
+    assert_close(curve.level(1_000.0), 0.7);
+}
+
+#[test]
+fn director_is_capped() {
+    let config = DifficultyConfig {
+        cap: 0.5,
+        ..DifficultyConfig::default()
+    };
+
+    let director = DifficultyDirector::from_progress(10_000.0, 0, &config);
+
+    assert_close(director.level, 0.5);
+    assert_close(
+        director.enemy_speed_multiplier,
+        1.0 + 0.5 * config.enemy_speed,
+    );
+}
+
+#[test]
+fn score_adds_pressure() {
+    let config = DifficultyConfig::default();
+
+    let idle = DifficultyDirector::from_progress(10.0, 0, &config);
+    let scoring = DifficultyDirector::from_progress(10.0, 5, &config);
+
+    assert!(scoring.level > idle.level);
+}
+
+#[test]
+fn unsorted_piecewise_curve_is_rejected() {
+    let mut config = GameConfig::default();
+    config.difficulty.curve = DifficultyCurve::Piecewise {
+        points: vec![(10.0, 0.5), (5.0, 0.7)],
+    };
+
+    assert!(config.validate().is_err());
+}
+
+#[test]
+fn too_steep_exponential_curve_is_rejected() {
+    let mut config = GameConfig::default();
+    config.difficulty.curve = DifficultyCurve::Exponential {
+        ramp_time: 100.0,
+        steepness: 100.0,
+    };
+
+    assert!(config.validate().is_err());
+}
+
+#[test]
+fn steepest_exponential_curve_is_capped_long_past_its_ramp() {
+    let config = DifficultyConfig {
+        curve: DifficultyCurve::Exponential {
+            ramp_time: 100.0,
+            steepness: MAX_CURVE_STEEPNESS,
+        },
+        ..DifficultyConfig::default()
+    };
+
+    let director = DifficultyDirector::from_progress(10_000.0, 0, &config);
+
+    assert_close(director.level, config.cap);
+}
+
+#[test]
+fn difficulty_ramps_up_during_a_run_and_is_recorded() {
+    let mut harness = SimulationHarness::new();
+    harness.start_run().clear_enemies().ticks(120);
+
+    let director = *harness.world().resource::<DifficultyDirector>();
+    assert!(director.level > 0.0);
+    assert!(director.enemy_speed_multiplier > 1.0);
+
+    let player = harness.player().unwrap();
+    let player_translation = harness.translation(player).unwrap();
+    harness
+        .set_lives(1)
+        .spawn_enemy(player_translation, Vec3::X);
+    harness.ticks(2);
+
+    let recorded = harness.world().resource::<LastRun>().difficulty.unwrap();
+    assert!(recorded.level >= director.level);
+}
@@ -0,0 +1,162 @@
+use bevy::math::Vec3;
+use bevy::prelude::Entity;
+
+use bevy_ball::game::enemy_behaviour::{
+    wall_hugger_direction, Charger, EnemyKind, EnemyWeight, Orbiter,
+};
+use bevy_ball::game::random::GameRng;
+use bevy_ball::harness::SimulationHarness;
+
+fn distance_to_player(harness: &mut SimulationHarness, enemy: Entity) -> f32 {
+    let player = harness.player().unwrap();
+    let player_translation = harness.translation(player).unwrap();
+    harness
+        .translation(enemy)
+        .unwrap()
+        .distance(player_translation)
+}
+
+#[test]
+fn pick_never_returns_a_kind_without_weight() {
+    let weights = [
+        EnemyWeight::new(EnemyKind::Bouncer, 1.0, 0.0),
+        EnemyWeight::new(EnemyKind::Homing, 0.0, 1.0),
+    ];
+    let mut rng = GameRng::from_seed(7);
+
+    for _ in 0..100 {
+        assert_eq!(EnemyKind::pick(&weights, 0.0, &mut rng), EnemyKind::Bouncer);
+        assert_eq!(EnemyKind::pick(&weights, 1.0, &mut rng), EnemyKind::Homing);
+    }
+}
+
+#[test]
+fn weight_follows_the_difficulty() {
+    let weight = EnemyWeight::new(EnemyKind::Charger, 1.0, 3.0);
+
+    assert_eq!(weight.at_level(0.0), 1.0);
+    assert_eq!(weight.at_level(0.5), 2.0);
+    assert_eq!(weight.at_level(5.0), 3.0);
+}
+
+#[test]
+fn homing_enemy_closes_in_on_the_player() {
+    let mut harness = SimulationHarness::empty_run();
+    let center = harness.arena().center();
+    // Start beside the player, heading past it.
+    let enemy = harness.spawn_enemy_of_kind(
+        EnemyKind::Homing,
+        center + Vec3::new(300.0, 0.0, 0.0),
+        Vec3::NEG_Y,
+    );
+    let start = distance_to_player(&mut harness, enemy);
+
+    harness.ticks(60);
+
+    assert!(distance_to_player(&mut harness, enemy) < start);
+}
+
+#[test]
+fn zig_zag_enemy_veers_off_its_heading() {
+    let mut harness = SimulationHarness::empty_run();
+    let start = harness.arena().center() + Vec3::new(0.0, 200.0, 0.0);
+    let enemy = harness.spawn_enemy_of_kind(EnemyKind::ZigZag, start, Vec3::X);
+
+    harness.tick();
+
+    let moved = harness.translation(enemy).unwrap() - start;
+    assert!(moved.x > 0.0);
+    assert!(moved.y.abs() > 0.0);
+}
+
+#[test]
+fn orbiter_keeps_its_distance_to_the_center() {
+    let mut harness = SimulationHarness::empty_run();
+    let radius = harness.config().enemy.behaviour.orbit_radius;
+    let enemy = harness.spawn_enemy_of_kind(
+        EnemyKind::Orbiter,
+        harness.arena().center() + Vec3::new(0.0, 200.0, 0.0),
+        Vec3::X,
+    );
+
+    for _ in 0..30 {
+        harness.tick();
+        let center = harness.world().get::<Orbiter>(enemy).unwrap().center;
+        let distance = harness.translation(enemy).unwrap().distance(center);
+        assert!((distance - radius).abs() < 0.01, "distance {distance}");
+    }
+}
+
+#[test]
+fn charger_waits_then_dashes_at_the_player() {
+    let mut harness = SimulationHarness::empty_run();
+    let center = harness.arena().center();
+    let start = center + Vec3::new(0.0, 250.0, 0.0);
+    let enemy = harness.spawn_enemy_of_kind(EnemyKind::Charger, start, Vec3::X);
+
+    harness.ticks(10);
+    assert_eq!(harness.translation(enemy), Some(start));
+
+    let is_dashing = harness.tick_until(200, |world| {
+        world
+            .get::<Charger>(enemy)
+            .is_some_and(|charger| charger.is_dashing)
+    });
+    harness.tick();
+
+    assert!(is_dashing);
+    let moved = harness.translation(enemy).unwrap() - start;
+    assert!(moved.y < 0.0, "moved {moved}");
+    assert!(moved.x.abs() < 0.01, "moved {moved}");
+}
+
+#[test]
+fn wall_hugger_heads_to_the_nearest_wall() {
+    let min = Vec3::new(10.0, 10.0, 0.0);
+    let max = Vec3::new(110.0, 60.0, 0.0);
+
+    assert_eq!(
+        wall_hugger_direction(Vec3::new(20.0, 35.0, 0.0), min, max),
+        Vec3::NEG_X
+    );
+    assert_eq!(
+        wall_hugger_direction(Vec3::new(60.0, 55.0, 0.0), min, max),
+        Vec3::Y
+    );
+}
+
+#[test]
+fn wall_hugger_crawls_counter_clockwise_around_the_corners() {
+    let min = Vec3::new(10.0, 10.0, 0.0);
+    let max = Vec3::new(110.0, 60.0, 0.0);
+
+    assert_eq!(wall_hugger_direction(min, min, max), Vec3::X);
+    assert_eq!(
+        wall_hugger_direction(Vec3::new(max.x, min.y, 0.0), min, max),
+        Vec3::Y
+    );
+    assert_eq!(wall_hugger_direction(max, min, max), Vec3::NEG_X);
+    assert_eq!(
+        wall_hugger_direction(Vec3::new(min.x, max.y, 0.0), min, max),
+        Vec3::NEG_Y
+    );
+}
+
+#[test]
+fn wall_hugger_crawls_along_the_walls() {
+    let mut harness = SimulationHarness::empty_run();
+    let arena = harness.arena();
+    let half_size = harness.config().enemy.size / 2.0;
+    // Close to the bottom right corner, to go around it quickly.
+    let start = Vec3::new(arena.width - 100.0, 100.0, 0.0);
+    let enemy = harness.spawn_enemy_of_kind(EnemyKind::WallHugger, start, Vec3::NEG_X);
+
+    harness.ticks(120);
+
+    let translation = harness.translation(enemy).unwrap();
+    assert!(
+        (translation.x - (arena.width - half_size)).abs() < 0.01,
+        "at {translation}"
+    );
+    assert!(translation.y > start.y, "at {translation}");
+}
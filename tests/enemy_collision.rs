@@ -0,0 +1,277 @@
+use bevy::math::Vec3;
+use bevy::prelude::Entity;
+
+use bevy_ball::game::enemy::Enemy;
+use bevy_ball::game::enemy_behaviour::{EnemyKind, Orbiter};
+use bevy_ball::harness::SimulationHarness;
+use bevy_ball::helpers::MovementHelper;
+
+/// A run with nothing in the arena, not even the player, so enemies can roam freely
+fn empty_arena() -> SimulationHarness {
+    let mut harness = SimulationHarness::empty_run();
+    let player = harness.player().unwrap();
+    harness.world_mut().despawn(player);
+    harness
+}
+
+fn direction(harness: &SimulationHarness, enemy: Entity) -> Vec3 {
+    harness.world().get::<Enemy>(enemy).unwrap().direction
+}
+
+#[test]
+fn enemies_meeting_head_on_bounce_back() {
+    let mut harness = empty_arena();
+    let left = harness.spawn_enemy(Vec3::new(500.0, 100.0, 0.0), Vec3::X);
+    let right = harness.spawn_enemy(Vec3::new(600.0, 100.0, 0.0), Vec3::NEG_X);
+
+    harness.ticks(30);
+
+    assert!(direction(&harness, left).abs_diff_eq(Vec3::NEG_X, 1e-5));
+    assert!(direction(&harness, right).abs_diff_eq(Vec3::X, 1e-5));
+    let distance = harness
+        .translation(left)
+        .unwrap()
+        .distance(harness.translation(right).unwrap());
+    assert!(
+        distance >= harness.config().enemy.size,
+        "distance {distance}"
+    );
+}
+
+fn speed_multiplier(harness: &SimulationHarness, enemy: Entity) -> f32 {
+    harness
+        .world()
+        .get::<Enemy>(enemy)
+        .unwrap()
+        .speed_multiplier
+}
+
+#[test]
+fn faster_enemy_hands_its_speed_to_a_slower_one() {
+    let mut harness = empty_arena();
+    let fast = harness.spawn_enemy(Vec3::new(500.0, 100.0, 0.0), Vec3::X);
+    let slow = harness.spawn_enemy(Vec3::new(600.0, 100.0, 0.0), Vec3::NEG_X);
+    harness
+        .world_mut()
+        .get_mut::<Enemy>(fast)
+        .unwrap()
+        .speed_multiplier = 1.5;
+    harness
+        .world_mut()
+        .get_mut::<Enemy>(slow)
+        .unwrap()
+        .speed_multiplier = 0.5;
+
+    let is_bounced = harness.tick_until(30, |world| {
+        world.get::<Enemy>(fast).unwrap().direction.x < 0.0
+    });
+
+    assert!(is_bounced);
+    assert!((speed_multiplier(&harness, fast) - 0.5).abs() < 1e-5);
+    assert!((speed_multiplier(&harness, slow) - 1.5).abs() < 1e-5);
+}
+
+#[test]
+fn bounces_do_not_speed_enemies_up_past_the_cap() {
+    let mut harness = empty_arena();
+    let max_speed_multiplier = harness.config().enemy.max_bounce_speed_multiplier;
+    let fast = harness.spawn_enemy(Vec3::new(500.0, 100.0, 0.0), Vec3::X);
+    let slow = harness.spawn_enemy(Vec3::new(600.0, 100.0, 0.0), Vec3::NEG_X);
+    harness
+        .world_mut()
+        .get_mut::<Enemy>(fast)
+        .unwrap()
+        .speed_multiplier = 10.0;
+
+    harness.tick_until(30, |world| {
+        world.get::<Enemy>(slow).unwrap().direction.x > 0.0
+    });
+
+    assert_eq!(speed_multiplier(&harness, slow), max_speed_multiplier);
+}
+
+#[test]
+fn orbiter_is_pushed_along_with_its_orbit() {
+    let mut harness = empty_arena();
+    let radius = harness.config().enemy.behaviour.orbit_radius;
+    let start = harness.arena().center();
+    let orbiter = harness.spawn_enemy_of_kind(EnemyKind::Orbiter, start, Vec3::Y);
+    let center_before = harness.world().get::<Orbiter>(orbiter).unwrap().center;
+    let bouncer = harness.spawn_enemy(start + Vec3::new(0.0, 10.0, 0.0), Vec3::NEG_Y);
+
+    harness.tick();
+
+    let center = harness.world().get::<Orbiter>(orbiter).unwrap().center;
+    assert_ne!(center, center_before);
+    let distance = harness
+        .translation(orbiter)
+        .unwrap()
+        .distance(harness.translation(bouncer).unwrap());
+    assert!(
+        distance >= harness.config().enemy.size - 0.01,
+        "distance {distance}"
+    );
+
+    harness.tick();
+
+    let center = harness.world().get::<Orbiter>(orbiter).unwrap().center;
+    let distance = harness.translation(orbiter).unwrap().distance(center);
+    assert!((distance - radius).abs() < 0.01, "distance {distance}");
+}
+
+#[test]
+fn wall_hugger_bounces_without_leaving_its_wall() {
+    let mut harness = empty_arena();
+    let half_size = harness.config().enemy.size / 2.0;
+    let wall_hugger = harness.spawn_enemy_of_kind(
+        EnemyKind::WallHugger,
+        Vec3::new(500.0, half_size, 0.0),
+        Vec3::X,
+    );
+    let bouncer = harness.spawn_enemy(Vec3::new(600.0, half_size, 0.0), Vec3::NEG_X);
+
+    harness.ticks(30);
+
+    assert!(direction(&harness, bouncer).x > 0.0);
+    let translation = harness.translation(wall_hugger).unwrap();
+    assert!((translation.y - half_size).abs() < 0.01, "at {translation}");
+    let distance = translation.distance(harness.translation(bouncer).unwrap());
+    assert!(
+        distance >= harness.config().enemy.size - 0.01,
+        "distance {distance}"
+    );
+}
+
+#[test]
+fn overlapping_enemies_are_pushed_apart() {
+    let mut harness = empty_arena();
+    let first = harness.spawn_enemy(Vec3::new(500.0, 300.0, 0.0), Vec3::Y);
+    let second = harness.spawn_enemy(Vec3::new(510.0, 300.0, 0.0), Vec3::Y);
+
+    harness.tick();
+
+    let distance = harness
+        .translation(first)
+        .unwrap()
+        .distance(harness.translation(second).unwrap());
+    assert!(
+        distance >= harness.config().enemy.size - 0.01,
+        "distance {distance}"
+    );
+}
+
+#[test]
+fn bigger_unit_is_pushed_less() {
+    let (small, big) = MovementHelper::separate(10.0, Vec3::ZERO, 30.0, Vec3::new(10.0, 0.0, 0.0));
+
+    assert!(small.abs_diff_eq(Vec3::new(-9.0, 0.0, 0.0), 1e-5));
+    assert!(big.abs_diff_eq(Vec3::new(11.0, 0.0, 0.0), 1e-5));
+}
+
+#[test]
+fn equal_units_exchange_their_velocities_along_the_impact() {
+    let (first, second) = MovementHelper::elastic_bounce(
+        10.0,
+        Vec3::ZERO,
+        Vec3::new(1.0, 1.0, 0.0).normalize(),
+        10.0,
+        Vec3::new(10.0, 0.0, 0.0),
+        Vec3::new(-1.0, 1.0, 0.0).normalize(),
+    )
+    .unwrap();
+
+    assert!(first.abs_diff_eq(Vec3::new(-1.0, 1.0, 0.0).normalize(), 1e-5));
+    assert!(second.abs_diff_eq(Vec3::new(1.0, 1.0, 0.0).normalize(), 1e-5));
+}
+
+#[test]
+fn heavier_unit_keeps_its_heading_and_throws_the_lighter_one_back() {
+    let (light, heavy) = MovementHelper::elastic_bounce(
+        10.0,
+        Vec3::ZERO,
+        Vec3::X,
+        40.0,
+        Vec3::new(10.0, 0.0, 0.0),
+        Vec3::NEG_X,
+    )
+    .unwrap();
+
+    // Masses of 100 and 1600: (-1500 - 3200) / 1700 and (-1500 + 200) / 1700.
+    assert!(light.abs_diff_eq(Vec3::new(-47.0 / 17.0, 0.0, 0.0), 1e-5));
+    assert!(heavy.abs_diff_eq(Vec3::new(-13.0 / 17.0, 0.0, 0.0), 1e-5));
+}
+
+#[test]
+fn bounce_keeps_momentum_and_energy() {
+    let (first_velocity, second_velocity) = (Vec3::new(2.0, 0.5, 0.0), Vec3::new(-0.5, 1.0, 0.0));
+    let (first, second) = MovementHelper::elastic_bounce(
+        20.0,
+        Vec3::ZERO,
+        first_velocity,
+        30.0,
+        Vec3::new(20.0, 10.0, 0.0),
+        second_velocity,
+    )
+    .unwrap();
+
+    let (first_mass, second_mass) = (400.0, 900.0);
+    let momentum = first_velocity * first_mass + second_velocity * second_mass;
+    let energy = first_velocity.length_squared() * first_mass
+        + second_velocity.length_squared() * second_mass;
+    assert!((first * first_mass + second * second_mass).abs_diff_eq(momentum, 1e-2));
+    let bounced_energy =
+        first.length_squared() * first_mass + second.length_squared() * second_mass;
+    assert!((bounced_energy - energy).abs() < 1e-2 * energy);
+}
+
+#[test]
+fn units_moving_apart_do_not_bounce() {
+    let bounce = MovementHelper::elastic_bounce(
+        10.0,
+        Vec3::ZERO,
+        Vec3::NEG_X,
+        10.0,
+        Vec3::new(5.0, 0.0, 0.0),
+        Vec3::X,
+    );
+
+    assert_eq!(bounce, None);
+}
+
+#[test]
+fn hundreds_of_enemies_stay_in_the_arena() {
+    let mut harness = empty_arena();
+    let arena = harness.arena();
+    let half_size = harness.config().enemy.size / 2.0;
+    // Packed side by side, in every direction.
+    for row in 0..10 {
+        for column in 0..20 {
+            let translation = Vec3::new(
+                half_size + column as f32 * 64.0,
+                half_size + row as f32 * 72.0,
+                0.0,
+            );
+            let angle = (row * 20 + column) as f32;
+            harness.spawn_enemy(translation, Vec3::new(angle.cos(), angle.sin(), 0.0));
+        }
+    }
+
+    harness.ticks(300);
+
+    let enemies = harness.enemies();
+    assert!(enemies.len() >= 200);
+    for enemy in enemies {
+        let translation = harness.translation(enemy).unwrap();
+        assert!(translation.is_finite(), "at {translation}");
+        assert!(
+            translation.x >= half_size - 0.01 && translation.x <= arena.width - half_size + 0.01
+        );
+        assert!(
+            translation.y >= half_size - 0.01 && translation.y <= arena.height - half_size + 0.01
+        );
+        assert!(direction(&harness, enemy).is_normalized());
+        assert!(
+            speed_multiplier(&harness, enemy) <= harness.config().enemy.max_bounce_speed_multiplier
+        );
+    }
+}
@@ -0,0 +1,102 @@
+use bevy::input::gamepad::GamepadButton;
+use bevy::math::{Vec2, Vec3};
+use bevy::prelude::Entity;
+
+use bevy_ball::game::simulation::TickRate;
+use bevy_ball::game::GameState;
+use bevy_ball::harness::SimulationHarness;
+use bevy_ball::helpers::MovementHelper;
+use bevy_ball::ApplicationState;
+
+const TICKS: usize = 10;
+
+/// A run with nothing in the arena but the player, and a gamepad plugged in
+fn empty_run_with_gamepad() -> (SimulationHarness, Entity) {
+    let mut harness = SimulationHarness::empty_run();
+    let gamepad = harness.connect_gamepad();
+    (harness, gamepad)
+}
+
+/// How far the player goes in [`TICKS`] with the left stick held at `x`, `y`
+fn stick_move(x: f32, y: f32) -> (SimulationHarness, Vec3) {
+    let (mut harness, gamepad) = empty_run_with_gamepad();
+    let player = harness.player().unwrap();
+    let start = harness.translation(player).unwrap();
+
+    harness.move_left_stick(gamepad, x, y).ticks(TICKS);
+
+    let moved = harness.translation(player).unwrap() - start;
+    (harness, moved)
+}
+
+fn full_speed_distance(harness: &SimulationHarness) -> f32 {
+    harness.config().player.speed * TICKS as f32 / TickRate::default().hz as f32
+}
+
+#[test]
+fn full_tilt_moves_the_player_at_full_speed() {
+    let (harness, moved) = stick_move(1.0, 0.0);
+
+    let expected = full_speed_distance(&harness);
+    assert!((moved.x - expected).abs() < 0.01, "moved {moved}");
+    assert_eq!(moved.y, 0.0);
+}
+
+#[test]
+fn partial_tilt_follows_the_response_curve() {
+    let (harness, moved) = stick_move(0.0, -0.5);
+
+    let speed = MovementHelper::handle_stick(Vec2::new(0.0, -0.5), &harness.config().gamepad);
+    let expected = full_speed_distance(&harness) * speed.y;
+    assert!(speed.y > -0.5, "the default curve is slower than the tilt");
+    assert!((moved.y - expected).abs() < 0.01, "moved {moved}");
+    assert_eq!(moved.x, 0.0);
+}
+
+#[test]
+fn stick_within_the_deadzone_leaves_the_player_still() {
+    let (harness, moved) = stick_move(0.1, 0.1);
+
+    assert!(Vec2::new(0.1, 0.1).length() < harness.config().gamepad.stick_deadzone);
+    assert_eq!(moved, Vec3::ZERO);
+}
+
+#[test]
+fn dpad_moves_the_player_at_full_speed() {
+    let (mut harness, gamepad) = empty_run_with_gamepad();
+    let player = harness.player().unwrap();
+    let start = harness.translation(player).unwrap();
+
+    harness
+        .press_button(gamepad, GamepadButton::DPadLeft)
+        .ticks(TICKS);
+
+    let moved = harness.translation(player).unwrap() - start;
+    let expected = -full_speed_distance(&harness);
+    assert!((moved.x - expected).abs() < 0.01, "moved {moved}");
+}
+
+#[test]
+fn start_button_pauses_and_resumes_the_run() {
+    let (mut harness, gamepad) = empty_run_with_gamepad();
+
+    harness.press_button(gamepad, GamepadButton::Start).ticks(2);
+    assert_eq!(harness.game_state(), GameState::Paused);
+
+    harness
+        .release_button(gamepad, GamepadButton::Start)
+        .tick()
+        .press_button(gamepad, GamepadButton::Start)
+        .ticks(2);
+    assert_eq!(harness.game_state(), GameState::Running);
+}
+
+#[test]
+fn start_button_starts_a_run_from_the_main_menu() {
+    let mut harness = SimulationHarness::new();
+    let gamepad = harness.connect_gamepad();
+
+    harness.press_button(gamepad, GamepadButton::Start).ticks(2);
+
+    assert_eq!(harness.application_state(), ApplicationState::InGame);
+}
@@ -0,0 +1,168 @@
+use bevy::math::Vec3;
+use bevy::prelude::{KeyCode, State, World};
+
+use bevy_ball::game::high_score::{HighScore, HighScoreEntry, HIGH_SCORE_CAPACITY};
+use bevy_ball::game::player::CollidedWithEnemy;
+use bevy_ball::game::simulation::TickRate;
+use bevy_ball::game::GameState;
+use bevy_ball::harness::SimulationHarness;
+use bevy_ball::ApplicationState;
+
+fn is_in(state: ApplicationState) -> impl Fn(&World) -> bool {
+    move |world: &World| *world.resource::<State<ApplicationState>>().get() == state
+}
+
+/// Leaderboard full of scores no test run can beat
+fn unbeatable_high_score() -> HighScore {
+    let mut high_score = HighScore::default();
+    for timestamp in 0..HIGH_SCORE_CAPACITY as u64 {
+        high_score.insert(HighScoreEntry::new("Ace", 1_000, timestamp));
+    }
+    high_score
+}
+
+#[test]
+fn run_starts_with_a_player_enemies_and_stars() {
+    let mut harness = SimulationHarness::new();
+    harness.start_run().tick();
+
+    assert_eq!(harness.application_state(), ApplicationState::InGame);
+    assert_eq!(harness.game_state(), GameState::Running);
+    assert!(harness.player().is_some());
+    assert!(!harness.enemies().is_empty());
+    assert!(!harness.stars().is_empty());
+    assert_eq!(harness.score(), Some(0));
+}
+
+#[test]
+fn enemies_and_stars_spawn_clear_of_the_player() {
+    for seed in 0..4 {
+        let mut harness = SimulationHarness::new();
+        harness.seed(seed).start_run();
+        let player = harness.player().unwrap();
+        let player_translation = harness.translation(player).unwrap();
+        let clear_radius = harness.config().player.spawn_clear_radius;
+
+        let mut spawned = harness.enemies();
+        spawned.extend(harness.stars());
+        for entity in spawned {
+            let distance = harness
+                .translation(entity)
+                .unwrap()
+                .distance(player_translation);
+            assert!(
+                distance >= clear_radius,
+                "seed {seed}: spawned {distance} away"
+            );
+        }
+    }
+}
+
+#[test]
+fn touching_a_star_raises_score_by_one() {
+    let mut harness = SimulationHarness::empty_run();
+    let player = harness.player().unwrap();
+    let player_translation = harness.translation(player).unwrap();
+    let star = harness.spawn_star(player_translation);
+
+    harness.tick();
+
+    assert_eq!(harness.score(), Some(1));
+    assert!(harness.world().get_entity(star).is_err());
+}
+
+#[test]
+fn enemy_overlap_on_the_last_life_ends_the_run_on_the_game_over_screen() {
+    let mut harness = SimulationHarness::empty_run();
+    harness.set_lives(1);
+    harness.world_mut().insert_resource(unbeatable_high_score());
+    harness.count_events::<CollidedWithEnemy>();
+    let player = harness.player().unwrap();
+    let player_translation = harness.translation(player).unwrap();
+    harness.spawn_enemy(player_translation, Vec3::X);
+
+    let is_game_over = harness.tick_until(10, is_in(ApplicationState::GameOver));
+
+    assert!(is_game_over);
+    assert_eq!(harness.event_count::<CollidedWithEnemy>(), 1);
+    assert!(harness.player().is_none());
+}
+
+#[test]
+fn ranked_run_asks_for_a_name() {
+    let mut harness = SimulationHarness::empty_run();
+    harness.set_lives(1);
+    let player = harness.player().unwrap();
+    let player_translation = harness.translation(player).unwrap();
+    harness.spawn_enemy(player_translation, Vec3::X);
+
+    assert!(harness.tick_until(10, is_in(ApplicationState::NameEntry)));
+}
+
+#[test]
+fn scripted_keyboard_input_moves_the_player() {
+    let mut harness = SimulationHarness::empty_run();
+    let player = harness.player().unwrap();
+    let start = harness.translation(player).unwrap();
+    let ticks = 10;
+
+    // Nothing is pressed on the first tick of the run.
+    harness.tick().press(KeyCode::ArrowRight).ticks(ticks);
+
+    let expected = harness.config().player.speed * ticks as f32 / TickRate::default().hz as f32;
+    let moved = harness.translation(player).unwrap() - start;
+    assert!((moved.x - expected).abs() < 0.01, "moved {moved}");
+    assert_eq!(moved.y, 0.0);
+}
+
+#[test]
+fn back_key_pauses_the_run() {
+    let mut harness = SimulationHarness::empty_run();
+
+    harness.tick().press(KeyCode::Escape).ticks(2);
+
+    assert_eq!(harness.game_state(), GameState::Paused);
+}
+
+#[test]
+fn paused_run_does_not_move() {
+    let mut harness = SimulationHarness::new();
+    // The simulation ticks once more in the frame the pause key is read.
+    harness.start_run().tick().press(KeyCode::Space).tick();
+    let enemies = harness.enemies();
+    let before: Vec<_> = enemies
+        .iter()
+        .map(|enemy| harness.translation(*enemy))
+        .collect();
+
+    harness.ticks(5);
+
+    let after: Vec<_> = enemies
+        .iter()
+        .map(|enemy| harness.translation(*enemy))
+        .collect();
+    assert_eq!(harness.game_state(), GameState::Paused);
+    assert_eq!(before, after);
+}
+
+#[test]
+fn same_seed_plays_the_same_run() {
+    let positions = |seed: u64| {
+        let mut harness = SimulationHarness::new();
+        harness
+            .seed(seed)
+            .start_run()
+            .press(KeyCode::ArrowUp)
+            .ticks(120);
+        let mut enemies: Vec<_> = harness
+            .enemies()
+            .into_iter()
+            .map(|enemy| harness.translation(enemy).unwrap().to_array())
+            .collect();
+        enemies.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        (enemies, harness.score())
+    };
+
+    assert_eq!(positions(42), positions(42));
+    assert_ne!(positions(42), positions(43));
+}
@@ -0,0 +1,228 @@
+use std::fs;
+
+use bevy::input::keyboard::{Key, KeyboardInput};
+use bevy::input::ButtonState;
+use bevy::prelude::{Entity, Events, KeyCode, NextState};
+
+use bevy_ball::game::high_score::{
+    HighScore, HighScoreEntry, HighScoreNameEntered, LastRun, PlayerNameError, HIGH_SCORE_FILE_NAME,
+};
+use bevy_ball::harness::SimulationHarness;
+use bevy_ball::storage::{StorageDirectory, StorageError};
+use bevy_ball::ui::UIPlugin;
+use bevy_ball::ApplicationState;
+
+#[test]
+fn missing_file_loads_nothing() {
+    let directory = tempfile::tempdir().unwrap();
+    let storage = StorageDirectory::new(directory.path());
+
+    let loaded = HighScore::load_from(&storage.file(HIGH_SCORE_FILE_NAME)).unwrap();
+
+    assert!(loaded.is_none());
+}
+
+#[test]
+fn saved_high_score_loads_back() {
+    let directory = tempfile::tempdir().unwrap();
+    let storage = StorageDirectory::new(directory.path().join("nested"));
+    let path = storage.file(HIGH_SCORE_FILE_NAME);
+    let mut high_score = HighScore::default();
+    high_score.insert(HighScoreEntry::new("Alice", 12, 100));
+    high_score.insert(HighScoreEntry::new("Bob", 3, 200));
+
+    high_score.save_to(&path).unwrap();
+    let loaded = HighScore::load_from(&path).unwrap().unwrap();
+
+    assert_eq!(loaded.entries(), high_score.entries());
+    assert!(!path.with_extension("tmp").exists());
+}
+
+#[test]
+fn version_1_file_is_migrated() {
+    let directory = tempfile::tempdir().unwrap();
+    let path = StorageDirectory::new(directory.path()).file(HIGH_SCORE_FILE_NAME);
+    fs::write(
+        &path,
+        "(version: 1, data: (scores: [(\"Alice\", 3), (\"Bob\", 7), (\"Carol\", 3)]))",
+    )
+    .unwrap();
+
+    let loaded = HighScore::load_from(&path).unwrap().unwrap();
+
+    let names: Vec<&str> = loaded
+        .entries()
+        .iter()
+        .map(|entry| entry.name.as_str())
+        .collect();
+    assert_eq!(names, ["Bob", "Alice", "Carol"]);
+}
+
+#[test]
+fn corrupt_file_is_an_error() {
+    let directory = tempfile::tempdir().unwrap();
+    let path = StorageDirectory::new(directory.path()).file(HIGH_SCORE_FILE_NAME);
+    fs::write(&path, "(version: 2, data: (entries: [(name: \"Alice\"").unwrap();
+
+    let loaded = HighScore::load_from(&path);
+
+    assert!(matches!(loaded, Err(StorageError::Parse(_))));
+}
+
+#[test]
+fn newer_version_is_rejected() {
+    let directory = tempfile::tempdir().unwrap();
+    let path = StorageDirectory::new(directory.path()).file(HIGH_SCORE_FILE_NAME);
+    fs::write(&path, "(version: 99, data: (entries: []))").unwrap();
+
+    let loaded = HighScore::load_from(&path);
+
+    assert!(matches!(
+        loaded,
+        Err(StorageError::UnsupportedVersion { found: 99, .. })
+    ));
+}
+
+#[test]
+fn entries_are_sorted_and_ties_go_to_the_earlier_run() {
+    let mut high_score = HighScore::default();
+
+    assert_eq!(
+        high_score.insert(HighScoreEntry::new("Alice", 5, 30)),
+        Some(1)
+    );
+    assert_eq!(
+        high_score.insert(HighScoreEntry::new("Bob", 9, 20)),
+        Some(1)
+    );
+    assert_eq!(
+        high_score.insert(HighScoreEntry::new("Carol", 5, 10)),
+        Some(2)
+    );
+    assert_eq!(
+        high_score.insert(HighScoreEntry::new("Dave", 5, 40)),
+        Some(4)
+    );
+
+    let names: Vec<&str> = high_score
+        .entries()
+        .iter()
+        .map(|entry| entry.name.as_str())
+        .collect();
+    assert_eq!(names, ["Bob", "Carol", "Alice", "Dave"]);
+}
+
+#[test]
+fn table_is_capped() {
+    let mut high_score = HighScore::with_capacity(2);
+    high_score.insert(HighScoreEntry::new("Alice", 5, 1));
+    high_score.insert(HighScoreEntry::new("Bob", 9, 2));
+
+    assert_eq!(high_score.rank_of(1, 3), None);
+    assert_eq!(high_score.insert(HighScoreEntry::new("Carol", 1, 3)), None);
+    assert_eq!(
+        high_score.insert(HighScoreEntry::new("Dave", 7, 4)),
+        Some(2)
+    );
+
+    let scores: Vec<u32> = high_score
+        .entries()
+        .iter()
+        .map(|entry| entry.score)
+        .collect();
+    assert_eq!(scores, [9, 7]);
+}
+
+#[test]
+fn personal_best_is_per_name() {
+    let mut high_score = HighScore::default();
+    high_score.insert(HighScoreEntry::new("Alice", 5, 1));
+    high_score.insert(HighScoreEntry::new("Bob", 9, 2));
+
+    assert!(high_score.is_personal_best("Alice", 6));
+    assert!(!high_score.is_personal_best("Alice", 5));
+    assert!(!high_score.is_personal_best("Bob", 6));
+    assert!(high_score.is_personal_best("Carol", 1));
+}
+
+#[test]
+fn player_names_are_validated() {
+    assert_eq!(
+        HighScoreEntry::validate_name("  Alice_01 "),
+        Ok("Alice_01".to_string())
+    );
+    assert_eq!(
+        HighScoreEntry::validate_name("   "),
+        Err(PlayerNameError::Empty)
+    );
+    assert_eq!(
+        HighScoreEntry::validate_name("Alice!"),
+        Err(PlayerNameError::InvalidCharacter('!'))
+    );
+    assert_eq!(
+        HighScoreEntry::validate_name("ThirteenChars"),
+        Err(PlayerNameError::TooLong)
+    );
+}
+
+#[test]
+fn run_confirmed_twice_is_recorded_once() {
+    let mut harness = SimulationHarness::new();
+    harness.tick();
+    *harness.world_mut().resource_mut::<LastRun>() = LastRun {
+        score: 7,
+        timestamp: 1,
+        rank: Some(1),
+        ..LastRun::default()
+    };
+
+    for _ in 0..2 {
+        harness.world_mut().send_event(HighScoreNameEntered {
+            name: "Alice".to_string(),
+        });
+    }
+    harness.tick();
+    harness.world_mut().send_event(HighScoreNameEntered {
+        name: "Alice".to_string(),
+    });
+    harness.tick();
+
+    let high_score = harness.world().resource::<HighScore>();
+    assert_eq!(high_score.entries().len(), 1);
+}
+
+fn type_key(harness: &mut SimulationHarness, logical_key: Key) {
+    harness.world_mut().send_event(KeyboardInput {
+        key_code: KeyCode::KeyA,
+        logical_key,
+        state: ButtonState::Pressed,
+        repeat: false,
+        window: Entity::PLACEHOLDER,
+    });
+}
+
+#[test]
+fn keys_pressed_before_the_name_entry_are_not_typed() {
+    let mut harness = SimulationHarness::new();
+    harness.app().add_plugins(UIPlugin);
+    harness.start_run();
+
+    // Typed on the frame the run ends, before the menu shows up.
+    type_key(&mut harness, Key::Character("a".into()));
+    harness
+        .world_mut()
+        .resource_mut::<NextState<ApplicationState>>()
+        .set(ApplicationState::NameEntry);
+    harness.tick();
+    type_key(&mut harness, Key::Character("b".into()));
+    type_key(&mut harness, Key::Enter);
+    harness.tick();
+
+    let events = harness.world().resource::<Events<HighScoreNameEntered>>();
+    let mut cursor = events.get_cursor();
+    let names: Vec<_> = cursor
+        .read(events)
+        .map(|event| event.name.as_str())
+        .collect();
+    assert_eq!(names, ["b"]);
+}
@@ -0,0 +1,164 @@
+use std::fs;
+
+use bevy::prelude::KeyCode;
+
+use bevy_ball::game::GameState;
+use bevy_ball::harness::SimulationHarness;
+use bevy_ball::input_bindings::{InputAction, InputBindings, INPUT_BINDINGS_FILE_NAME};
+use bevy_ball::storage::{StorageDirectory, StorageError};
+
+fn rebind(harness: &mut SimulationHarness, action: InputAction, key: KeyCode) {
+    harness
+        .world_mut()
+        .resource_mut::<InputBindings>()
+        .rebind(action, key);
+}
+
+#[test]
+fn every_action_has_a_default_key() {
+    let input_bindings = InputBindings::default();
+
+    for action in InputAction::ALL {
+        assert!(!input_bindings.keys(action).is_empty(), "{:?}", action);
+    }
+}
+
+#[test]
+fn rebinding_a_key_takes_it_from_its_previous_action() {
+    let mut input_bindings = InputBindings::default();
+
+    input_bindings.rebind(InputAction::Pause, KeyCode::KeyW);
+
+    assert_eq!(input_bindings.keys(InputAction::Pause), [KeyCode::KeyW]);
+    assert_eq!(input_bindings.keys(InputAction::MoveUp), [KeyCode::ArrowUp]);
+    assert_eq!(
+        input_bindings.action_of(KeyCode::Space),
+        None,
+        "the previous pause key is free"
+    );
+}
+
+#[test]
+fn saved_bindings_load_back() {
+    let directory = tempfile::tempdir().unwrap();
+    let path = StorageDirectory::new(directory.path()).file(INPUT_BINDINGS_FILE_NAME);
+    let mut input_bindings = InputBindings::default();
+    input_bindings.rebind(InputAction::MoveLeft, KeyCode::KeyJ);
+
+    assert!(InputBindings::load_from(&path).unwrap().is_none());
+    input_bindings.save_to(&path).unwrap();
+    let loaded = InputBindings::load_from(&path).unwrap().unwrap();
+
+    assert_eq!(loaded, input_bindings);
+}
+
+#[test]
+fn actions_missing_from_the_file_get_their_default_keys() {
+    let directory = tempfile::tempdir().unwrap();
+    let path = StorageDirectory::new(directory.path()).file(INPUT_BINDINGS_FILE_NAME);
+    fs::write(&path, "(version: 1, data: (bindings: {MoveUp: [KeyI]}))").unwrap();
+
+    let loaded = InputBindings::load_from(&path).unwrap().unwrap();
+
+    assert_eq!(loaded.keys(InputAction::MoveUp), [KeyCode::KeyI]);
+    assert_eq!(
+        loaded.keys(InputAction::Pause),
+        InputAction::Pause.default_keys()
+    );
+}
+
+#[test]
+fn quit_binding_saved_before_menu_navigation_loads_as_back() {
+    let directory = tempfile::tempdir().unwrap();
+    let path = StorageDirectory::new(directory.path()).file(INPUT_BINDINGS_FILE_NAME);
+    fs::write(&path, "(version: 1, data: (bindings: {Quit: [KeyQ]}))").unwrap();
+
+    let loaded = InputBindings::load_from(&path).unwrap().unwrap();
+
+    assert_eq!(loaded.keys(InputAction::Back), [KeyCode::KeyQ]);
+    assert_eq!(
+        loaded.keys(InputAction::Confirm),
+        InputAction::Confirm.default_keys()
+    );
+}
+
+#[test]
+fn gamepad_bindings_saved_before_menu_navigation_get_the_default_buttons() {
+    let directory = tempfile::tempdir().unwrap();
+    let path = StorageDirectory::new(directory.path()).file(INPUT_BINDINGS_FILE_NAME);
+    fs::write(
+        &path,
+        "(version: 1, data: (bindings: {Quit: [Escape]}, gamepad_bindings: {Quit: [], Pause: [Start]}))",
+    )
+    .unwrap();
+
+    let loaded = InputBindings::load_from(&path).unwrap().unwrap();
+
+    assert_eq!(
+        loaded.buttons(InputAction::Back),
+        InputAction::Back.default_buttons()
+    );
+    assert_eq!(
+        loaded.buttons(InputAction::Confirm),
+        InputAction::Confirm.default_buttons()
+    );
+}
+
+#[test]
+fn newer_version_is_rejected() {
+    let directory = tempfile::tempdir().unwrap();
+    let path = StorageDirectory::new(directory.path()).file(INPUT_BINDINGS_FILE_NAME);
+    fs::write(&path, "(version: 99, data: (bindings: {}))").unwrap();
+
+    let loaded = InputBindings::load_from(&path);
+
+    assert!(matches!(
+        loaded,
+        Err(StorageError::UnsupportedVersion { found: 99, .. })
+    ));
+}
+
+#[test]
+fn saved_bindings_are_used_from_startup() {
+    let directory = tempfile::tempdir().unwrap();
+    let storage = StorageDirectory::new(directory.path());
+    let mut input_bindings = InputBindings::default();
+    input_bindings.rebind(InputAction::Pause, KeyCode::KeyP);
+    input_bindings
+        .save_to(&storage.file(INPUT_BINDINGS_FILE_NAME))
+        .unwrap();
+
+    let harness = SimulationHarness::with_storage(directory.path());
+
+    assert_eq!(*harness.world().resource::<InputBindings>(), input_bindings);
+}
+
+#[test]
+fn rebound_keys_move_the_player() {
+    let mut harness = SimulationHarness::empty_run();
+    rebind(&mut harness, InputAction::MoveRight, KeyCode::KeyL);
+    let player = harness.player().unwrap();
+    let start = harness.translation(player).unwrap();
+
+    harness.tick().press(KeyCode::ArrowRight).ticks(5);
+    assert_eq!(harness.translation(player), Some(start));
+
+    harness
+        .release(KeyCode::ArrowRight)
+        .press(KeyCode::KeyL)
+        .ticks(5);
+    assert!(harness.translation(player).unwrap().x > start.x);
+}
+
+#[test]
+fn rebound_pause_key_pauses_the_run() {
+    let mut harness = SimulationHarness::new();
+    harness.start_run().tick();
+    rebind(&mut harness, InputAction::Pause, KeyCode::KeyP);
+
+    harness.press(KeyCode::Space).tick();
+    assert_eq!(harness.game_state(), GameState::Running);
+
+    harness.press(KeyCode::KeyP).ticks(2);
+    assert_eq!(harness.game_state(), GameState::Paused);
+}
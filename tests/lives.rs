@@ -0,0 +1,161 @@
+use bevy::math::Vec3;
+use bevy::prelude::Visibility;
+
+use bevy_ball::game::enemy_behaviour::EnemyKind;
+use bevy_ball::game::lives::{Invulnerable, LostLife};
+use bevy_ball::game::player::CollidedWithEnemy;
+use bevy_ball::game::simulation::{InterpolatedTransform, TickRate};
+use bevy_ball::game::GameState;
+use bevy_ball::harness::SimulationHarness;
+
+/// Move the player off the center and drop an enemy on it
+fn hit_player_off_center(harness: &mut SimulationHarness) {
+    let player = harness.player().unwrap();
+    let hit_translation = harness.arena().center() - Vec3::new(400.0, 0.0, 0.0);
+    harness.teleport(player, hit_translation);
+    harness.spawn_enemy(hit_translation, Vec3::X);
+}
+
+fn is_invulnerable(harness: &mut SimulationHarness) -> bool {
+    let player = harness.player().unwrap();
+    harness.world().get::<Invulnerable>(player).is_some()
+}
+
+#[test]
+fn run_starts_with_the_configured_lives() {
+    let harness = SimulationHarness::empty_run();
+
+    assert_eq!(harness.lives(), Some(harness.config().player.lives));
+}
+
+#[test]
+fn hit_costs_a_life_and_respawns_the_player_at_the_center() {
+    let mut harness = SimulationHarness::empty_run();
+    harness.count_events::<LostLife>();
+    harness.count_events::<CollidedWithEnemy>();
+    let lives = harness.lives().unwrap();
+    hit_player_off_center(&mut harness);
+
+    harness.tick();
+
+    let player = harness.player().unwrap();
+    assert_eq!(harness.lives(), Some(lives - 1));
+    assert_eq!(harness.event_count::<LostLife>(), 1);
+    assert_eq!(harness.event_count::<CollidedWithEnemy>(), 0);
+    assert_eq!(harness.game_state(), GameState::Running);
+    assert_eq!(harness.translation(player), Some(harness.arena().center()));
+    assert!(is_invulnerable(&mut harness));
+}
+
+#[test]
+fn invulnerable_player_goes_through_enemies() {
+    let mut harness = SimulationHarness::empty_run();
+    hit_player_off_center(&mut harness);
+    harness.tick();
+    let lives = harness.lives();
+
+    let center = harness.arena().center();
+    harness.spawn_enemy(center, Vec3::X);
+    harness.ticks(2);
+
+    assert_eq!(harness.lives(), lives);
+}
+
+#[test]
+fn invulnerability_wears_off() {
+    let mut harness = SimulationHarness::empty_run();
+    hit_player_off_center(&mut harness);
+    harness.tick();
+
+    let invulnerability_ticks =
+        (harness.config().player.invulnerability_time as f64 * TickRate::default().hz) as usize;
+    // Enemies spawning meanwhile could hit the player right as it wears off, whatever the seed.
+    for _ in 0..=invulnerability_ticks {
+        harness.clear_enemies().tick();
+    }
+
+    let player = harness.player().unwrap();
+    assert!(!is_invulnerable(&mut harness));
+    assert_eq!(
+        harness.world().get::<Visibility>(player),
+        Some(&Visibility::Inherited)
+    );
+}
+
+#[test]
+fn respawn_pushes_nearby_enemies_away() {
+    let mut harness = SimulationHarness::empty_run();
+    let center = harness.arena().center();
+    let radius = harness.config().player.respawn_clear_radius;
+    let near = harness.spawn_enemy(center + Vec3::new(60.0, 0.0, 0.0), Vec3::X);
+    let far = harness.spawn_enemy(center + Vec3::new(0.0, radius + 50.0, 0.0), Vec3::X);
+    hit_player_off_center(&mut harness);
+
+    harness.tick();
+
+    let near_distance = harness.translation(near).unwrap().distance(center);
+    let far_offset = harness.translation(far).unwrap() - center;
+    assert!(
+        near_distance >= radius - 1e-3,
+        "near enemy at {near_distance}"
+    );
+    assert!((far_offset.y - (radius + 50.0)).abs() < 1e-3);
+}
+
+#[test]
+fn respawn_pushes_enemies_without_drawing_them_sliding() {
+    let mut harness = SimulationHarness::empty_run();
+    let center = harness.arena().center();
+    let near = harness.spawn_enemy(center + Vec3::new(60.0, 0.0, 0.0), Vec3::X);
+    hit_player_off_center(&mut harness);
+
+    harness.tick();
+
+    let interpolated_transform = harness.world().get::<InterpolatedTransform>(near).unwrap();
+    assert_eq!(
+        interpolated_transform.previous,
+        interpolated_transform.current
+    );
+}
+
+#[test]
+fn respawn_pushes_orbiting_enemies_away_with_their_orbit() {
+    let mut harness = SimulationHarness::empty_run();
+    let center = harness.arena().center();
+    let radius = harness.config().player.respawn_clear_radius;
+    let orbiter = harness.spawn_enemy_of_kind(
+        EnemyKind::Orbiter,
+        center + Vec3::new(60.0, 0.0, 0.0),
+        Vec3::X,
+    );
+    hit_player_off_center(&mut harness);
+
+    harness.ticks(2);
+
+    let distance = harness.translation(orbiter).unwrap().distance(center);
+    assert!(distance >= radius * 0.9, "orbiter at {distance}");
+}
+
+#[test]
+fn hit_on_the_last_life_ends_the_run() {
+    let mut harness = SimulationHarness::empty_run();
+    harness.count_events::<LostLife>();
+    harness.count_events::<CollidedWithEnemy>();
+    harness.set_lives(2);
+    hit_player_off_center(&mut harness);
+    harness.tick();
+    harness.clear_enemies();
+    let invulnerability_ticks =
+        (harness.config().player.invulnerability_time as f64 * TickRate::default().hz) as usize;
+    // Enemies spawning meanwhile could hit the player right as it wears off, whatever the seed.
+    for _ in 0..=invulnerability_ticks {
+        harness.clear_enemies().tick();
+    }
+
+    hit_player_off_center(&mut harness);
+    harness.ticks(2);
+
+    assert_eq!(harness.event_count::<LostLife>(), 1);
+    assert_eq!(harness.event_count::<CollidedWithEnemy>(), 1);
+    assert!(harness.player().is_none());
+}
@@ -0,0 +1,136 @@
+use bevy::math::Vec3;
+use bevy::prelude::{Component, Timer, TimerMode};
+
+use bevy_ball::game::player::CollidedWithEnemy;
+use bevy_ball::game::power_up::{
+    Magnet, PowerUpEffect, PowerUpKind, ScoreMultiplier, Shield, SlowMotion,
+};
+use bevy_ball::game::simulation::TickRate;
+use bevy_ball::game::GameState;
+use bevy_ball::harness::SimulationHarness;
+
+fn give_player(harness: &mut SimulationHarness, effect: impl Component) {
+    let player = harness.player().unwrap();
+    harness.world_mut().entity_mut(player).insert(effect);
+}
+
+fn timer(seconds: f32) -> Timer {
+    Timer::from_seconds(seconds, TimerMode::Once)
+}
+
+fn has_effect<E: PowerUpEffect>(harness: &mut SimulationHarness) -> bool {
+    let player = harness.player().unwrap();
+    harness.world().get::<E>(player).is_some()
+}
+
+#[test]
+fn picking_up_a_power_up_gives_its_effect() {
+    let mut harness = SimulationHarness::empty_run();
+    let player = harness.player().unwrap();
+    let player_translation = harness.translation(player).unwrap();
+    let power_up = harness.spawn_power_up(PowerUpKind::Magnet, player_translation);
+
+    harness.tick();
+
+    assert!(has_effect::<Magnet>(&mut harness));
+    assert!(harness.world().get_entity(power_up).is_err());
+}
+
+#[test]
+fn shield_absorbs_a_single_enemy_hit() {
+    let mut harness = SimulationHarness::empty_run();
+    harness.set_lives(1);
+    harness.count_events::<CollidedWithEnemy>();
+    give_player(&mut harness, Shield { timer: timer(10.0) });
+    let player = harness.player().unwrap();
+    let player_translation = harness.translation(player).unwrap();
+    let enemy = harness.spawn_enemy(player_translation, Vec3::X);
+
+    harness.ticks(2);
+
+    assert_eq!(harness.event_count::<CollidedWithEnemy>(), 0);
+    assert_eq!(harness.game_state(), GameState::Running);
+    assert!(!has_effect::<Shield>(&mut harness));
+    assert!(harness.world().get_entity(enemy).is_err());
+
+    harness.spawn_enemy(player_translation, Vec3::X);
+    harness.ticks(2);
+
+    assert_eq!(harness.event_count::<CollidedWithEnemy>(), 1);
+}
+
+#[test]
+fn effects_wear_off() {
+    let mut harness = SimulationHarness::empty_run();
+    give_player(&mut harness, ScoreMultiplier { timer: timer(0.5) });
+
+    harness.ticks(TickRate::default().hz as usize / 4);
+    assert!(has_effect::<ScoreMultiplier>(&mut harness));
+
+    harness.ticks(TickRate::default().hz as usize / 2);
+    assert!(!has_effect::<ScoreMultiplier>(&mut harness));
+}
+
+#[test]
+fn score_multiplier_scores_more_per_star() {
+    let mut harness = SimulationHarness::empty_run();
+    give_player(&mut harness, ScoreMultiplier { timer: timer(10.0) });
+    let player = harness.player().unwrap();
+    let player_translation = harness.translation(player).unwrap();
+    harness.spawn_star(player_translation);
+
+    harness.tick();
+
+    assert_eq!(
+        harness.score(),
+        Some(harness.config().power_up.score_multiplier)
+    );
+}
+
+#[test]
+fn slow_motion_slows_enemies_down() {
+    let mut harness = SimulationHarness::empty_run();
+    let player = harness.player().unwrap();
+    let start = harness.translation(player).unwrap() + Vec3::new(0.0, 200.0, 0.0);
+    let enemy = harness.spawn_enemy(start, Vec3::X);
+
+    harness.tick();
+    let normal_step = harness.translation(enemy).unwrap().x - start.x;
+    give_player(&mut harness, SlowMotion { timer: timer(10.0) });
+    let before = harness.translation(enemy).unwrap().x;
+    harness.tick();
+    let slow_step = harness.translation(enemy).unwrap().x - before;
+
+    let expected = normal_step * harness.config().power_up.slow_motion_multiplier;
+    assert!(
+        (slow_step - expected).abs() < normal_step * 0.01,
+        "moved {slow_step}, expected {expected}"
+    );
+}
+
+#[test]
+fn magnet_pulls_stars_within_reach() {
+    let mut harness = SimulationHarness::empty_run();
+    give_player(&mut harness, Magnet { timer: timer(10.0) });
+    let player = harness.player().unwrap();
+    let player_translation = harness.translation(player).unwrap();
+    let radius = harness.config().power_up.magnet_radius;
+    let near = harness.spawn_star(player_translation + Vec3::new(radius - 10.0, 0.0, 0.0));
+    let far = harness.spawn_star(player_translation + Vec3::new(0.0, radius + 10.0, 0.0));
+
+    harness.tick();
+
+    let near_distance = harness
+        .translation(near)
+        .unwrap()
+        .distance(player_translation);
+    let far_distance = harness
+        .translation(far)
+        .unwrap()
+        .distance(player_translation);
+    assert!(
+        near_distance < radius - 10.0,
+        "near star at {near_distance}"
+    );
+    assert!((far_distance - (radius + 10.0)).abs() < 1e-3);
+}
@@ -0,0 +1,174 @@
+use bevy::app::FixedUpdate;
+use bevy::prelude::{
+    EventReader, IntoSystemConfigs, KeyCode, Or, Query, ResMut, Resource, Transform, With,
+};
+
+use bevy_ball::game::config::GameConfig;
+use bevy_ball::game::enemy::Enemy;
+use bevy_ball::game::input::PlayerInput;
+use bevy_ball::game::player::{CollidedWithEnemy, Player};
+use bevy_ball::game::replay::{ActiveReplay, Replay, ReplayMode, REPLAY_FILE_NAME};
+use bevy_ball::game::simulation::SimulationSet;
+use bevy_ball::harness::SimulationHarness;
+use bevy_ball::storage::StorageDirectory;
+
+const TICK_RATE: f64 = 60.0;
+
+const STEERING: [KeyCode; 4] = [
+    KeyCode::ArrowRight,
+    KeyCode::ArrowUp,
+    KeyCode::ArrowLeft,
+    KeyCode::ArrowDown,
+];
+const TICKS_PER_KEY: usize = 40;
+const MAX_RUN_TICKS: usize = 60 * 60 * 5;
+
+fn right() -> PlayerInput {
+    PlayerInput { x: 1.0, y: 0.0 }
+}
+
+/// Score and translations of the player and enemies on the tick the run ended
+#[derive(Resource, Debug, Default, PartialEq)]
+struct RunEnd {
+    score: Option<u32>,
+    translations: Vec<[f32; 3]>,
+}
+
+type PlayerOrEnemy = Or<(With<Player>, With<Enemy>)>;
+
+fn record_run_end(
+    mut collided_with_enemy_event_reader: EventReader<CollidedWithEnemy>,
+    mut run_end: ResMut<RunEnd>,
+    query: Query<&Transform, PlayerOrEnemy>,
+) {
+    let Some(collided_with_enemy) = collided_with_enemy_event_reader.read().next() else {
+        return;
+    };
+    if run_end.score.is_some() {
+        return;
+    }
+
+    run_end.score = Some(collided_with_enemy.score);
+    run_end.translations = query
+        .iter()
+        .map(|transform| transform.translation.to_array())
+        .collect();
+    run_end
+        .translations
+        .sort_by(|a, b| a.partial_cmp(b).unwrap());
+}
+
+/// Harness with a single life, which records how its next run ends
+fn one_life_harness() -> SimulationHarness {
+    let mut harness = SimulationHarness::new();
+    harness
+        .world_mut()
+        .resource_mut::<GameConfig>()
+        .player
+        .lives = 1;
+    harness
+        .app()
+        .init_resource::<RunEnd>()
+        .add_systems(FixedUpdate, record_run_end.after(SimulationSet::Collision));
+    harness
+}
+
+fn run_end(harness: &SimulationHarness) -> &RunEnd {
+    harness.world().resource::<RunEnd>()
+}
+
+#[test]
+fn repeated_inputs_share_a_span() {
+    let mut replay = Replay::new(7, TICK_RATE, &GameConfig::default());
+    replay.push(right());
+    replay.push(right());
+    replay.push(PlayerInput::default());
+
+    assert_eq!(replay.spans.len(), 2);
+    assert_eq!(replay.spans[0].ticks, 2);
+    assert_eq!(replay.tick_count(), 3);
+}
+
+#[test]
+fn playback_returns_every_recorded_tick_in_order() {
+    let mut replay = Replay::new(7, TICK_RATE, &GameConfig::default());
+    let recorded = [
+        right(),
+        right(),
+        PlayerInput::default(),
+        PlayerInput { x: 0.0, y: -1.0 },
+    ];
+    for input in recorded {
+        replay.push(input);
+    }
+
+    let mut active_replay = ActiveReplay::new(replay);
+    let played: Vec<_> = std::iter::from_fn(|| active_replay.next_input()).collect();
+
+    assert_eq!(played, recorded);
+}
+
+#[test]
+fn saved_replay_loads_back() {
+    let directory = tempfile::tempdir().unwrap();
+    let path = StorageDirectory::new(directory.path()).file(REPLAY_FILE_NAME);
+    let mut replay = Replay::new(u64::MAX, TICK_RATE, &GameConfig::default());
+    replay.push(right());
+    replay.final_score = Some(3);
+
+    replay.save_to(&path).unwrap();
+    let loaded = Replay::load_from(&path).unwrap().unwrap();
+
+    assert_eq!(loaded, replay);
+}
+
+#[test]
+fn played_back_run_ends_like_the_recorded_one() {
+    let mut recording = one_life_harness();
+    recording.start_run();
+    for key_code in STEERING.iter().cycle().take(MAX_RUN_TICKS / TICKS_PER_KEY) {
+        recording
+            .press(*key_code)
+            .ticks(TICKS_PER_KEY)
+            .release(*key_code);
+        if run_end(&recording).score.is_some() {
+            break;
+        }
+    }
+    assert!(
+        run_end(&recording).score.is_some(),
+        "the recorded run never ended"
+    );
+    recording.ticks(2);
+    let path = recording
+        .world()
+        .resource::<StorageDirectory>()
+        .file(REPLAY_FILE_NAME);
+    let replay = Replay::load_from(&path).unwrap().unwrap();
+    assert!(replay.is_recorded_with(recording.config()));
+
+    let mut playback = one_life_harness();
+    playback
+        .world_mut()
+        .insert_resource(ReplayMode::Playback(replay));
+    playback.start_run();
+    // The replay holds ticks, not frames.
+    let ended = (0..MAX_RUN_TICKS).any(|_| {
+        playback.long_frame(3);
+        run_end(&playback).score.is_some()
+    });
+
+    assert!(ended, "the played back run never ended");
+    assert_eq!(run_end(&playback), run_end(&recording));
+}
+
+#[test]
+fn replay_knows_the_tuning_it_was_recorded_with() {
+    let mut config = GameConfig::default();
+    let replay = Replay::new(7, TICK_RATE, &config);
+
+    config.player.speed += 1.0;
+
+    assert!(replay.is_recorded_with(&GameConfig::default()));
+    assert!(!replay.is_recorded_with(&config));
+}
@@ -0,0 +1,172 @@
+use std::fs;
+
+use bevy::audio::{AudioPlayer, PlaybackSettings};
+use bevy::math::Vec3;
+use bevy::prelude::{AudioSource, Entity, Handle, Transform, With};
+
+use bevy_ball::arena::ArenaCamera;
+use bevy_ball::harness::SimulationHarness;
+use bevy_ball::settings::{Music, Settings, SteeringMode, SETTINGS_FILE_NAME};
+use bevy_ball::storage::{StorageDirectory, StorageError};
+
+/// Run with nothing in the arena but the player, about to lose a life
+fn run_about_to_lose_a_life(screen_shake: f32) -> SimulationHarness {
+    let mut harness = SimulationHarness::new();
+    harness.world_mut().resource_mut::<Settings>().screen_shake = screen_shake;
+    harness.start_run().clear_enemies().clear_stars();
+
+    let player = harness.player().unwrap();
+    let hit_translation = harness.arena().center() - Vec3::new(400.0, 0.0, 0.0);
+    harness.teleport(player, hit_translation);
+    harness.spawn_enemy(hit_translation, Vec3::X);
+    harness
+}
+
+fn camera_translation(harness: &mut SimulationHarness) -> Vec3 {
+    let world = harness.world_mut();
+    let mut camera_query = world.query_filtered::<&Transform, With<ArenaCamera>>();
+    camera_query.single(world).translation
+}
+
+#[test]
+fn saved_settings_load_back() {
+    let directory = tempfile::tempdir().unwrap();
+    let path = StorageDirectory::new(directory.path()).file(SETTINGS_FILE_NAME);
+    let settings = Settings {
+        master_volume: 0.5,
+        fullscreen: true,
+        vsync: false,
+        steering_mode: SteeringMode::Pointer,
+        ..Settings::default()
+    };
+
+    assert!(Settings::load_from(&path).unwrap().is_none());
+    settings.save_to(&path).unwrap();
+    let loaded = Settings::load_from(&path).unwrap().unwrap();
+
+    assert_eq!(loaded, settings);
+}
+
+#[test]
+fn out_of_range_values_are_clamped_on_load() {
+    let directory = tempfile::tempdir().unwrap();
+    let path = StorageDirectory::new(directory.path()).file(SETTINGS_FILE_NAME);
+    fs::write(
+        &path,
+        "(version: 1, data: (master_volume: 3.0, screen_shake: -1.0))",
+    )
+    .unwrap();
+
+    let loaded = Settings::load_from(&path).unwrap().unwrap();
+
+    assert_eq!(loaded.master_volume, 1.0);
+    assert_eq!(loaded.screen_shake, 0.0);
+    assert_eq!(loaded.music_volume, Settings::default().music_volume);
+}
+
+#[test]
+fn newer_version_is_rejected() {
+    let directory = tempfile::tempdir().unwrap();
+    let path = StorageDirectory::new(directory.path()).file(SETTINGS_FILE_NAME);
+    fs::write(&path, "(version: 99, data: ())").unwrap();
+
+    let loaded = Settings::load_from(&path);
+
+    assert!(matches!(
+        loaded,
+        Err(StorageError::UnsupportedVersion { found: 99, .. })
+    ));
+}
+
+#[test]
+fn saved_settings_are_used_from_startup() {
+    let directory = tempfile::tempdir().unwrap();
+    let storage = StorageDirectory::new(directory.path());
+    let settings = Settings {
+        sfx_volume: 0.3,
+        screen_shake: 0.0,
+        ..Settings::default()
+    };
+    settings.save_to(&storage.file(SETTINGS_FILE_NAME)).unwrap();
+
+    let harness = SimulationHarness::with_storage(directory.path());
+
+    assert_eq!(*harness.world().resource::<Settings>(), settings);
+}
+
+#[test]
+fn sound_volume_is_scaled_by_the_master_volume() {
+    let settings = Settings {
+        master_volume: 0.5,
+        sfx_volume: 0.8,
+        music_volume: 0.4,
+        ..Settings::default()
+    };
+
+    assert!((settings.volume(false) - 0.4).abs() < f32::EPSILON);
+    assert!((settings.volume(true) - 0.2).abs() < f32::EPSILON);
+}
+
+fn start_volume(harness: &SimulationHarness, sound: Entity) -> f32 {
+    harness
+        .world()
+        .get::<PlaybackSettings>(sound)
+        .unwrap()
+        .volume
+        .get()
+}
+
+#[test]
+fn music_starts_at_the_music_volume() {
+    let mut harness = SimulationHarness::new();
+    *harness.world_mut().resource_mut::<Settings>() = Settings {
+        master_volume: 0.5,
+        sfx_volume: 0.8,
+        music_volume: 0.4,
+        ..Settings::default()
+    };
+
+    let music = harness
+        .world_mut()
+        .spawn((AudioPlayer::<AudioSource>(Handle::default()), Music {}))
+        .id();
+    let sound_effect = harness
+        .world_mut()
+        .spawn(AudioPlayer::<AudioSource>(Handle::default()))
+        .id();
+
+    assert!((start_volume(&harness, music) - 0.2).abs() < f32::EPSILON);
+    assert!((start_volume(&harness, sound_effect) - 0.4).abs() < f32::EPSILON);
+}
+
+#[test]
+fn losing_a_life_shakes_the_camera() {
+    let mut harness = run_about_to_lose_a_life(1.0);
+    let center = harness.arena().center();
+
+    harness.tick();
+
+    assert_ne!(camera_translation(&mut harness), center);
+}
+
+#[test]
+fn camera_is_back_at_the_center_once_the_shake_wears_off() {
+    let mut harness = run_about_to_lose_a_life(1.0);
+    let center = harness.arena().center();
+
+    harness.ticks(60);
+
+    assert_eq!(camera_translation(&mut harness), center);
+}
+
+#[test]
+fn screen_shake_turned_off_keeps_the_camera_still() {
+    let mut harness = run_about_to_lose_a_life(0.0);
+    let center = harness.arena().center();
+    let lives = harness.lives().unwrap();
+
+    harness.tick();
+
+    assert_eq!(harness.lives(), Some(lives - 1));
+    assert_eq!(camera_translation(&mut harness), center);
+}
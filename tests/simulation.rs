@@ -0,0 +1,84 @@
+use std::time::Duration;
+
+use bevy::app::{App, FixedUpdate};
+use bevy::math::Vec3;
+use bevy::prelude::{Component, Query, Res, Time, Transform};
+use bevy::time::TimeUpdateStrategy;
+use bevy::MinimalPlugins;
+
+use bevy_ball::game::simulation::{InterpolatedTransform, SimulationPlugin, TickRate};
+
+#[derive(Component)]
+struct Velocity(Vec3);
+
+fn apply_velocity(time: Res<Time>, mut query: Query<(&mut Transform, &Velocity)>) {
+    for (mut transform, velocity) in query.iter_mut() {
+        transform.translation += velocity.0 * time.delta_secs();
+    }
+}
+
+/// Run one second of frames of `frame_time`, return the simulated and rendered translations
+fn run_one_second(frame_time: Duration) -> (Vec3, Vec3) {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugins(SimulationPlugin)
+        .insert_resource(TimeUpdateStrategy::ManualDuration(frame_time))
+        .add_systems(FixedUpdate, apply_velocity);
+    let entity = app
+        .world_mut()
+        .spawn((
+            Transform::from_xyz(10.0, 20.0, 0.0),
+            Velocity(Vec3::new(100.0, -50.0, 0.0)),
+            InterpolatedTransform::default(),
+        ))
+        .id();
+
+    // The first update only starts the clock.
+    let frames = (Duration::from_secs(1).as_secs_f64() / frame_time.as_secs_f64()) as usize;
+    for _ in 0..=frames {
+        app.update();
+    }
+
+    let world = app.world();
+    let simulated = world.get::<InterpolatedTransform>(entity).unwrap().current;
+    let rendered = world.get::<Transform>(entity).unwrap().translation;
+    (simulated, rendered)
+}
+
+#[test]
+fn new_entities_start_interpolating_from_their_spawn_translation() {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugins(SimulationPlugin);
+    let entity = app
+        .world_mut()
+        .spawn((
+            Transform::from_xyz(10.0, 20.0, 0.0),
+            InterpolatedTransform::default(),
+        ))
+        .id();
+
+    let interpolated_transform = app.world().get::<InterpolatedTransform>(entity).unwrap();
+
+    assert_eq!(interpolated_transform.previous, Vec3::new(10.0, 20.0, 0.0));
+    assert_eq!(interpolated_transform.current, Vec3::new(10.0, 20.0, 0.0));
+}
+
+#[test]
+fn simulation_does_not_depend_on_the_frame_rate() {
+    let (at_30_fps, _) = run_one_second(Duration::from_micros(33_333));
+    let (at_144_fps, _) = run_one_second(Duration::from_micros(6_944));
+    let (at_tick_rate, _) = run_one_second(Duration::from_secs_f64(1.0 / TickRate::default().hz));
+
+    assert_eq!(at_30_fps, at_144_fps);
+    assert_eq!(at_30_fps, at_tick_rate);
+}
+
+#[test]
+fn rendered_translation_stays_between_the_last_two_ticks() {
+    let (simulated, rendered) = run_one_second(Duration::from_micros(6_944));
+    let step = Vec3::new(100.0, -50.0, 0.0) / TickRate::default().hz as f32;
+
+    assert!(rendered.distance(simulated) <= step.length() + f32::EPSILON);
+    assert!(rendered.x <= simulated.x);
+}
@@ -0,0 +1,95 @@
+use bevy::math::Vec3;
+use bevy::prelude::{Entity, World};
+
+use bevy_ball::game::spatial_index::SpatialIndex;
+use bevy_ball::harness::SimulationHarness;
+
+fn entities<const N: usize>() -> [Entity; N] {
+    let mut world = World::new();
+    std::array::from_fn(|_| world.spawn_empty().id())
+}
+
+fn sorted(entities: impl Iterator<Item = Entity>) -> Vec<Entity> {
+    let mut entities: Vec<Entity> = entities.collect();
+    entities.sort();
+    entities
+}
+
+#[test]
+fn finds_only_the_units_in_reach() {
+    let [near, touching, far] = entities();
+    let mut spatial_index = SpatialIndex::new(64.0);
+    spatial_index.insert(near, Vec3::new(105.0, 100.0, 0.0), 20.0);
+    spatial_index.insert(touching, Vec3::new(130.0, 100.0, 0.0), 20.0);
+    spatial_index.insert(far, Vec3::new(400.0, 100.0, 0.0), 20.0);
+
+    let colliding = sorted(spatial_index.colliding_with(Vec3::new(100.0, 100.0, 0.0), 40.0));
+
+    assert_eq!(colliding, vec![near, touching]);
+}
+
+#[test]
+fn finds_big_units_across_cells() {
+    let [big] = entities();
+    let mut spatial_index = SpatialIndex::new(10.0);
+    spatial_index.insert(big, Vec3::new(100.0, 100.0, 0.0), 200.0);
+
+    let colliding = sorted(spatial_index.colliding_with(Vec3::new(190.0, 100.0, 0.0), 2.0));
+
+    assert_eq!(colliding, vec![big]);
+}
+
+#[test]
+fn updated_units_are_found_where_they_moved() {
+    let [unit] = entities();
+    let mut spatial_index = SpatialIndex::new(64.0);
+    spatial_index.insert(unit, Vec3::new(10.0, 10.0, 0.0), 20.0);
+
+    spatial_index.update(unit, Vec3::new(500.0, 500.0, 0.0));
+
+    assert_eq!(
+        spatial_index
+            .colliding_with(Vec3::new(10.0, 10.0, 0.0), 20.0)
+            .count(),
+        0
+    );
+    assert_eq!(
+        sorted(spatial_index.colliding_with(Vec3::new(500.0, 500.0, 0.0), 20.0)),
+        vec![unit]
+    );
+}
+
+#[test]
+fn removed_and_cleared_units_are_gone() {
+    let [first, second] = entities();
+    let mut spatial_index = SpatialIndex::new(64.0);
+    spatial_index.insert(first, Vec3::ZERO, 20.0);
+    spatial_index.insert(second, Vec3::ZERO, 20.0);
+
+    spatial_index.remove(first);
+    assert_eq!(
+        sorted(spatial_index.colliding_with(Vec3::ZERO, 20.0)),
+        vec![second]
+    );
+
+    spatial_index.clear();
+    assert!(spatial_index.is_empty());
+    assert_eq!(spatial_index.colliding_with(Vec3::ZERO, 20.0).count(), 0);
+}
+
+#[test]
+fn every_unit_of_a_run_is_indexed_each_tick() {
+    let mut harness = SimulationHarness::empty_run();
+    let enemy = harness.spawn_enemy(Vec3::new(100.0, 100.0, 0.0), Vec3::X);
+    let star = harness.spawn_star(Vec3::new(300.0, 100.0, 0.0));
+
+    harness.tick();
+
+    let player = harness.player().unwrap();
+    let spatial_index = harness.world().resource::<SpatialIndex>();
+    assert_eq!(spatial_index.len(), 3);
+    for unit in [player, enemy, star] {
+        let entry = spatial_index.get(unit).unwrap();
+        assert_eq!(Some(entry.translation), harness.translation(unit));
+    }
+}
@@ -0,0 +1,99 @@
+use bevy::math::Vec3;
+use bevy::prelude::KeyCode;
+
+use bevy_ball::game::simulation::TickRate;
+use bevy_ball::harness::SimulationHarness;
+use bevy_ball::settings::SteeringMode;
+
+/// A run with nothing in the arena but the player, steered by the pointer
+fn empty_pointer_run() -> SimulationHarness {
+    let mut harness = SimulationHarness::new();
+    harness
+        .set_steering_mode(SteeringMode::Pointer)
+        .start_run()
+        .clear_enemies()
+        .clear_stars();
+    harness
+}
+
+#[test]
+fn far_pointer_pulls_the_player_at_full_speed() {
+    let mut harness = empty_pointer_run();
+    let player = harness.player().unwrap();
+    let start = harness.tick().translation(player).unwrap();
+    let ticks = 10;
+
+    harness
+        .point_at(start + Vec3::new(0.0, 300.0, 0.0))
+        .ticks(ticks);
+
+    let expected = harness.config().player.speed * ticks as f32 / TickRate::default().hz as f32;
+    let moved = harness.translation(player).unwrap() - start;
+    assert!((moved.y - expected).abs() < 0.01, "moved {moved}");
+    assert!(moved.x.abs() < 1e-3);
+}
+
+#[test]
+fn player_slows_down_and_stops_on_the_pointer() {
+    let mut harness = empty_pointer_run();
+    let player = harness.player().unwrap();
+    let target = harness.translation(player).unwrap() + Vec3::new(80.0, 0.0, 0.0);
+    harness.point_at(target);
+
+    let mut steps = Vec::new();
+    let mut previous = harness.translation(player).unwrap();
+    for _ in 0..120 {
+        harness.tick();
+        let translation = harness.translation(player).unwrap();
+        assert!(translation.x <= target.x, "overshot to {translation}");
+        steps.push(translation.x - previous.x);
+        previous = translation;
+    }
+
+    let distance = target.distance(previous);
+    assert!(distance <= harness.config().pointer.dead_radius + 1e-3);
+    assert!(steps
+        .windows(2)
+        .skip(2)
+        .all(|pair| pair[1] <= pair[0] + 1e-4));
+}
+
+#[test]
+fn pointer_within_the_dead_radius_leaves_the_player_still() {
+    let mut harness = empty_pointer_run();
+    let player = harness.player().unwrap();
+    let start = harness.translation(player).unwrap();
+    let dead_radius = harness.config().pointer.dead_radius;
+
+    harness
+        .point_at(start + Vec3::new(dead_radius / 2.0, 0.0, 0.0))
+        .ticks(10);
+
+    assert_eq!(harness.translation(player), Some(start));
+}
+
+#[test]
+fn lifted_pointer_stops_the_player() {
+    let mut harness = empty_pointer_run();
+    let player = harness.player().unwrap();
+    let start = harness.translation(player).unwrap();
+    harness
+        .point_at(start + Vec3::new(300.0, 0.0, 0.0))
+        .ticks(5);
+
+    let before = harness.translation(player);
+    harness.lift_pointer().ticks(5);
+
+    assert_eq!(harness.translation(player), before);
+}
+
+#[test]
+fn keys_do_not_steer_in_pointer_mode() {
+    let mut harness = empty_pointer_run();
+    let player = harness.player().unwrap();
+    let start = harness.translation(player).unwrap();
+
+    harness.tick().press(KeyCode::ArrowRight).ticks(5);
+
+    assert_eq!(harness.translation(player), Some(start));
+}
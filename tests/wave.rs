@@ -0,0 +1,140 @@
+use bevy::math::Vec3;
+use bevy::prelude::{State, World};
+
+use bevy_ball::game::GameState;
+use bevy_ball::harness::SimulationHarness;
+
+fn is_in(state: GameState) -> impl Fn(&World) -> bool {
+    move |world: &World| world.resource::<State<GameState>>().get() == &state
+}
+
+fn score_a_star(harness: &mut SimulationHarness) {
+    let player = harness.player().unwrap();
+    let player_translation = harness.translation(player).unwrap();
+    harness.spawn_star(player_translation);
+    harness.tick();
+}
+
+#[test]
+fn run_starts_on_the_first_wave() {
+    let mut harness = SimulationHarness::new();
+    harness.start_run().tick();
+
+    assert_eq!(harness.wave(), Some(1));
+    assert_eq!(
+        harness.enemies().len(),
+        harness.config().wave(1).enemy_count
+    );
+    assert_eq!(harness.stars().len(), harness.config().wave(1).star_count);
+}
+
+#[test]
+fn score_threshold_starts_an_intermission() {
+    let mut harness = SimulationHarness::empty_run_with(|config| {
+        config.waves[0].duration = None;
+        config.waves[0].score = Some(1);
+    });
+
+    score_a_star(&mut harness);
+
+    assert!(harness.tick_until(2, is_in(GameState::Intermission)));
+    assert_eq!(harness.wave(), Some(2));
+}
+
+#[test]
+fn duration_threshold_starts_an_intermission() {
+    let mut harness = SimulationHarness::empty_run_with(|config| {
+        config.waves[0].duration = Some(0.5);
+        config.waves[0].score = None;
+    });
+
+    assert!(!harness.tick_until(25, is_in(GameState::Intermission)));
+    assert!(harness.tick_until(10, is_in(GameState::Intermission)));
+}
+
+#[test]
+fn intermission_holds_the_arena_still() {
+    let mut harness = SimulationHarness::empty_run_with(|config| {
+        config.waves[0].score = Some(1);
+        config.intermission_time = 1.0;
+    });
+    score_a_star(&mut harness);
+    harness.tick_until(2, is_in(GameState::Intermission));
+    let enemy = harness.spawn_enemy(harness.arena().center(), Vec3::X);
+    let before = harness.translation(enemy);
+
+    harness.ticks(30);
+
+    assert_eq!(harness.game_state(), GameState::Intermission);
+    assert_eq!(harness.translation(enemy), before);
+}
+
+#[test]
+fn next_wave_brings_its_enemies_and_stars() {
+    let mut harness = SimulationHarness::empty_run_with(|config| {
+        config.waves[0].score = Some(1);
+        config.intermission_time = 0.5;
+    });
+    score_a_star(&mut harness);
+
+    assert!(harness.tick_until(2, is_in(GameState::Intermission)));
+    assert!(harness.tick_until(40, is_in(GameState::Running)));
+    assert_eq!(harness.wave(), Some(2));
+    assert_eq!(
+        harness.enemies().len(),
+        harness.config().wave(2).enemy_count
+    );
+    assert_eq!(harness.stars().len(), harness.config().wave(2).star_count);
+}
+
+#[test]
+fn wave_ending_mid_frame_does_not_skip_the_next_one() {
+    let mut harness = SimulationHarness::empty_run_with(|config| {
+        config.waves[0].score = Some(1);
+        config.waves[1].score = Some(1);
+    });
+    let player = harness.player().unwrap();
+    harness.spawn_star(harness.translation(player).unwrap());
+
+    harness.long_frame(3).tick();
+
+    assert_eq!(harness.game_state(), GameState::Intermission);
+    assert_eq!(harness.wave(), Some(2));
+}
+
+/// Translation of an enemy once the first wave ended, on frames of `ticks_per_frame` ticks
+fn enemy_after_the_wave_ends(ticks_per_frame: u32) -> Vec3 {
+    let mut harness = SimulationHarness::empty_run_with(|config| {
+        config.waves[0].score = Some(1);
+    });
+    let player = harness.player().unwrap();
+    harness.spawn_star(harness.translation(player).unwrap());
+    let enemy = harness.spawn_enemy(
+        harness.arena().center() + Vec3::new(300.0, 200.0, 0.0),
+        Vec3::X,
+    );
+
+    harness.long_frame(ticks_per_frame).tick();
+
+    assert_eq!(harness.game_state(), GameState::Intermission);
+    harness.translation(enemy).unwrap()
+}
+
+#[test]
+fn wave_end_does_not_depend_on_the_frame_rate() {
+    assert_eq!(enemy_after_the_wave_ends(1), enemy_after_the_wave_ends(3));
+}
+
+#[test]
+fn last_wave_never_ends() {
+    let mut harness = SimulationHarness::empty_run_with(|config| {
+        config.waves.truncate(1);
+        config.waves[0].duration = None;
+        config.waves[0].score = None;
+    });
+
+    harness.ticks(120);
+
+    assert_eq!(harness.game_state(), GameState::Running);
+    assert_eq!(harness.wave(), Some(1));
+}